@@ -0,0 +1,108 @@
+use std::future::Future;
+use std::io::Result;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The async counterpart of [`BetterBufRead`][crate::BetterBufRead].
+///
+/// Only filling the buffer may need to wait on the source; once data is in
+/// the buffer, it can be read and consumed synchronously.
+pub trait AsyncBetterBufRead {
+  /// Attempts to fill the internal buffer with at least `n_bytes` if
+  /// possible, or as many as possible if the end of the file is reached.
+  ///
+  /// Like [`BetterBufRead::fill_or_eof`][crate::BetterBufRead::fill_or_eof],
+  /// this may return an IO error if `n_bytes` exceeds the current capacity
+  /// or if reading from the source fails.
+  fn poll_fill_or_eof(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    n_bytes: usize,
+  ) -> Poll<Result<()>>;
+  /// Returns all data available in memory.
+  ///
+  /// This may be smaller than the last `n_bytes` filled, but only if EOF was
+  /// reached.
+  fn buffer(&self) -> &[u8];
+  /// Advances by `n_bytes`, reducing the size of the available data to read.
+  ///
+  /// Panics if `n_bytes` is greater than the buffer's length.
+  fn consume(&mut self, n_bytes: usize);
+  /// Returns the capacity of the internal buffer, if one exists.
+  fn capacity(&self) -> Option<usize>;
+  /// Modifies capacity of the internal buffer, if one exists.
+  fn resize_capacity(&mut self, desired: usize);
+
+  /// Returns a future that fills the internal buffer with at least `n_bytes`
+  /// if possible, or as many as possible if the end of the file is reached.
+  fn fill_or_eof(&mut self, n_bytes: usize) -> FillOrEof<'_, Self>
+  where
+    Self: Unpin,
+  {
+    FillOrEof {
+      inner: self,
+      n_bytes,
+    }
+  }
+}
+
+/// The future returned by
+/// [`AsyncBetterBufRead::fill_or_eof`][crate::AsyncBetterBufRead::fill_or_eof].
+pub struct FillOrEof<'a, R: AsyncBetterBufRead + Unpin + ?Sized> {
+  inner: &'a mut R,
+  n_bytes: usize,
+}
+
+impl<R: AsyncBetterBufRead + Unpin + ?Sized> Future for FillOrEof<'_, R> {
+  type Output = Result<()>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+    let this = self.get_mut();
+    Pin::new(&mut *this.inner).poll_fill_or_eof(cx, this.n_bytes)
+  }
+}
+
+impl AsyncBetterBufRead for &[u8] {
+  #[inline]
+  fn poll_fill_or_eof(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+    _n_bytes: usize,
+  ) -> Poll<Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+
+  #[inline]
+  fn buffer(&self) -> &[u8] {
+    self
+  }
+
+  #[inline]
+  fn consume(&mut self, n_bytes: usize) {
+    *self = &self[n_bytes..];
+  }
+
+  #[inline]
+  fn capacity(&self) -> Option<usize> {
+    None
+  }
+
+  #[inline]
+  fn resize_capacity(&mut self, _desired: usize) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use futures::executor::block_on;
+
+  #[test]
+  fn test_slice_reads() {
+    let data = vec![0_u8, 1, 2, 3, 4, 5, 6, 7];
+    let mut slice = data.as_slice();
+    block_on(slice.fill_or_eof(33)).unwrap();
+    assert_eq!(AsyncBetterBufRead::buffer(&slice), &data);
+    AsyncBetterBufRead::consume(&mut slice, 3);
+    assert_eq!(AsyncBetterBufRead::buffer(&slice), &data[3..]);
+  }
+}
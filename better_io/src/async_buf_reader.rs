@@ -0,0 +1,195 @@
+use std::cmp::max;
+use std::io::{Error, ErrorKind, Result};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_io::AsyncRead;
+
+use crate::async_buf_read::AsyncBetterBufRead;
+
+const DEFAULT_CAPACITY: usize = 8192;
+
+/// An implementation of [`AsyncBetterBufRead`][crate::AsyncBetterBufRead]
+/// that wraps a generic `AsyncRead`.
+///
+/// This is the async counterpart of
+/// [`BetterBufReader`][crate::BetterBufReader].
+/// Use it to wrap things like network streams; tokio's readers can be
+/// adapted to `futures_io::AsyncRead` with `tokio_util::compat`.
+pub struct AsyncBetterBufReader<R: AsyncRead + Unpin> {
+  inner: R,
+  buffer: Vec<u8>,
+  desired_capacity: usize,
+  pos: usize,
+  filled: usize,
+}
+
+impl<R: AsyncRead + Unpin> AsyncBetterBufRead for AsyncBetterBufReader<R> {
+  fn poll_fill_or_eof(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    n_bytes: usize,
+  ) -> Poll<Result<()>> {
+    let this = self.get_mut();
+
+    // cycle the buffer if necessary
+    let unfilled = this.buffer.len() - this.pos;
+    let max_available = max(unfilled, this.desired_capacity);
+    if n_bytes > max_available {
+      return Poll::Ready(Err(Error::new(
+        ErrorKind::InvalidInput,
+        "requested reading more bytes than fit in buffer",
+      )));
+    }
+
+    if n_bytes > unfilled {
+      this.buffer.copy_within(this.pos..this.filled, 0);
+      this.buffer.truncate(this.desired_capacity);
+      this.filled -= this.pos;
+      this.pos = 0;
+    }
+
+    let target = this.pos + n_bytes;
+    while this.filled < target {
+      let read = Pin::new(&mut this.inner).poll_read(
+        cx,
+        &mut this.buffer[this.filled..target],
+      );
+      match read {
+        Poll::Ready(Ok(0)) => break,
+        Poll::Ready(Ok(n)) => {
+          this.filled += n;
+        }
+        Poll::Ready(Err(ref e)) if e.kind() == ErrorKind::Interrupted => {}
+        Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+        // bytes read so far stay in the buffer, so polling again resumes
+        // where we left off
+        Poll::Pending => return Poll::Pending,
+      }
+    }
+
+    Poll::Ready(Ok(()))
+  }
+
+  fn buffer(&self) -> &[u8] {
+    &self.buffer[self.pos..self.filled]
+  }
+
+  #[inline]
+  fn consume(&mut self, n_bytes: usize) {
+    self.pos += n_bytes;
+  }
+
+  #[inline]
+  fn capacity(&self) -> Option<usize> {
+    Some(self.desired_capacity)
+  }
+
+  fn resize_capacity(&mut self, desired: usize) {
+    self.desired_capacity = desired;
+    if desired >= self.filled {
+      self.buffer.resize(desired, 0);
+    }
+  }
+}
+
+impl<R: AsyncRead + Unpin> AsyncBetterBufReader<R> {
+  /// Creates an `AsyncBetterBufReader` based on an `AsyncRead`.
+  ///
+  /// Providing preloaded data is optional, but can be useful if instantiating
+  /// based on another abstraction that held a buffer and `AsyncRead`.
+  ///
+  /// Panics if `preloaded_data` is longer than `capacity`.
+  pub fn new(preloaded_data: &[u8], inner: R, capacity: usize) -> Self {
+    let mut buffer = vec![0; capacity];
+    let filled = preloaded_data.len();
+    buffer[0..filled].copy_from_slice(preloaded_data);
+    Self {
+      inner,
+      buffer,
+      desired_capacity: capacity,
+      pos: 0,
+      filled,
+    }
+  }
+
+  /// Creates an `AsyncBetterBufReader` based on an `AsyncRead`, supplying
+  /// sensible defaults.
+  pub fn from_read_simple(inner: R) -> Self {
+    Self::new(&[], inner, DEFAULT_CAPACITY)
+  }
+
+  /// Returns the inner `AsyncRead`, dropping the `AsyncBetterBufReader` and
+  /// its buffer.
+  ///
+  /// To avoid losing data, be sure to read the last of the buffer before
+  /// calling this.
+  pub fn into_inner(self) -> R {
+    self.inner
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::pin::Pin;
+  use std::task::{Context, Poll};
+
+  use futures::executor::block_on;
+  use futures_io::AsyncRead;
+
+  use crate::{AsyncBetterBufRead, AsyncBetterBufReader};
+
+  // yields at most 2 bytes per read, and is pending every other poll
+  struct TrickleRead {
+    data: Vec<u8>,
+    pos: usize,
+    ready: bool,
+  }
+
+  impl AsyncRead for TrickleRead {
+    fn poll_read(
+      mut self: Pin<&mut Self>,
+      cx: &mut Context<'_>,
+      buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+      if !self.ready {
+        self.ready = true;
+        cx.waker().wake_by_ref();
+        return Poll::Pending;
+      }
+      self.ready = false;
+      let n = buf.len().min(2).min(self.data.len() - self.pos);
+      buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+      self.pos += n;
+      Poll::Ready(Ok(n))
+    }
+  }
+
+  #[test]
+  fn test_async_better_buf_reader() {
+    let inner = TrickleRead {
+      data: (2..10_u8).collect(),
+      pos: 0,
+      ready: false,
+    };
+    let mut reader = AsyncBetterBufReader::new(&[0, 1], inner, 5);
+
+    // filling
+    assert_eq!(reader.buffer(), &[0, 1]);
+    block_on(reader.fill_or_eof(5)).unwrap();
+    assert_eq!(reader.buffer(), &[0, 1, 2, 3, 4]);
+    assert!(block_on(reader.fill_or_eof(6)).is_err());
+
+    // consuming and cycling the buffer
+    reader.consume(3);
+    assert_eq!(reader.buffer(), &[3, 4]);
+    block_on(reader.fill_or_eof(5)).unwrap();
+    assert_eq!(reader.buffer(), &[3, 4, 5, 6, 7]);
+
+    // reaching EOF
+    reader.consume(4);
+    block_on(reader.fill_or_eof(5)).unwrap();
+    assert_eq!(reader.buffer(), &[7, 8, 9]);
+    assert_eq!(reader.into_inner().pos, 8);
+  }
+}
@@ -1,5 +1,13 @@
+#[cfg(feature = "async")]
+pub use async_buf_read::{AsyncBetterBufRead, FillOrEof};
+#[cfg(feature = "async")]
+pub use async_buf_reader::AsyncBetterBufReader;
 pub use buf_read::BetterBufRead;
 pub use buf_reader::BetterBufReader;
 
+#[cfg(feature = "async")]
+mod async_buf_read;
+#[cfg(feature = "async")]
+mod async_buf_reader;
 mod buf_read;
 mod buf_reader;
@@ -0,0 +1,183 @@
+use crate::constants::{Bitlen, Weight};
+use crate::data_types::{Latent, Number};
+
+/// The sum of some numbers, widened so that it rarely overflows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumberSum {
+  /// The exact sum of integers.
+  /// Only sums of 128-bit integers can overflow this, in which case they
+  /// wrap around.
+  Int(i128),
+  /// The sum of floats, accumulated as `f64`s.
+  ///
+  /// Each batch is summed on its own before being added to the page's and
+  /// chunk's totals, so the result may differ slightly from a sequential sum.
+  Float(f64),
+}
+
+impl NumberSum {
+  /// Returns the sum as an `f64`, rounding if necessary.
+  pub fn to_f64(self) -> f64 {
+    match self {
+      NumberSum::Int(sum) => sum as f64,
+      NumberSum::Float(sum) => sum,
+    }
+  }
+
+  pub(crate) fn add(self, other: Self) -> Self {
+    match (self, other) {
+      (NumberSum::Int(a), NumberSum::Int(b)) => NumberSum::Int(a.wrapping_add(b)),
+      (NumberSum::Float(a), NumberSum::Float(b)) => NumberSum::Float(a + b),
+      _ => unreachable!("sums of different number types"),
+    }
+  }
+}
+
+/// Summary statistics of numbers, computed while streaming through
+/// compressed data.
+///
+/// See
+/// [`wrapped::PageDecompressor::aggregate`][crate::wrapped::PageDecompressor::aggregate]
+/// and
+/// [`standalone::FileDecompressor::aggregate`][crate::standalone::FileDecompressor::aggregate].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Aggregates<T: Number> {
+  /// The count of numbers.
+  pub count: usize,
+  /// The smallest number, or `None` if there were no numbers.
+  ///
+  /// Floats are ordered as by `total_cmp`, so e.g. positive NaNs are the
+  /// largest.
+  pub min: Option<T>,
+  /// The largest number, or `None` if there were no numbers.
+  pub max: Option<T>,
+  /// The sum of the numbers.
+  pub sum: NumberSum,
+}
+
+impl<T: Number> Aggregates<T> {
+  pub(crate) fn empty() -> Self {
+    Self {
+      count: 0,
+      min: None,
+      max: None,
+      sum: T::sum(&[]),
+    }
+  }
+
+  /// Returns the average of the numbers, or `None` if there were no numbers.
+  pub fn mean(&self) -> Option<f64> {
+    if self.count == 0 {
+      None
+    } else {
+      Some(self.sum.to_f64() / self.count as f64)
+    }
+  }
+
+  fn update_min_max(&mut self, min: T::L, max: T::L) {
+    self.min = Some(match self.min {
+      Some(x) if x.to_latent_ordered() <= min => x,
+      _ => T::from_latent_ordered(min),
+    });
+    self.max = Some(match self.max {
+      Some(x) if x.to_latent_ordered() >= max => x,
+      _ => T::from_latent_ordered(max),
+    });
+  }
+
+  // updates the min and max from a batch of ordered latents
+  pub(crate) fn update_from_latents(&mut self, latents: &[T::L]) {
+    if let Some(&first) = latents.first() {
+      let (min, max) = latents.iter().fold((first, first), |(min, max), &l| {
+        (min.min(l), max.max(l))
+      });
+      self.update_min_max(min, max);
+    }
+  }
+
+  // updates the count and sum from a batch of numbers
+  pub(crate) fn update_from_nums(&mut self, nums: &[T]) {
+    self.count += nums.len();
+    self.sum = self.sum.add(T::sum(nums));
+  }
+
+  pub(crate) fn merge(&mut self, other: &Self) {
+    if let (Some(min), Some(max)) = (other.min, other.max) {
+      self.update_min_max(
+        min.to_latent_ordered(),
+        max.to_latent_ordered(),
+      );
+    }
+    self.count += other.count;
+    self.sum = self.sum.add(other.sum);
+  }
+}
+
+/// The range of numbers covered by one bin of a chunk, as returned by
+/// [`ChunkMeta::bin_bounds`][crate::metadata::ChunkMeta::bin_bounds].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct BinBounds<T: Number> {
+  /// The smallest number the bin can contain.
+  pub lower: T,
+  /// The largest number the bin can contain.
+  pub upper: T,
+  /// The bin's weight in the chunk's asymmetric numeral system table,
+  /// which is roughly proportional to how many numbers fell into it.
+  pub weight: Weight,
+}
+
+impl<T: Number> BinBounds<T> {
+  pub(crate) fn new(lower: T::L, offset_bits: Bitlen, weight: Weight) -> Self {
+    let span = if offset_bits == 0 {
+      T::L::ZERO
+    } else {
+      T::L::MAX >> (T::L::BITS - offset_bits.min(T::L::BITS))
+    };
+    let upper = if span > T::L::MAX - lower {
+      T::L::MAX
+    } else {
+      lower + span
+    };
+    Self {
+      lower: T::from_latent_ordered(lower),
+      upper: T::from_latent_ordered(upper),
+      weight,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_merge() {
+    let mut aggs = Aggregates::<f32>::empty();
+    assert_eq!(aggs.mean(), None);
+
+    let mut other = Aggregates::<f32>::empty();
+    let nums = [-1.0_f32, 3.0, 0.5];
+    let latents = nums.map(f32::to_latent_ordered);
+    other.update_from_latents(&latents);
+    other.update_from_nums(&nums);
+    aggs.merge(&other);
+    aggs.merge(&Aggregates::empty());
+    assert_eq!(aggs.count, 3);
+    assert_eq!(aggs.min, Some(-1.0));
+    assert_eq!(aggs.max, Some(3.0));
+    assert_eq!(aggs.sum, NumberSum::Float(2.5));
+    assert_eq!(aggs.mean(), Some(2.5 / 3.0));
+  }
+
+  #[test]
+  fn test_bin_bounds() {
+    let bounds = BinBounds::<i32>::new(0_i32.to_latent_ordered(), 3, 7);
+    assert_eq!(bounds.lower, 0);
+    assert_eq!(bounds.upper, 7);
+    let bounds = BinBounds::<u8>::new(200, 8, 1);
+    assert_eq!(bounds.lower, 200);
+    assert_eq!(bounds.upper, 255);
+  }
+}
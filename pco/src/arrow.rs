@@ -0,0 +1,310 @@
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, ArrayRef, ArrowPrimitiveType, NullArray, PrimitiveArray};
+use arrow_buffer::{NullBuffer, ScalarBuffer};
+use arrow_schema::{DataType, DECIMAL128_MAX_PRECISION};
+
+use crate::data_types::{Number, NumberType};
+use crate::errors::{PcoError, PcoResult};
+use crate::standalone::{
+  simple_compress, simple_compress_nullable, simple_decompress_nullable, FileDecompressor,
+  NullableNums, NumberTypeOrTermination,
+};
+use crate::ChunkConfig;
+
+// Calls $f::<P> for the Arrow primitive type P matching the data type, or
+// returns an error if pco can't store it.
+macro_rules! match_arrow_type {
+  ($data_type: expr, $f: ident($($arg: expr),*)) => {{
+    use arrow_array::types::*;
+    use arrow_schema::{DataType, IntervalUnit, TimeUnit};
+    match $data_type {
+      DataType::Int8 => $f::<Int8Type>($($arg),*),
+      DataType::Int16 => $f::<Int16Type>($($arg),*),
+      DataType::Int32 => $f::<Int32Type>($($arg),*),
+      DataType::Int64 => $f::<Int64Type>($($arg),*),
+      DataType::UInt8 => $f::<UInt8Type>($($arg),*),
+      DataType::UInt16 => $f::<UInt16Type>($($arg),*),
+      DataType::UInt32 => $f::<UInt32Type>($($arg),*),
+      DataType::UInt64 => $f::<UInt64Type>($($arg),*),
+      DataType::Float16 => $f::<Float16Type>($($arg),*),
+      DataType::Float32 => $f::<Float32Type>($($arg),*),
+      DataType::Float64 => $f::<Float64Type>($($arg),*),
+      DataType::Timestamp(TimeUnit::Second, _) => $f::<TimestampSecondType>($($arg),*),
+      DataType::Timestamp(TimeUnit::Millisecond, _) => $f::<TimestampMillisecondType>($($arg),*),
+      DataType::Timestamp(TimeUnit::Microsecond, _) => $f::<TimestampMicrosecondType>($($arg),*),
+      DataType::Timestamp(TimeUnit::Nanosecond, _) => $f::<TimestampNanosecondType>($($arg),*),
+      DataType::Date32 => $f::<Date32Type>($($arg),*),
+      DataType::Date64 => $f::<Date64Type>($($arg),*),
+      DataType::Time32(TimeUnit::Second) => $f::<Time32SecondType>($($arg),*),
+      DataType::Time32(TimeUnit::Millisecond) => $f::<Time32MillisecondType>($($arg),*),
+      DataType::Time64(TimeUnit::Microsecond) => $f::<Time64MicrosecondType>($($arg),*),
+      DataType::Time64(TimeUnit::Nanosecond) => $f::<Time64NanosecondType>($($arg),*),
+      DataType::Duration(TimeUnit::Second) => $f::<DurationSecondType>($($arg),*),
+      DataType::Duration(TimeUnit::Millisecond) => $f::<DurationMillisecondType>($($arg),*),
+      DataType::Duration(TimeUnit::Microsecond) => $f::<DurationMicrosecondType>($($arg),*),
+      DataType::Duration(TimeUnit::Nanosecond) => $f::<DurationNanosecondType>($($arg),*),
+      DataType::Interval(IntervalUnit::YearMonth) => $f::<IntervalYearMonthType>($($arg),*),
+      DataType::Decimal128(_, _) => $f::<Decimal128Type>($($arg),*),
+      other => Err($crate::errors::PcoError::invalid_argument(format!(
+        "unsupported Arrow data type: {:?}",
+        other,
+      ))),
+    }
+  }};
+}
+pub(crate) use match_arrow_type;
+
+fn compress_primitive<P: ArrowPrimitiveType>(
+  array: &dyn Array,
+  config: &ChunkConfig,
+) -> PcoResult<Vec<u8>>
+where
+  P::Native: Number,
+{
+  let array = array.as_primitive::<P>();
+  match array.nulls().filter(|nulls| nulls.null_count() > 0) {
+    None => simple_compress(array.values(), config),
+    Some(nulls) => {
+      let values = array.iter().flatten().collect::<Vec<_>>();
+      let validity = nulls.iter().collect::<Vec<_>>();
+      simple_compress_nullable(&values, &validity, config)
+    }
+  }
+}
+
+fn decompress_primitive<P: ArrowPrimitiveType>(
+  src: &[u8],
+  data_type: &DataType,
+) -> PcoResult<ArrayRef>
+where
+  P::Native: Number,
+{
+  let (file_decompressor, rest) = FileDecompressor::new(src)?;
+  let type_or_termination = file_decompressor.peek_number_type_or_termination(rest)?;
+  if !matches!(
+    type_or_termination,
+    NumberTypeOrTermination::Termination
+  ) && u8::from(type_or_termination) != P::Native::NUMBER_TYPE_BYTE
+  {
+    return Err(PcoError::invalid_argument(format!(
+      "Arrow data type {:?} does not match the file's number type {:?}",
+      data_type, type_or_termination,
+    )));
+  }
+
+  let NullableNums { values, validity } = simple_decompress_nullable::<P::Native>(src)?;
+  let nulls = if values.len() == validity.len() {
+    None
+  } else {
+    Some(NullBuffer::from(validity))
+  };
+  Ok(nullable_primitive::<P>(
+    values, nulls, data_type,
+  ))
+}
+
+// Builds an array from the non-null values, filling in null rows with the
+// default value, as Arrow expects.
+pub(crate) fn nullable_primitive<P: ArrowPrimitiveType>(
+  values: Vec<P::Native>,
+  nulls: Option<NullBuffer>,
+  data_type: &DataType,
+) -> ArrayRef {
+  let values = match &nulls {
+    Some(nulls) if nulls.null_count() > 0 => {
+      let mut values = values.into_iter();
+      nulls
+        .iter()
+        .map(|is_valid| {
+          if is_valid {
+            values.next().unwrap_or_default()
+          } else {
+            P::Native::default()
+          }
+        })
+        .collect::<Vec<_>>()
+    }
+    _ => values,
+  };
+  let array =
+    PrimitiveArray::<P>::new(ScalarBuffer::from(values), nulls).with_data_type(data_type.clone());
+  Arc::new(array)
+}
+
+fn default_data_type(number_type: NumberType) -> PcoResult<DataType> {
+  let data_type = match number_type {
+    NumberType::I8 => DataType::Int8,
+    NumberType::I16 => DataType::Int16,
+    NumberType::I32 => DataType::Int32,
+    NumberType::I64 => DataType::Int64,
+    NumberType::I128 => DataType::Decimal128(DECIMAL128_MAX_PRECISION, 0),
+    NumberType::U8 => DataType::UInt8,
+    NumberType::U16 => DataType::UInt16,
+    NumberType::U32 => DataType::UInt32,
+    NumberType::U64 => DataType::UInt64,
+    NumberType::F16 => DataType::Float16,
+    NumberType::F32 => DataType::Float32,
+    NumberType::F64 => DataType::Float64,
+    NumberType::BF16 | NumberType::U128 => {
+      return Err(PcoError::compatibility(format!(
+        "{:?} has no equivalent Arrow data type",
+        number_type,
+      )))
+    }
+  };
+  Ok(data_type)
+}
+
+/// Takes in an Arrow array of a primitive data type and returns compressed
+/// bytes of a standalone .pco file.
+///
+/// Will return an error if the data type is unsupported or the compressor
+/// config is invalid.
+///
+/// Integers, floats, decimals, and temporal types (timestamps, dates,
+/// times, durations, and year-month intervals) are supported, each being
+/// compressed as its underlying integer or float.
+/// Arrays containing nulls are written as nullable files.
+/// Only the physical numbers get stored, so temporal units, time zones,
+/// and decimal precisions must be passed to
+/// [`decompress_to_array_as`] to recover the original data type.
+pub fn compress_array(array: &dyn Array, config: &ChunkConfig) -> PcoResult<Vec<u8>> {
+  match_arrow_type!(
+    array.data_type(),
+    compress_primitive(array, config)
+  )
+}
+
+/// Takes in compressed bytes of a standalone .pco file and returns an Arrow
+/// array of the file's number type.
+///
+/// Will return an error if there are any compatibility, corruption,
+/// or insufficient data issues.
+///
+/// `i128`s decompress as decimals with maximum precision and a scale of 0.
+/// A file with no chunks has no number type, so it decompresses as a
+/// [`NullArray`] of its row count.
+pub fn decompress_to_array(src: &[u8]) -> PcoResult<ArrayRef> {
+  let (file_decompressor, rest) = FileDecompressor::new(src)?;
+  match file_decompressor.peek_number_type_or_termination(rest)? {
+    NumberTypeOrTermination::Known(number_type) => {
+      decompress_to_array_as(src, &default_data_type(number_type)?)
+    }
+    NumberTypeOrTermination::Termination => {
+      let n = simple_decompress_nullable::<u8>(src)?.len();
+      Ok(Arc::new(NullArray::new(n)))
+    }
+    NumberTypeOrTermination::Unknown(byte) => Err(PcoError::compatibility(format!(
+      "unrecognized number type byte {} has no equivalent Arrow data type",
+      byte,
+    ))),
+  }
+}
+
+/// Takes in compressed bytes of a standalone .pco file and returns an Arrow
+/// array of the given data type.
+///
+/// Will return an error if the data type is unsupported or doesn't match
+/// the file's number type, or if there are any compatibility,
+/// corruption, or insufficient data issues.
+///
+/// For instance, a file compressed from a millisecond timestamp array can
+/// be decompressed with that same data type, since both are stored as
+/// `i64`s.
+pub fn decompress_to_array_as(src: &[u8], data_type: &DataType) -> PcoResult<ArrayRef> {
+  match_arrow_type!(
+    data_type,
+    decompress_primitive(src, data_type)
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use arrow_array::{Date32Array, Float64Array, Int16Array, TimestampMillisecondArray};
+  use arrow_schema::TimeUnit;
+
+  use super::*;
+  use crate::errors::ErrorKind;
+
+  #[test]
+  fn test_roundtrip_without_nulls() -> PcoResult<()> {
+    let array = Float64Array::from((0..1000).map(|x| x as f64 / 4.0).collect::<Vec<_>>());
+    let compressed = compress_array(&array, &ChunkConfig::default())?;
+    let recovered = decompress_to_array(&compressed)?;
+    assert_eq!(recovered.as_ref(), &array as &dyn Array);
+    assert_eq!(recovered.null_count(), 0);
+    Ok(())
+  }
+
+  #[test]
+  fn test_roundtrip_with_nulls() -> PcoResult<()> {
+    let array = Int16Array::from(
+      (0..1000)
+        .map(|x| if x % 7 == 0 { None } else { Some(x % 100) })
+        .collect::<Vec<_>>(),
+    );
+    let compressed = compress_array(&array, &ChunkConfig::default())?;
+    let recovered = decompress_to_array(&compressed)?;
+    assert_eq!(recovered.as_ref(), &array as &dyn Array);
+
+    // slices only compress the rows they contain
+    let sliced = array.slice(500, 20);
+    let compressed = compress_array(&sliced, &ChunkConfig::default())?;
+    let recovered = decompress_to_array(&compressed)?;
+    assert_eq!(recovered.as_ref(), &sliced as &dyn Array);
+    Ok(())
+  }
+
+  #[test]
+  fn test_temporal_types() -> PcoResult<()> {
+    let timestamps = TimestampMillisecondArray::from(vec![
+      Some(1_700_000_000_000),
+      None,
+      Some(1_700_000_001_000),
+    ])
+    .with_timezone("UTC");
+    let compressed = compress_array(&timestamps, &ChunkConfig::default())?;
+    let recovered = decompress_to_array_as(&compressed, timestamps.data_type())?;
+    assert_eq!(recovered.as_ref(), &timestamps as &dyn Array);
+    // without the data type, the physical numbers come back
+    let recovered = decompress_to_array(&compressed)?;
+    assert_eq!(recovered.data_type(), &DataType::Int64);
+
+    let dates = Date32Array::from(vec![19_000, 19_001, 19_100]);
+    let compressed = compress_array(&dates, &ChunkConfig::default())?;
+    let recovered = decompress_to_array_as(&compressed, &DataType::Date32)?;
+    assert_eq!(recovered.as_ref(), &dates as &dyn Array);
+
+    // the data type must match the file's number type
+    let err = decompress_to_array_as(
+      &compressed,
+      &DataType::Timestamp(TimeUnit::Second, None),
+    )
+    .unwrap_err();
+    assert!(matches!(
+      err.kind,
+      ErrorKind::InvalidArgument
+    ));
+    Ok(())
+  }
+
+  #[test]
+  fn test_all_nulls_and_unsupported() -> PcoResult<()> {
+    let array = Int16Array::from(vec![None, None, None]);
+    let compressed = compress_array(&array, &ChunkConfig::default())?;
+    let recovered = decompress_to_array(&compressed)?;
+    assert_eq!(recovered.data_type(), &DataType::Null);
+    assert_eq!(recovered.len(), 3);
+    let recovered = decompress_to_array_as(&compressed, &DataType::Int16)?;
+    assert_eq!(recovered.as_ref(), &array as &dyn Array);
+
+    let err = compress_array(&NullArray::new(3), &ChunkConfig::default()).unwrap_err();
+    assert!(matches!(
+      err.kind,
+      ErrorKind::InvalidArgument
+    ));
+    Ok(())
+  }
+}
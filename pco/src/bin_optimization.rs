@@ -197,6 +197,36 @@ pub fn optimize_bins<L: Latent>(
   res
 }
 
+// Estimates the bits needed to encode latents with these bins, excluding
+// the bins' own metadata, where the bins' counts are scaled by `count_scale`
+// (e.g. because they were counted on a sample).
+pub fn est_latent_bits<L: Latent>(
+  infos: &[BinCompressionInfo<L>],
+  counts: &[Weight],
+  count_scale: f64,
+) -> f64 {
+  let total_count = counts.iter().sum::<Weight>();
+  if total_count == 0 {
+    return 0.0;
+  }
+
+  let total_count_log2 = log2_approx(total_count as f32);
+  let sample_bits = infos
+    .iter()
+    .zip(counts)
+    .map(|(info, &count)| {
+      bin_cost(
+        0.0,
+        info.lower,
+        info.upper,
+        count,
+        total_count_log2,
+      )
+    })
+    .sum::<f32>();
+  sample_bits as f64 * count_scale
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
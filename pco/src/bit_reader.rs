@@ -255,6 +255,12 @@ impl<R: BetterBufRead> BitReaderBuilder<R> {
     self.inner
   }
 
+  // Only safe to read from once the builder is done reading, since bytes
+  // consumed this way won't be reflected in the EOF buffer.
+  pub fn inner_mut(&mut self) -> &mut R {
+    &mut self.inner
+  }
+
   fn update(&mut self, final_bit_idx: usize) {
     let bytes_consumed = final_bit_idx / 8;
     self.inner.consume(bytes_consumed);
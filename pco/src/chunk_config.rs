@@ -1,5 +1,6 @@
 use crate::constants::{Bitlen, DEFAULT_MAX_PAGE_N};
 use crate::errors::{PcoError, PcoResult};
+use crate::metadata::ErrorBound;
 use crate::DEFAULT_COMPRESSION_LEVEL;
 
 /// Specifies how Pco should choose a [`mode`][crate::metadata::Mode] to compress this
@@ -70,8 +71,49 @@ pub enum DeltaSpec {
   TryLookback,
 }
 
-// TODO consider adding a "lossiness" spec that allows dropping secondary latent
-// vars.
+/// Specifies whether Pco may lossily round numbers to compress them better.
+///
+/// Lossy chunks record the error bound they guarantee in their
+/// [`ChunkMeta`][crate::metadata::ChunkMeta], so decompressors can report it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub enum LossySpec {
+  /// Recovers every number exactly.
+  #[default]
+  Lossless,
+  /// Rounds each number to a multiple of `2 * max_abs_error`, guaranteeing
+  /// every decompressed number is within `max_abs_error` of the original.
+  ///
+  /// Only applies to floating-point types, and requires `ModeSpec::Auto`,
+  /// since the chunk is always compressed with `FloatMult` mode.
+  /// Non-finite numbers are recovered exactly.
+  MaxAbsError(f64),
+  /// Rounds each number's mantissa to this many bits, guaranteeing a relative
+  /// error of at most `2^-(significant_bits + 1)` for normal floats.
+  ///
+  /// Only applies to floating-point types, and requires `ModeSpec::Auto`,
+  /// since the chunk is always compressed with `FloatQuant` mode.
+  /// Mantissas are rounded to nearest, ties to even; this rounding will never
+  /// change, so decompressed numbers are reproducible across pco versions.
+  /// Non-finite numbers are recovered exactly, and no finite number gets
+  /// rounded up to infinity.
+  /// If the data type has no more mantissa bits than this, compression is
+  /// lossless.
+  SignificantBits(Bitlen),
+}
+
+impl LossySpec {
+  pub(crate) fn error_bound(&self) -> Option<ErrorBound> {
+    match self {
+      LossySpec::Lossless => None,
+      &LossySpec::MaxAbsError(max_abs_error) => Some(ErrorBound::MaxAbsError(max_abs_error)),
+      &LossySpec::SignificantBits(significant_bits) => {
+        Some(ErrorBound::SignificantBits(significant_bits))
+      }
+    }
+  }
+}
+
 /// All configurations available for a compressor.
 ///
 /// Some, like `delta_encoding_order`, are explicitly stored in the
@@ -107,6 +149,17 @@ pub struct ChunkConfig {
   /// Specifies how the chunk should be split into pages (default: equal pages
   /// up to 2^18 numbers each).
   pub paging_spec: PagingSpec,
+  /// Whether to store the min, max, and count of the chunk and each of its
+  /// pages (default: false).
+  ///
+  /// See [`Stats`](crate::metadata::Stats).
+  /// This costs 2 uncompressed numbers and a count per page and chunk, but allows
+  /// readers to skip pages without decompressing them.
+  pub store_stats: bool,
+  /// Specifies whether numbers may be lossily rounded (default: lossless).
+  ///
+  /// See [`LossySpec`] for the guarantees each option provides.
+  pub lossy_spec: LossySpec,
 }
 
 impl Default for ChunkConfig {
@@ -116,6 +169,8 @@ impl Default for ChunkConfig {
       mode_spec: ModeSpec::default(),
       delta_spec: DeltaSpec::default(),
       paging_spec: PagingSpec::EqualPagesUpTo(DEFAULT_MAX_PAGE_N),
+      store_stats: false,
+      lossy_spec: LossySpec::default(),
     }
   }
 }
@@ -144,6 +199,18 @@ impl ChunkConfig {
     self.paging_spec = paging_spec;
     self
   }
+
+  /// Sets [`store_stats`][ChunkConfig::store_stats].
+  pub fn with_store_stats(mut self, store_stats: bool) -> Self {
+    self.store_stats = store_stats;
+    self
+  }
+
+  /// Sets [`lossy_spec`][ChunkConfig::lossy_spec].
+  pub fn with_lossy_spec(mut self, lossy_spec: LossySpec) -> Self {
+    self.lossy_spec = lossy_spec;
+    self
+  }
 }
 
 /// `PagingSpec` specifies how a chunk is split into pages.
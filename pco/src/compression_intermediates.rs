@@ -4,7 +4,7 @@ use crate::data_types::SplitLatents;
 use crate::data_types::{Latent, Number};
 use crate::delta::DeltaState;
 use crate::metadata::per_latent_var::{LatentVarKey, PerLatentVar};
-use crate::metadata::{DynLatents, Mode};
+use crate::metadata::{DynLatents, Mode, Stats};
 use std::ops::Range;
 
 #[derive(Clone, Debug)]
@@ -17,6 +17,7 @@ pub struct PageInfoVar {
 pub struct PageInfo {
   pub page_n: usize,
   pub per_latent_var: PerLatentVar<PageInfoVar>,
+  pub stats: Option<Stats>,
 }
 
 impl PageInfo {
@@ -9,18 +9,22 @@ pub(crate) type Weight = u32;
 pub(crate) type DeltaLookback = u32;
 
 // compatibility
-pub const CURRENT_FORMAT_VERSION: u8 = 3;
+pub const CURRENT_FORMAT_VERSION: u8 = 4;
 
 // bit lengths
 pub const BITS_TO_ENCODE_ANS_SIZE_LOG: Bitlen = 4;
 pub const BITS_TO_ENCODE_MODE_VARIANT: Bitlen = 4;
 pub const BITS_TO_ENCODE_DELTA_ENCODING_VARIANT: Bitlen = 4;
 pub const BITS_TO_ENCODE_DELTA_ENCODING_ORDER: Bitlen = 3;
+pub const BITS_TO_ENCODE_ERROR_BOUND_VARIANT: Bitlen = 4;
 pub const BITS_TO_ENCODE_LZ_DELTA_WINDOW_N_LOG: Bitlen = 5;
 pub const BITS_TO_ENCODE_LZ_DELTA_STATE_N_LOG: Bitlen = 4;
 pub const BITS_TO_ENCODE_N_BINS: Bitlen = 15;
 // conservative: wide enough to support quantizing float datasets with 255 unused bits of precision
 pub const BITS_TO_ENCODE_QUANTIZE_K: Bitlen = 8;
+pub const BITS_TO_ENCODE_SIGNIFICANT_BITS: Bitlen = 8;
+// enough for counts up to and including MAX_ENTRIES
+pub const BITS_TO_ENCODE_STATS_COUNT: Bitlen = 25;
 
 // padding
 pub const HEADER_PADDING: usize = 1;
@@ -81,6 +85,11 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_bits_to_encode_stats_count() {
+    assert_can_encode(BITS_TO_ENCODE_STATS_COUNT, MAX_ENTRIES);
+  }
+
   #[test]
   fn test_ans_interleaving_fits_in_u64() {
     assert!(ANS_INTERLEAVING * MAX_ANS_BITS as usize <= 57);
@@ -0,0 +1,19 @@
+use std::io::Write;
+
+// counts the bytes written through it, so that chunk sizes can be indexed
+pub(crate) struct CountingWriter<W: Write> {
+  pub inner: W,
+  pub count: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    let n = self.inner.write(buf)?;
+    self.count += n as u64;
+    Ok(n)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.inner.flush()
+  }
+}
@@ -1,12 +1,13 @@
 use std::mem;
 
-use half::f16;
+use half::{bf16, f16};
 
 use super::ModeAndLatents;
-use crate::chunk_config::ModeSpec;
+use crate::aggregate::NumberSum;
+use crate::chunk_config::{LossySpec, ModeSpec};
 use crate::compression_intermediates::Bid;
 use crate::constants::Bitlen;
-use crate::data_types::{split_latents_classic, Float, Latent, Number};
+use crate::data_types::{split_latents_classic, Float, Latent, Number, SplitLatents};
 use crate::describers::LatentDescriber;
 use crate::errors::{PcoError, PcoResult};
 use crate::float_mult_utils::FloatMultConfig;
@@ -26,10 +27,60 @@ fn filter_sample<F: Float>(num: &F) -> Option<F> {
   None
 }
 
+fn split_latents_max_abs_error<F: Float>(
+  nums: &[F],
+  max_abs_error: f64,
+) -> PcoResult<ModeAndLatents> {
+  let mut base = F::from_f64(2.0 * max_abs_error);
+  if base.to_f64() > 2.0 * max_abs_error {
+    // round down so that rounding to the nearest multiple stays in bounds
+    base = F::from_latent_ordered(base.to_latent_ordered() - F::L::ONE);
+  }
+  let config = FloatMultConfig::from_base(base);
+  if !base.is_normal() || !config.inv_base.is_normal() {
+    return Err(PcoError::invalid_argument(format!(
+      "max absolute error {} is out of range for {}",
+      max_abs_error,
+      std::any::type_name::<F>(),
+    )));
+  }
+
+  Ok((
+    Mode::float_mult(base),
+    float_mult_utils::split_latents_lossy(nums, config, max_abs_error),
+  ))
+}
+
+fn split_latents_significant_bits<F: Float>(
+  nums: &[F],
+  significant_bits: Bitlen,
+) -> ModeAndLatents {
+  let k = F::PRECISION_BITS - significant_bits;
+  let rounded = float_quant_utils::round_mantissas(nums, k);
+  (
+    Mode::FloatQuant(k),
+    float_quant_utils::split_latents(&rounded, k),
+  )
+}
+
 fn choose_mode_and_split_latents<F: Float>(
   nums: &[F],
   chunk_config: &ChunkConfig,
 ) -> PcoResult<ModeAndLatents> {
+  match chunk_config.lossy_spec {
+    LossySpec::MaxAbsError(max_abs_error) => {
+      return split_latents_max_abs_error(nums, max_abs_error);
+    }
+    // with at least as many significant bits as the float has, we're lossless
+    LossySpec::SignificantBits(significant_bits) if significant_bits < F::PRECISION_BITS => {
+      return Ok(split_latents_significant_bits(
+        nums,
+        significant_bits,
+      ));
+    }
+    _ => (),
+  }
+
   match chunk_config.mode_spec {
     ModeSpec::Auto => {
       // up to 3 bids: classic, float mult, float quant modes
@@ -198,118 +249,122 @@ macro_rules! impl_float {
   };
 }
 
-impl Float for f16 {
-  const PRECISION_BITS: Bitlen = Self::MANTISSA_DIGITS as Bitlen - 1;
-  const ZERO: Self = f16::ZERO;
-  const MAX_FOR_SAMPLING: Self = f16::from_bits(30719); // Half of MAX size.
+macro_rules! impl_half_float {
+  ($t: ty, $exp_offset: expr, $max_for_sampling_bits: expr) => {
+    impl Float for $t {
+      const PRECISION_BITS: Bitlen = Self::MANTISSA_DIGITS as Bitlen - 1;
+      const ZERO: Self = <$t>::ZERO;
+      const MAX_FOR_SAMPLING: Self = <$t>::from_bits($max_for_sampling_bits); // Half of MAX size.
 
-  #[inline]
-  fn abs(self) -> Self {
-    Self::from_bits(self.to_bits() & 0x7FFF)
-  }
+      #[inline]
+      fn abs(self) -> Self {
+        Self::from_bits(self.to_bits() & 0x7FFF)
+      }
 
-  fn inv(self) -> Self {
-    Self::ONE / self
-  }
+      fn inv(self) -> Self {
+        Self::ONE / self
+      }
 
-  #[inline]
-  fn round(self) -> Self {
-    Self::from_f32(self.to_f32().round())
-  }
+      #[inline]
+      fn round(self) -> Self {
+        Self::from_f32(self.to_f32().round())
+      }
 
-  #[inline]
-  fn exp2(power: i32) -> Self {
-    Self::from_bits(((15 + power) as u16) << Self::PRECISION_BITS)
-  }
+      #[inline]
+      fn exp2(power: i32) -> Self {
+        Self::from_bits((($exp_offset + power) as u16) << Self::PRECISION_BITS)
+      }
 
-  #[inline]
-  fn from_f64(x: f64) -> Self {
-    Self::from_f64(x)
-  }
+      #[inline]
+      fn from_f64(x: f64) -> Self {
+        Self::from_f64(x)
+      }
 
-  #[inline]
-  fn to_f64(self) -> f64 {
-    self.to_f64()
-  }
+      #[inline]
+      fn to_f64(self) -> f64 {
+        self.to_f64()
+      }
 
-  #[inline]
-  fn is_normal(self) -> bool {
-    self.is_normal()
-  }
+      #[inline]
+      fn is_normal(self) -> bool {
+        self.is_normal()
+      }
 
-  #[inline]
-  fn is_sign_positive_(&self) -> bool {
-    self.is_sign_positive()
-  }
+      #[inline]
+      fn is_sign_positive_(&self) -> bool {
+        self.is_sign_positive()
+      }
 
-  #[inline]
-  fn exponent(&self) -> i32 {
-    (self.abs().to_bits() >> Self::PRECISION_BITS) as i32 - 15
-  }
+      #[inline]
+      fn exponent(&self) -> i32 {
+        (self.abs().to_bits() >> Self::PRECISION_BITS) as i32 - $exp_offset
+      }
 
-  #[inline]
-  fn trailing_zeros(&self) -> u32 {
-    self.to_bits().trailing_zeros()
-  }
+      #[inline]
+      fn trailing_zeros(&self) -> u32 {
+        self.to_bits().trailing_zeros()
+      }
 
-  #[inline]
-  fn max(a: Self, b: Self) -> Self {
-    Self::max(a, b)
-  }
+      #[inline]
+      fn max(a: Self, b: Self) -> Self {
+        Self::max(a, b)
+      }
 
-  #[inline]
-  fn min(a: Self, b: Self) -> Self {
-    Self::min(a, b)
-  }
+      #[inline]
+      fn min(a: Self, b: Self) -> Self {
+        Self::min(a, b)
+      }
 
-  #[inline]
-  fn to_latent_bits(self) -> Self::L {
-    self.to_bits()
-  }
+      #[inline]
+      fn to_latent_bits(self) -> Self::L {
+        self.to_bits()
+      }
 
-  #[inline]
-  fn int_float_from_latent(l: Self::L) -> Self {
-    let mid = Self::L::MID;
-    let (negative, abs_int) = if l >= mid {
-      (false, l - mid)
-    } else {
-      (true, mid - 1 - l)
-    };
-    let gpi = 1 << Self::MANTISSA_DIGITS;
-    let abs_float = if abs_int < gpi {
-      Self::from_f32(abs_int as f32)
-    } else {
-      Self::from_bits(Self::from_f32(gpi as f32).to_bits() + (abs_int - gpi))
-    };
-    if negative {
-      -abs_float
-    } else {
-      abs_float
-    }
-  }
+      #[inline]
+      fn int_float_from_latent(l: Self::L) -> Self {
+        let mid = Self::L::MID;
+        let (negative, abs_int) = if l >= mid {
+          (false, l - mid)
+        } else {
+          (true, mid - 1 - l)
+        };
+        let gpi = 1 << Self::MANTISSA_DIGITS;
+        let abs_float = if abs_int < gpi {
+          Self::from_f32(abs_int as f32)
+        } else {
+          Self::from_bits(Self::from_f32(gpi as f32).to_bits() + (abs_int - gpi))
+        };
+        if negative {
+          -abs_float
+        } else {
+          abs_float
+        }
+      }
 
-  #[inline]
-  fn int_float_to_latent(self) -> Self::L {
-    let abs = self.abs();
-    let gpi = 1 << Self::MANTISSA_DIGITS;
-    let gpi_float = Self::from_f32(gpi as f32);
-    let abs_int = if abs < gpi_float {
-      abs.to_f32() as Self::L
-    } else {
-      gpi + (abs.to_bits() - gpi_float.to_bits())
-    };
-    if self.is_sign_positive() {
-      Self::L::MID + abs_int
-    } else {
-      // -1 because we need to distinguish -0.0 from +0.0
-      Self::L::MID - 1 - abs_int
-    }
-  }
+      #[inline]
+      fn int_float_to_latent(self) -> Self::L {
+        let abs = self.abs();
+        let gpi = 1 << Self::MANTISSA_DIGITS;
+        let gpi_float = Self::from_f32(gpi as f32);
+        let abs_int = if abs < gpi_float {
+          abs.to_f32() as Self::L
+        } else {
+          gpi + (abs.to_bits() - gpi_float.to_bits())
+        };
+        if self.is_sign_positive() {
+          Self::L::MID + abs_int
+        } else {
+          // -1 because we need to distinguish -0.0 from +0.0
+          Self::L::MID - 1 - abs_int
+        }
+      }
 
-  #[inline]
-  fn from_latent_numerical(l: Self::L) -> Self {
-    Self::from_f32(l as f32)
-  }
+      #[inline]
+      fn from_latent_numerical(l: Self::L) -> Self {
+        Self::from_f32(l as f32)
+      }
+    }
+  };
 }
 
 macro_rules! impl_float_number {
@@ -343,6 +398,20 @@ macro_rules! impl_float_number {
       ) -> PcoResult<ModeAndLatents> {
         choose_mode_and_split_latents(nums, config)
       }
+      fn split_latents_with_mode(nums: &[Self], mode: Mode) -> SplitLatents {
+        match mode {
+          Mode::Classic => split_latents_classic(nums),
+          Mode::FloatMult(dyn_latent) => {
+            let base = Self::from_latent_ordered(*dyn_latent.downcast_ref::<Self::L>().unwrap());
+            float_mult_utils::split_latents(nums, FloatMultConfig::from_base(base))
+          }
+          Mode::FloatQuant(k) => float_quant_utils::split_latents(nums, k),
+          _ => unreachable!("impossible mode for floats"),
+        }
+      }
+      fn sum(nums: &[Self]) -> NumberSum {
+        NumberSum::Float(nums.iter().map(|&x| x.to_f64()).sum())
+      }
 
       #[inline]
       fn from_latent_ordered(l: Self::L) -> Self {
@@ -391,10 +460,13 @@ macro_rules! impl_float_number {
 
 impl_float!(f32, u32, 127);
 impl_float!(f64, u64, 1023);
-// f16 Float is implemented separately because it's non-native.
+// f16 and bf16 Floats are implemented separately because they're non-native.
+impl_half_float!(f16, 15, 0x77ff);
+impl_half_float!(bf16, 127, 0x7eff);
 impl_float_number!(f32, u32, 1_u32 << 31, 5);
 impl_float_number!(f64, u64, 1_u64 << 63, 6);
 impl_float_number!(f16, u16, 1_u16 << 15, 9);
+impl_float_number!(bf16, u16, 1_u16 << 15, 14);
 
 #[cfg(test)]
 mod tests {
@@ -485,9 +557,48 @@ mod tests {
     assert_eq!(<f32 as Float>::exp2(2), 4.0);
 
     assert_eq!(<f16 as Float>::exp2(0), f16::ONE);
+    assert_eq!(<bf16 as Float>::exp2(0), bf16::ONE);
+    assert_eq!(
+      <bf16 as Float>::exp2(-3),
+      bf16::from_f32(0.125)
+    );
     assert_eq!(<f64 as Float>::exp2(0), 1.0);
   }
 
+  #[test]
+  fn test_bf16_exponent() {
+    assert_eq!(bf16::ONE.exponent(), 0);
+    assert_eq!(bf16::from_f32(31.0).exponent(), 4);
+    assert_eq!(bf16::from_f32(-0.3333).exponent(), -2);
+  }
+
+  #[test]
+  fn int_float_bf16_invertibility() {
+    for x in [
+      -bf16::NAN,
+      bf16::NEG_INFINITY,
+      bf16::MIN,
+      -bf16::ONE,
+      bf16::NEG_ZERO,
+      bf16::ZERO,
+      bf16::from_f32(3.0),
+      bf16::from_f32(300.0),
+      bf16::MAX,
+      bf16::INFINITY,
+      bf16::NAN,
+    ] {
+      let int = x.int_float_to_latent();
+      let recovered = bf16::int_float_from_latent(int);
+      assert_eq!(
+        x.to_bits(),
+        recovered.to_bits(),
+        "{} != {}",
+        x,
+        recovered
+      );
+    }
+  }
+
   #[test]
   fn int_float32_invertibility() {
     for x in [
@@ -6,8 +6,10 @@ use std::ops::{
 };
 
 pub use dynamic::{LatentType, NumberType};
+pub use number_like::NumberLike;
 pub use split_latents::SplitLatents;
 
+use crate::aggregate::NumberSum;
 use crate::constants::Bitlen;
 use crate::describers::LatentDescriber;
 use crate::errors::PcoResult;
@@ -18,6 +20,7 @@ use crate::ChunkConfig;
 
 mod dynamic;
 mod floats;
+mod number_like;
 mod signeds;
 mod split_latents;
 mod unsigneds;
@@ -144,7 +147,8 @@ pub trait Latent:
 ///   wouldn't preserve ordering and would cause pco to fail. In this example,
 ///   one needs to flip the sign bit and, if negative, the rest of the bits.
 ///
-/// Custom data types (defined outside of pco) are not currently supported.
+/// Custom data types (defined outside of pco) are best supported by
+/// implementing [`NumberLike`], which maps them to a built-in `Number`.
 pub trait Number: Copy + Debug + Display + Default + PartialEq + Send + Sync + 'static {
   /// A number from 1-255 that corresponds to the number's data type.
   ///
@@ -155,7 +159,7 @@ pub trait Number: Copy + Debug + Display + Default + PartialEq + Send + Sync + '
   ///
   /// To choose a header byte for a new data type, review all header bytes in
   /// the library and pick an unused one. For instance, as of writing, bytes
-  /// 1 through 6 are used, so 7 would be a good choice for another
+  /// 1 through 14 are used, so 15 would be a good choice for another
   /// `pco` data type implementation.
   const NUMBER_TYPE_BYTE: u8;
 
@@ -177,6 +181,14 @@ pub trait Number: Copy + Debug + Display + Default + PartialEq + Send + Sync + '
     nums: &[Self],
     config: &ChunkConfig,
   ) -> PcoResult<ModeAndLatents>;
+  /// Breaks the numbers into latent variables using a mode that was already
+  /// chosen, e.g. from a previous chunk's metadata.
+  ///
+  /// The mode must be valid for this type.
+  fn split_latents_with_mode(nums: &[Self], mode: Mode) -> SplitLatents;
+  /// Adds up the numbers, as used by
+  /// [`Aggregates`][crate::Aggregates].
+  fn sum(nums: &[Self]) -> NumberSum;
 
   fn from_latent_ordered(l: Self::L) -> Self;
   fn to_latent_ordered(self) -> Self::L;
@@ -0,0 +1,203 @@
+use std::fmt::{Debug, Display};
+use std::mem;
+
+use crate::aggregate::NumberSum;
+use crate::data_types::{ModeAndLatents, Number, SplitLatents};
+use crate::describers::LatentDescriber;
+use crate::errors::PcoResult;
+use crate::metadata::per_latent_var::PerLatentVar;
+use crate::metadata::{ChunkMeta, DynLatents, Mode};
+use crate::ChunkConfig;
+
+/// Trait for user-defined data types that can be compressed by mapping them
+/// to one of pco's built-in [`Number`] types.
+///
+/// Every `NumberLike` type automatically implements `Number`, so it works
+/// with the standalone and wrapped APIs, and pco chooses modes like IntMult
+/// exactly as it would for `Self::Repr`.
+/// For instance, a fixed-point price stored in cents could use `i64` as its
+/// representation.
+///
+/// Files are written with `Self::Repr`'s data type byte, so they can also be
+/// decompressed as `Self::Repr`.
+/// For good compression, `to_repr` should preserve ordering and
+/// `from_repr` should invert it.
+///
+/// # Safety
+///
+/// `Self` must have the same size and alignment as `Self::Repr`, and every
+/// bit pattern of that size must be a valid `Self`, since pco decompresses
+/// into `Self` buffers in place.
+/// A `#[repr(transparent)]` wrapper around `Self::Repr` or another primitive
+/// of the same size satisfies this.
+pub unsafe trait NumberLike:
+  Copy + Debug + Display + Default + PartialEq + Send + Sync + 'static
+{
+  /// The built-in number type this type gets compressed as.
+  type Repr: Number;
+
+  fn to_repr(self) -> Self::Repr;
+  fn from_repr(repr: Self::Repr) -> Self;
+}
+
+impl<T: NumberLike> Number for T {
+  const NUMBER_TYPE_BYTE: u8 = T::Repr::NUMBER_TYPE_BYTE;
+
+  type L = <T::Repr as Number>::L;
+
+  fn get_latent_describers(meta: &ChunkMeta) -> PerLatentVar<LatentDescriber> {
+    T::Repr::get_latent_describers(meta)
+  }
+
+  fn mode_is_valid(mode: Mode) -> bool {
+    T::Repr::mode_is_valid(mode)
+  }
+  fn choose_mode_and_split_latents(
+    nums: &[Self],
+    config: &ChunkConfig,
+  ) -> PcoResult<ModeAndLatents> {
+    let reprs = nums.iter().map(|&x| x.to_repr()).collect::<Vec<_>>();
+    T::Repr::choose_mode_and_split_latents(&reprs, config)
+  }
+  fn split_latents_with_mode(nums: &[Self], mode: Mode) -> SplitLatents {
+    let reprs = nums.iter().map(|&x| x.to_repr()).collect::<Vec<_>>();
+    T::Repr::split_latents_with_mode(&reprs, mode)
+  }
+  fn sum(nums: &[Self]) -> NumberSum {
+    let reprs = nums.iter().map(|&x| x.to_repr()).collect::<Vec<_>>();
+    T::Repr::sum(&reprs)
+  }
+
+  #[inline]
+  fn from_latent_ordered(l: Self::L) -> Self {
+    T::from_repr(T::Repr::from_latent_ordered(l))
+  }
+  #[inline]
+  fn to_latent_ordered(self) -> Self::L {
+    self.to_repr().to_latent_ordered()
+  }
+  fn join_latents(mode: Mode, primary: &mut [Self::L], secondary: Option<&DynLatents>) {
+    T::Repr::join_latents(mode, primary, secondary)
+  }
+
+  fn transmute_to_latents(slice: &mut [Self]) -> &mut [Self::L] {
+    // Safety: NumberLike guarantees Self and Self::Repr have the same layout,
+    // and every Number's latent has the same layout as the number.
+    unsafe { mem::transmute(slice) }
+  }
+  #[inline]
+  fn transmute_to_latent(self) -> Self::L {
+    unsafe { mem::transmute_copy(&self) }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fmt::{Display, Formatter};
+
+  use super::*;
+  use crate::metadata::DynLatent;
+  use crate::standalone::{simple_compress, simple_decompress};
+  use crate::wrapped::{FileCompressor, FileDecompressor};
+
+  // a fixed-point price in cents
+  #[derive(Clone, Copy, Debug, Default, PartialEq)]
+  #[repr(transparent)]
+  struct Price(i64);
+
+  impl Display for Price {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+      write!(
+        f,
+        "${}.{:02}",
+        self.0 / 100,
+        self.0.abs() % 100
+      )
+    }
+  }
+
+  unsafe impl NumberLike for Price {
+    type Repr = i64;
+
+    fn to_repr(self) -> i64 {
+      self.0
+    }
+    fn from_repr(repr: i64) -> Self {
+      Self(repr)
+    }
+  }
+
+  // an ID whose bits are stored reversed in memory, to exercise a
+  // non-trivial mapping
+  #[derive(Clone, Copy, Debug, Default, PartialEq)]
+  #[repr(transparent)]
+  struct ReversedId(u32);
+
+  impl Display for ReversedId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+      write!(f, "{}", self.0.reverse_bits())
+    }
+  }
+
+  unsafe impl NumberLike for ReversedId {
+    type Repr = u32;
+
+    fn to_repr(self) -> u32 {
+      self.0.reverse_bits()
+    }
+    fn from_repr(repr: u32) -> Self {
+      Self(repr.reverse_bits())
+    }
+  }
+
+  #[test]
+  fn test_standalone_recovery() -> PcoResult<()> {
+    let prices = (0..1000)
+      .map(|i| Price((i % 37 - 10) * 100))
+      .collect::<Vec<_>>();
+    let compressed = simple_compress(&prices, &ChunkConfig::default())?;
+    assert_eq!(
+      simple_decompress::<Price>(&compressed)?,
+      prices
+    );
+
+    // the file is also readable as the underlying representation
+    let reprs = prices.iter().map(|p| p.0).collect::<Vec<_>>();
+    assert_eq!(simple_decompress::<i64>(&compressed)?, reprs);
+
+    let ids = (0..1000)
+      .map(|i| ReversedId::from_repr(i * 3))
+      .collect::<Vec<_>>();
+    let compressed = simple_compress(&ids, &ChunkConfig::default())?;
+    assert_eq!(
+      simple_decompress::<ReversedId>(&compressed)?,
+      ids
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_wrapped_int_mult() -> PcoResult<()> {
+    let prices = (0..1000)
+      .map(|i| Price((i * 7919) % 1013 * 100 + 1))
+      .collect::<Vec<_>>();
+
+    let fc = FileCompressor::default();
+    let mut compressed = fc.write_header(Vec::new())?;
+    let cc = fc.chunk_compressor(&prices, &ChunkConfig::default())?;
+    assert_eq!(
+      cc.meta().mode,
+      Mode::IntMult(DynLatent::new(100_u64).unwrap())
+    );
+    compressed = cc.write_chunk_meta(compressed)?;
+    compressed = cc.write_page(0, compressed)?;
+
+    let (fd, src) = FileDecompressor::new(compressed.as_slice())?;
+    let (cd, src) = fd.chunk_decompressor::<Price, _>(src)?;
+    let mut pd = cd.page_decompressor(src, prices.len())?;
+    let mut recovered = vec![Price::default(); prices.len()];
+    pd.decompress(&mut recovered)?;
+    assert_eq!(recovered, prices);
+    Ok(())
+  }
+}
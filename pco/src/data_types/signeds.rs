@@ -1,6 +1,7 @@
 use std::mem;
 
-use crate::data_types::{unsigneds, ModeAndLatents, Number};
+use crate::aggregate::NumberSum;
+use crate::data_types::{split_latents_classic, unsigneds, ModeAndLatents, Number, SplitLatents};
 use crate::describers::LatentDescriber;
 use crate::errors::PcoResult;
 use crate::metadata::per_latent_var::PerLatentVar;
@@ -29,6 +30,23 @@ macro_rules! impl_signed {
       ) -> PcoResult<ModeAndLatents> {
         unsigneds::choose_mode_and_split_latents(&nums, config)
       }
+      fn split_latents_with_mode(nums: &[Self], mode: Mode) -> SplitLatents {
+        match mode {
+          Mode::Classic => split_latents_classic(nums),
+          Mode::IntMult(dyn_latent) => {
+            let base = *dyn_latent.downcast_ref::<Self::L>().unwrap();
+            int_mult_utils::split_latents(nums, base)
+          }
+          _ => unreachable!("impossible mode for signed ints"),
+        }
+      }
+      fn sum(nums: &[Self]) -> NumberSum {
+        NumberSum::Int(
+          nums
+            .iter()
+            .fold(0_i128, |sum, &x| sum.wrapping_add(x as i128)),
+        )
+      }
 
       #[inline]
       fn from_latent_ordered(l: Self::L) -> Self {
@@ -63,6 +81,8 @@ macro_rules! impl_signed {
 impl_signed!(i32, u32, 3);
 impl_signed!(i64, u64, 4);
 impl_signed!(i16, u16, 8);
+impl_signed!(i8, u8, 11);
+impl_signed!(i128, u128, 13);
 
 #[cfg(test)]
 mod tests {
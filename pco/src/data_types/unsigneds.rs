@@ -1,16 +1,23 @@
 use super::ModeAndLatents;
+use crate::aggregate::NumberSum;
 use crate::constants::Bitlen;
-use crate::data_types::{split_latents_classic, Latent, Number};
+use crate::data_types::{split_latents_classic, Latent, Number, SplitLatents};
 use crate::describers::LatentDescriber;
 use crate::errors::{PcoError, PcoResult};
 use crate::metadata::per_latent_var::PerLatentVar;
 use crate::metadata::{ChunkMeta, DynLatent, DynLatents, Mode};
-use crate::{describers, int_mult_utils, ChunkConfig, ModeSpec};
+use crate::{describers, int_mult_utils, ChunkConfig, LossySpec, ModeSpec};
 
 pub fn choose_mode_and_split_latents<T: Number>(
   nums: &[T],
   config: &ChunkConfig,
 ) -> PcoResult<ModeAndLatents> {
+  if config.lossy_spec != LossySpec::Lossless {
+    return Err(PcoError::invalid_argument(
+      "unable to use lossy compression for ints",
+    ));
+  }
+
   match config.mode_spec {
     ModeSpec::Auto => {
       if let Some(base) = int_mult_utils::choose_base(nums) {
@@ -80,9 +87,11 @@ macro_rules! impl_latent {
   };
 }
 
+impl_latent!(u8);
 impl_latent!(u16);
 impl_latent!(u32);
 impl_latent!(u64);
+impl_latent!(u128);
 
 macro_rules! impl_unsigned_number {
   ($t: ty, $header_byte: expr) => {
@@ -106,6 +115,23 @@ macro_rules! impl_unsigned_number {
       ) -> PcoResult<ModeAndLatents> {
         choose_mode_and_split_latents(nums, config)
       }
+      fn split_latents_with_mode(nums: &[Self], mode: Mode) -> SplitLatents {
+        match mode {
+          Mode::Classic => split_latents_classic(nums),
+          Mode::IntMult(dyn_latent) => {
+            let base = *dyn_latent.downcast_ref::<Self::L>().unwrap();
+            int_mult_utils::split_latents(nums, base)
+          }
+          _ => unreachable!("impossible mode for unsigned ints"),
+        }
+      }
+      fn sum(nums: &[Self]) -> NumberSum {
+        NumberSum::Int(
+          nums
+            .iter()
+            .fold(0_i128, |sum, &x| sum.wrapping_add(x as i128)),
+        )
+      }
 
       #[inline]
       fn from_latent_ordered(l: Self::L) -> Self {
@@ -140,6 +166,8 @@ macro_rules! impl_unsigned_number {
 impl_unsigned_number!(u32, 1);
 impl_unsigned_number!(u64, 2);
 impl_unsigned_number!(u16, 7);
+impl_unsigned_number!(u8, 10);
+impl_unsigned_number!(u128, 12);
 
 #[cfg(test)]
 mod tests {
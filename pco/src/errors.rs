@@ -6,6 +6,10 @@ use std::{fmt, io};
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ErrorKind {
+  /// `ChecksumMismatch` errors occur during decompression, indicating the
+  /// data does not match the checksum stored alongside it.
+  /// This usually means the data was corrupted after it was written.
+  ChecksumMismatch,
   /// `Compatibility` errors occur during decompression, indicating the library
   /// version is not up-to-date enough for the provided data.
   Compatibility,
@@ -40,6 +44,10 @@ impl PcoError {
     }
   }
 
+  pub(crate) fn checksum_mismatch<S: AsRef<str>>(message: S) -> Self {
+    Self::new(ErrorKind::ChecksumMismatch, message)
+  }
+
   pub(crate) fn compatibility<S: AsRef<str>>(message: S) -> Self {
     Self::new(ErrorKind::Compatibility, message)
   }
@@ -0,0 +1,126 @@
+use crate::data_types::Number;
+use crate::errors::PcoResult;
+use crate::metadata::{DeltaEncoding, Mode};
+use crate::wrapped::chunk_compressor;
+use crate::ChunkConfig;
+
+/// A prediction of how pco would compress a chunk of numbers.
+///
+/// See [`estimate_compressed_size`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SizeEstimate {
+  /// The mode pco would most likely choose.
+  pub mode: Mode,
+  /// The delta encoding pco would most likely choose.
+  pub delta_encoding: DeltaEncoding,
+  /// The approximate byte size of the wrapped chunk, including its
+  /// metadata and pages.
+  pub size: usize,
+}
+
+impl SizeEstimate {
+  /// Returns the estimated compression ratio relative to the numbers'
+  /// uncompressed size.
+  pub fn compression_ratio<T: Number>(&self, n: usize) -> f64 {
+    (n * std::mem::size_of::<T>()) as f64 / self.size as f64
+  }
+}
+
+/// Cheaply estimates how well the numbers would compress as a single chunk
+/// with this config.
+///
+/// Will return an error if any arguments provided are invalid, e.g. if there
+/// are no numbers or too many for a chunk.
+///
+/// This chooses the mode and delta encoding as compression would, but only
+/// trains bins on a sample of the latents and predicts their cost without
+/// writing any pages, so it is typically much faster than compressing.
+/// Standalone files add a few bytes to this for their header and framing.
+///
+/// Example:
+/// ```
+/// use pco::{estimate_compressed_size, ChunkConfig};
+/// # use pco::errors::PcoResult;
+///
+/// # fn main() -> PcoResult<()> {
+/// let nums = (0..10000).map(|i| i * 3).collect::<Vec<i64>>();
+/// let estimate = estimate_compressed_size(&nums, &ChunkConfig::default())?;
+/// assert!(estimate.compression_ratio::<i64>(nums.len()) > 10.0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn estimate_compressed_size<T: Number>(
+  nums: &[T],
+  config: &ChunkConfig,
+) -> PcoResult<SizeEstimate> {
+  let (mode, delta_encoding, size) = chunk_compressor::estimate(nums, config)?;
+  Ok(SizeEstimate {
+    mode,
+    delta_encoding,
+    size,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::chunk_config::DeltaSpec;
+  use crate::wrapped::FileCompressor;
+
+  fn check_estimate<T: Number>(nums: &[T], config: &ChunkConfig) -> PcoResult<()> {
+    let estimate = estimate_compressed_size(nums, config)?;
+    let cc = FileCompressor::default().chunk_compressor(nums, config)?;
+    let compressed = cc.write_pages(cc.write_chunk_meta(Vec::new())?)?;
+    assert_eq!(estimate.mode, cc.meta().mode);
+    assert_eq!(
+      estimate.delta_encoding,
+      cc.meta().delta_encoding
+    );
+    let ratio = estimate.size as f64 / compressed.len() as f64;
+    assert!(
+      (0.8..1.25).contains(&ratio),
+      "estimated {} but compressed to {}",
+      estimate.size,
+      compressed.len(),
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_estimates() -> PcoResult<()> {
+    let config = ChunkConfig::default();
+    let ints = (0..20000_i64)
+      .map(|i| (i * 1000) + (i * i) % 17)
+      .collect::<Vec<_>>();
+    check_estimate(&ints, &config)?;
+    let floats = (0..20000)
+      .map(|i| (i as f64 * 0.01).sin())
+      .collect::<Vec<_>>();
+    check_estimate(&floats, &config)?;
+    let decimals = (0..20000)
+      .map(|i| ((i * 7919) % 1000) as f32 * 0.1)
+      .collect::<Vec<_>>();
+    check_estimate(&decimals, &config)?;
+    check_estimate(
+      &ints,
+      &config
+        .clone()
+        .with_delta_spec(DeltaSpec::None)
+        .with_store_stats(true),
+    )?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_estimate_incompressible() -> PcoResult<()> {
+    // pseudorandom numbers shouldn't be estimated larger than the baseline
+    let nums = (0..5000_u64)
+      .map(|i| i.wrapping_mul(0x9e3779b97f4a7c15))
+      .collect::<Vec<_>>();
+    let estimate = estimate_compressed_size(&nums, &ChunkConfig::default())?;
+    assert!(estimate.size <= crate::wrapped::guarantee::chunk_size::<u64>(nums.len()));
+    assert!(estimate_compressed_size::<u64>(&[], &ChunkConfig::default()).is_err());
+    Ok(())
+  }
+}
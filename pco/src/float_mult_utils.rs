@@ -55,6 +55,39 @@ pub(crate) fn split_latents<F: Float>(page_nums: &[F], config: FloatMultConfig<F
   }
 }
 
+// Like `split_latents`, but rounds each number to its nearest multiple of
+// `base` whenever that stays within `max_abs_error`, so nearly all
+// adjustments are trivial.
+pub(crate) fn split_latents_lossy<F: Float>(
+  page_nums: &[F],
+  config: FloatMultConfig<F>,
+  max_abs_error: f64,
+) -> SplitLatents {
+  let FloatMultConfig { base, inv_base } = config;
+  let mut primary = Vec::with_capacity(page_nums.len());
+  let mut adjustments = Vec::with_capacity(page_nums.len());
+  for &num in page_nums {
+    let mult = (num * inv_base).round();
+    let approx = mult * base;
+    primary.push(F::int_float_to_latent(mult));
+    // The subtraction is exact since `num` and `approx` are close, and
+    // non-finite numbers fail the check, keeping their exact adjustments.
+    let adj = if (num.to_f64() - approx.to_f64()).abs() <= max_abs_error {
+      F::L::ZERO
+    } else {
+      num
+        .to_latent_ordered()
+        .wrapping_sub(approx.to_latent_ordered())
+    };
+    adjustments.push(adj.toggle_center());
+  }
+
+  SplitLatents {
+    primary: DynLatents::new(primary).unwrap(),
+    secondary: Some(DynLatents::new(adjustments).unwrap()),
+  }
+}
+
 // The rest of this file concerns automatically detecting the float `base`
 // such that `x = mult * base + adj * ULP` usefully splits a delta `x` into
 // latent variables `mult` and `adj` (if such a `base` exists).
@@ -316,7 +349,7 @@ pub(crate) struct FloatMultConfig<F: Float> {
 }
 
 impl<F: Float> FloatMultConfig<F> {
-  fn from_base(base: F) -> Self {
+  pub(crate) fn from_base(base: F) -> Self {
     Self {
       base,
       inv_base: base.inv(),
@@ -67,6 +67,48 @@ pub(crate) fn split_latents<F: Float>(page_nums: &[F], k: Bitlen) -> SplitLatent
   }
 }
 
+// Rounds each finite number's mantissa to a multiple of 2^k, with ties to
+// even, so that FloatQuant(k) splits it into a trivial secondary latent.
+// This rounding is part of pco's lossy spec and must never change, since it
+// determines what users get back when they decompress.
+pub(crate) fn round_mantissas<F: Float>(nums: &[F], k: Bitlen) -> Vec<F> {
+  let mag_mask = F::L::MID - F::L::ONE;
+  let inf_mag = F::from_f64(f64::INFINITY).to_latent_bits() & mag_mask;
+  let lowest_k_bits_max = (F::L::ONE << k) - F::L::ONE;
+  let half = F::L::ONE << (k - 1);
+  nums
+    .iter()
+    .map(|&num| {
+      let mag = num.to_latent_bits() & mag_mask;
+      if mag >= inf_mag {
+        // infinities and NaNs are kept exactly
+        return num;
+      }
+
+      let lowest_k_bits = mag & lowest_k_bits_max;
+      let truncated = mag - lowest_k_bits;
+      let is_odd = (truncated >> k) & F::L::ONE == F::L::ONE;
+      let rounded = if lowest_k_bits > half || (lowest_k_bits == half && is_odd) {
+        truncated + (F::L::ONE << k)
+      } else {
+        truncated
+      };
+      // we never round finite numbers up to infinity
+      let rounded = if rounded >= inf_mag {
+        truncated
+      } else {
+        rounded
+      };
+
+      if num.is_sign_positive_() {
+        F::from_latent_ordered(F::L::MID + rounded)
+      } else {
+        F::from_latent_ordered(mag_mask - rounded)
+      }
+    })
+    .collect()
+}
+
 pub(crate) fn compute_bid<F: Float>(sample: &[F]) -> Option<Bid<F>> {
   let (k, bits_saved_per_infrequent_primary) = estimate_best_k_and_bits_saved(sample);
   let bits_saved_per_num = sampling::est_bits_saved_per_num(sample, |x| {
@@ -16,21 +16,33 @@
 #[cfg(doctest)]
 struct ReadmeDoctest;
 
-pub use chunk_config::{ChunkConfig, DeltaSpec, ModeSpec, PagingSpec};
+pub use aggregate::{Aggregates, BinBounds, NumberSum};
+pub use chunk_config::{ChunkConfig, DeltaSpec, LossySpec, ModeSpec, PagingSpec};
 pub use constants::{DEFAULT_COMPRESSION_LEVEL, DEFAULT_MAX_PAGE_N, FULL_BATCH_N};
+pub use estimate::{estimate_compressed_size, SizeEstimate};
 pub use progress::Progress;
 
+/// for compressing/decompressing Arrow arrays
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod data_types;
 /// for inspecting certain types of Pco metadata
 pub mod describers;
 pub mod errors;
 /// structs representing stored information about how compression was done
 pub mod metadata;
+/// for storing pco data in Parquet files, as a container convention rather
+/// than a Parquet encoding
+#[cfg(feature = "parquet")]
+pub mod parquet;
 /// for compressing/decompressing .pco files
 pub mod standalone;
+/// for compressing/decompressing tables of named columns
+pub mod table;
 /// for compressing/decompressing as part of an outer, wrapping format
 pub mod wrapped;
 
+mod aggregate;
 mod ans;
 mod bin_optimization;
 mod bit_reader;
@@ -40,7 +52,9 @@ mod chunk_config;
 mod compression_intermediates;
 mod compression_table;
 mod constants;
+mod counting_writer;
 mod delta;
+mod estimate;
 mod float_mult_utils;
 mod float_quant_utils;
 mod histograms;
@@ -49,6 +63,7 @@ mod latent_batch_dissector;
 mod latent_chunk_compressor;
 mod latent_page_decompressor;
 mod macros;
+mod parallel;
 mod progress;
 mod read_write_uint;
 mod sampling;
@@ -24,15 +24,20 @@ dtype_dispatch::build_dtype_macros!(
 
   Number,
   {
+    BF16 => half::bf16,
     F16 => half::f16,
     F32 => f32,
     F64 => f64,
+    I8 => i8,
     I16 => i16,
     I32 => i32,
     I64 => i64,
+    I128 => i128,
+    U8 => u8,
     U16 => u16,
     U32 => u32,
     U64 => u64,
+    U128 => u128,
   },
 );
 
@@ -52,12 +57,15 @@ dtype_dispatch::build_dtype_macros!(
 
   Latent,
   {
+    U8 => u8,
     U16 => u16,
     U32 => u32,
     U64 => u64,
+    U128 => u128,
   },
 );
 
 pub(crate) use define_latent_enum;
 pub(crate) use define_number_enum;
 pub(crate) use match_latent_enum;
+pub(crate) use match_number_enum;
@@ -2,15 +2,20 @@ use std::io::Write;
 
 use better_io::BetterBufRead;
 
+use crate::aggregate::BinBounds;
 use crate::bit_reader::BitReaderBuilder;
 use crate::bit_writer::BitWriter;
 use crate::constants::DeltaLookback;
-use crate::data_types::LatentType;
+use crate::data_types::{LatentType, Number};
 use crate::errors::{PcoError, PcoResult};
+use crate::macros::match_latent_enum;
 use crate::metadata::chunk_latent_var::ChunkLatentVarMeta;
 use crate::metadata::delta_encoding::DeltaEncoding;
+use crate::metadata::dyn_latent::DynLatent;
+use crate::metadata::error_bound::ErrorBound;
 use crate::metadata::format_version::FormatVersion;
 use crate::metadata::per_latent_var::PerLatentVar;
+use crate::metadata::stats::Stats;
 use crate::metadata::Mode;
 
 /// The metadata of a pco chunk.
@@ -24,6 +29,13 @@ pub struct ChunkMeta {
   /// compress/decompress the inputs
   /// according to the formula used by `mode`.
   pub per_latent_var: PerLatentVar<ChunkLatentVarMeta>,
+  /// Summary statistics of the chunk's numbers, if they were stored.
+  ///
+  /// When present, each page also stores its own statistics.
+  pub stats: Option<Stats>,
+  /// The guaranteed bound on the numbers' errors, if they were lossily
+  /// compressed.
+  pub error_bound: Option<ErrorBound>,
 }
 
 impl ChunkMeta {
@@ -35,6 +47,12 @@ impl ChunkMeta {
       .sum();
     let n_bits = self.mode.exact_bit_size() as usize
       + self.delta_encoding.exact_bit_size() as usize
+      + 1
+      + self.stats_bit_size()
+      + 1
+      + self
+        .error_bound
+        .map_or(0, |bound| bound.exact_bit_size() as usize)
       + bits_for_latent_vars;
     n_bits.div_ceil(8)
   }
@@ -47,10 +65,18 @@ impl ChunkMeta {
         let delta_encoding = self.delta_encoding.for_latent_var(key);
         var_meta.exact_page_meta_bit_size(delta_encoding)
       })
-      .sum();
+      .sum()
+      + self.stats_bit_size();
     bit_size.div_ceil(8)
   }
 
+  fn stats_bit_size(&self) -> usize {
+    self
+      .stats
+      .as_ref()
+      .map_or(0, |stats| stats.exact_bit_size() as usize)
+  }
+
   pub(crate) fn validate_delta_encoding(&self) -> PcoResult<()> {
     let delta_latent_var = &self.per_latent_var.delta;
     match (self.delta_encoding, delta_latent_var) {
@@ -74,16 +100,119 @@ impl ChunkMeta {
     }
   }
 
+  // Checks that metadata provided by the user has the latent variables and
+  // latent types that a chunk of this number type would.
+  pub(crate) fn validate_latent_types(&self, latent_type: LatentType) -> PcoResult<()> {
+    let mode_base_matches = match self.mode {
+      Mode::IntMult(base) | Mode::FloatMult(base) => {
+        match_latent_enum!(
+          base,
+          DynLatent<L>(_base) => { LatentType::new::<L>().unwrap() == latent_type }
+        )
+      }
+      Mode::Classic | Mode::FloatQuant(_) => true,
+    };
+    let expected = PerLatentVar {
+      delta: self.delta_encoding.latent_type(),
+      primary: self.mode.primary_latent_type(latent_type),
+      secondary: self.mode.secondary_latent_type(latent_type),
+    };
+    let var_matches = |var_meta: Option<&ChunkLatentVarMeta>, expected: Option<LatentType>| {
+      var_meta.map(|var_meta| var_meta.latent_type()) == expected
+    };
+    let vars_match = var_matches(
+      self.per_latent_var.delta.as_ref(),
+      expected.delta,
+    ) && var_matches(
+      Some(&self.per_latent_var.primary),
+      Some(expected.primary),
+    ) && var_matches(
+      self.per_latent_var.secondary.as_ref(),
+      expected.secondary,
+    );
+
+    if mode_base_matches && vars_match {
+      Ok(())
+    } else {
+      Err(PcoError::invalid_argument(format!(
+        "chunk metadata does not match latent type {:?}",
+        latent_type,
+      )))
+    }
+  }
+
+  /// Returns the range of numbers covered by each bin, or `None` if the
+  /// bins don't describe the numbers directly or `T` doesn't match the
+  /// chunk's latent type.
+  ///
+  /// Bins only describe the numbers directly in classic mode without delta
+  /// encoding.
+  /// In that case, every number lies within the bounds of one of these
+  /// bins, so they work as a coarse histogram computed from the metadata
+  /// alone.
+  pub fn bin_bounds<T: Number>(&self) -> Option<Vec<BinBounds<T>>> {
+    if !matches!(
+      (self.mode, self.delta_encoding),
+      (Mode::Classic, DeltaEncoding::None)
+    ) {
+      return None;
+    }
+
+    let bins = self.per_latent_var.primary.bins.downcast_ref::<T::L>()?;
+    Some(
+      bins
+        .iter()
+        .map(|bin| BinBounds::new(bin.lower, bin.offset_bits, bin.weight))
+        .collect(),
+    )
+  }
+
+  /// Returns bounds on the smallest and largest numbers in the chunk, or
+  /// `None` if they can't be determined from the metadata alone.
+  ///
+  /// These are exact if the chunk stored its statistics.
+  /// Otherwise they come from the [`bin_bounds`][Self::bin_bounds], which
+  /// contain every number but may be loose.
+  pub fn bounds<T: Number>(&self) -> Option<(T, T)> {
+    if let Some(stats) = &self.stats {
+      return Some((stats.min::<T>()?, stats.max::<T>()?));
+    }
+
+    let bin_bounds = self.bin_bounds::<T>()?;
+    let lower = bin_bounds
+      .iter()
+      .map(|bounds| bounds.lower.to_latent_ordered())
+      .min()?;
+    let upper = bin_bounds
+      .iter()
+      .map(|bounds| bounds.upper.to_latent_ordered())
+      .max()?;
+    Some((
+      T::from_latent_ordered(lower),
+      T::from_latent_ordered(upper),
+    ))
+  }
+
   pub(crate) unsafe fn read_from<R: BetterBufRead>(
     reader_builder: &mut BitReaderBuilder<R>,
     version: &FormatVersion,
     latent_type: LatentType,
   ) -> PcoResult<Self> {
-    let (mode, delta_encoding) = reader_builder.with_reader(|reader| {
+    let (mode, delta_encoding, stats, error_bound) = reader_builder.with_reader(|reader| {
       let mode = Mode::read_from(reader, version, latent_type)?;
       let delta_encoding = DeltaEncoding::read_from(version, reader)?;
+      let stats = if version.supports_stats() && reader.read_bool() {
+        Some(Stats::read_from(reader, latent_type)?)
+      } else {
+        None
+      };
+      let error_bound = if version.supports_error_bounds() && reader.read_bool() {
+        Some(ErrorBound::read_from(reader)?)
+      } else {
+        None
+      };
 
-      Ok((mode, delta_encoding))
+      Ok((mode, delta_encoding, stats, error_bound))
     })?;
 
     let delta = if let Some(delta_latent_type) = delta_encoding.latent_type() {
@@ -123,12 +252,22 @@ impl ChunkMeta {
       mode,
       delta_encoding,
       per_latent_var,
+      stats,
+      error_bound,
     })
   }
 
   pub(crate) unsafe fn write_to<W: Write>(&self, writer: &mut BitWriter<W>) -> PcoResult<()> {
     self.mode.write_to(writer);
     self.delta_encoding.write_to(writer);
+    writer.write_bool(self.stats.is_some());
+    if let Some(stats) = &self.stats {
+      stats.write_to(writer);
+    }
+    writer.write_bool(self.error_bound.is_some());
+    if let Some(error_bound) = &self.error_bound {
+      error_bound.write_to(writer);
+    }
 
     writer.flush()?;
 
@@ -180,6 +319,7 @@ mod tests {
           ans_final_state_idxs: [0; ANS_INTERLEAVING],
         }
       }),
+      stats: meta.stats,
     };
     unsafe {
       page_meta.write_to(
@@ -211,6 +351,8 @@ mod tests {
         },
         secondary: None,
       },
+      stats: None,
+      error_bound: None,
     };
 
     check_exact_sizes(&meta)
@@ -233,6 +375,8 @@ mod tests {
         },
         secondary: None,
       },
+      stats: None,
+      error_bound: None,
     };
 
     check_exact_sizes(&meta)
@@ -279,6 +423,12 @@ mod tests {
           ]),
         }),
       },
+      stats: Some(Stats {
+        min: DynLatent::U32(3),
+        max: DynLatent::U32(77),
+        count: 100,
+      }),
+      error_bound: Some(ErrorBound::MaxAbsError(0.5)),
     };
 
     check_exact_sizes(&meta)
@@ -0,0 +1,107 @@
+use std::io::Write;
+
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::constants::{
+  Bitlen, BITS_TO_ENCODE_ERROR_BOUND_VARIANT, BITS_TO_ENCODE_SIGNIFICANT_BITS,
+};
+use crate::errors::{PcoError, PcoResult};
+
+/// A guarantee on how far lossily compressed numbers may be from their
+/// original values.
+///
+/// This is only stored if a lossy
+/// [`ChunkConfig::lossy_spec`][crate::ChunkConfig::lossy_spec] was used during
+/// compression.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum ErrorBound {
+  /// Every number is within this absolute distance of its original value.
+  ///
+  /// Non-finite numbers are exact.
+  MaxAbsError(f64),
+  /// Every number's mantissa was rounded to this many bits.
+  ///
+  /// Non-finite numbers are exact.
+  SignificantBits(Bitlen),
+}
+
+// We compare bits so that error bounds (and therefore chunk metas) have a
+// proper equivalence relation.
+impl PartialEq for ErrorBound {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Self::MaxAbsError(a), Self::MaxAbsError(b)) => a.to_bits() == b.to_bits(),
+      (Self::SignificantBits(a), Self::SignificantBits(b)) => a == b,
+      _ => false,
+    }
+  }
+}
+
+impl Eq for ErrorBound {}
+
+impl ErrorBound {
+  /// Returns the max absolute error, if this bound has one.
+  pub fn max_abs_error(&self) -> Option<f64> {
+    match self {
+      Self::MaxAbsError(max_abs_error) => Some(*max_abs_error),
+      _ => None,
+    }
+  }
+
+  /// Returns the count of mantissa bits kept, if this bound has one.
+  pub fn significant_bits(&self) -> Option<Bitlen> {
+    match self {
+      Self::SignificantBits(significant_bits) => Some(*significant_bits),
+      _ => None,
+    }
+  }
+
+  pub(crate) fn exact_bit_size(&self) -> Bitlen {
+    let payload_bits = match self {
+      Self::MaxAbsError(_) => 64,
+      Self::SignificantBits(_) => BITS_TO_ENCODE_SIGNIFICANT_BITS,
+    };
+    BITS_TO_ENCODE_ERROR_BOUND_VARIANT + payload_bits
+  }
+
+  pub(crate) unsafe fn read_from(reader: &mut BitReader) -> PcoResult<Self> {
+    let bound = match reader.read_bitlen(BITS_TO_ENCODE_ERROR_BOUND_VARIANT) {
+      0 => {
+        let max_abs_error = f64::from_bits(reader.read_uint::<u64>(64));
+        if max_abs_error.is_nan() || max_abs_error <= 0.0 {
+          reader.check_in_bounds()?;
+          return Err(PcoError::corruption(format!(
+            "invalid max absolute error bound {}",
+            max_abs_error,
+          )));
+        }
+        Self::MaxAbsError(max_abs_error)
+      }
+      1 => Self::SignificantBits(reader.read_bitlen(BITS_TO_ENCODE_SIGNIFICANT_BITS)),
+      value => {
+        reader.check_in_bounds()?;
+        return Err(PcoError::corruption(format!(
+          "unknown error bound value {}",
+          value
+        )));
+      }
+    };
+    Ok(bound)
+  }
+
+  pub(crate) unsafe fn write_to<W: Write>(&self, writer: &mut BitWriter<W>) {
+    let variant = match self {
+      Self::MaxAbsError(_) => 0,
+      Self::SignificantBits(_) => 1,
+    };
+    writer.write_bitlen(variant, BITS_TO_ENCODE_ERROR_BOUND_VARIANT);
+    match self {
+      Self::MaxAbsError(max_abs_error) => writer.write_uint(max_abs_error.to_bits(), 64),
+      &Self::SignificantBits(significant_bits) => writer.write_bitlen(
+        significant_bits,
+        BITS_TO_ENCODE_SIGNIFICANT_BITS,
+      ),
+    }
+  }
+}
@@ -50,4 +50,12 @@ impl FormatVersion {
   pub(crate) fn supports_delta_variants(&self) -> bool {
     self.0 >= 3
   }
+
+  pub(crate) fn supports_stats(&self) -> bool {
+    self.0 >= 4
+  }
+
+  pub(crate) fn supports_error_bounds(&self) -> bool {
+    self.0 >= 4
+  }
 }
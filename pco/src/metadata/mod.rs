@@ -5,8 +5,10 @@ pub use delta_encoding::{DeltaConsecutiveConfig, DeltaEncoding, DeltaLookbackCon
 pub use dyn_bins::DynBins;
 pub use dyn_latent::DynLatent;
 pub use dyn_latents::DynLatents;
+pub use error_bound::ErrorBound;
 pub use mode::Mode;
 pub use per_latent_var::{LatentVarKey, PerLatentVar};
+pub use stats::Stats;
 
 pub(crate) mod bin;
 pub(crate) mod bins;
@@ -16,8 +18,10 @@ pub(crate) mod delta_encoding;
 pub(crate) mod dyn_bins;
 pub(crate) mod dyn_latent;
 pub(crate) mod dyn_latents;
+pub(crate) mod error_bound;
 pub(crate) mod format_version;
 pub(crate) mod mode;
 pub(crate) mod page;
 pub(crate) mod page_latent_var;
 pub(crate) mod per_latent_var;
+pub(crate) mod stats;
@@ -6,6 +6,7 @@ use crate::constants::Bitlen;
 use crate::errors::PcoResult;
 use crate::metadata::page_latent_var::PageLatentVarMeta;
 use crate::metadata::per_latent_var::PerLatentVar;
+use crate::metadata::stats::Stats;
 use crate::metadata::ChunkMeta;
 
 // Data page metadata is slightly semantically different from chunk metadata,
@@ -16,6 +17,8 @@ use crate::metadata::ChunkMeta;
 #[derive(Clone, Debug)]
 pub struct PageMeta {
   pub per_latent_var: PerLatentVar<PageLatentVarMeta>,
+  // present if and only if the chunk has stats
+  pub stats: Option<Stats>,
 }
 
 impl PageMeta {
@@ -30,6 +33,9 @@ impl PageMeta {
     {
       latent_var_meta.write_to(ans_size_log, writer);
     }
+    if let Some(stats) = &self.stats {
+      stats.write_to(writer);
+    }
     writer.finish_byte();
   }
 
@@ -49,8 +55,19 @@ impl PageMeta {
           chunk_latent_var_meta.ans_size_log,
         )
       });
+    let stats = if chunk_meta.stats.is_some() {
+      Some(Stats::read_from(
+        reader,
+        chunk_meta.per_latent_var.primary.latent_type(),
+      )?)
+    } else {
+      None
+    };
     reader.drain_empty_byte("non-zero bits at end of data page metadata")?;
 
-    Ok(Self { per_latent_var })
+    Ok(Self {
+      per_latent_var,
+      stats,
+    })
   }
 }
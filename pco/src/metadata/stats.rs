@@ -0,0 +1,111 @@
+use std::io::Write;
+
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::constants::{Bitlen, BITS_TO_ENCODE_STATS_COUNT, MAX_ENTRIES};
+use crate::data_types::{Latent, LatentType, Number};
+use crate::errors::{PcoError, PcoResult};
+use crate::macros::match_latent_enum;
+use crate::metadata::dyn_latent::DynLatent;
+
+/// Optional summary statistics of the numbers in a chunk or page.
+///
+/// These are only stored if
+/// [`ChunkConfig::store_stats`][crate::ChunkConfig::store_stats] was enabled
+/// during compression.
+/// They can be read without decoding any latents, e.g. to skip pages that
+/// can't match a filter.
+///
+/// The min and max are stored as the numbers' order-preserving latents, so
+/// they follow pco's total order on numbers.
+/// For instance, a float page containing NaN may have NaN as its max.
+/// Since pco has no concept of nulls, all numbers in a chunk are counted.
+/// No sum is stored: an exact one would take up to 128 bits, and float sums
+/// depend on summation order; use
+/// [`PageDecompressor::aggregate`][crate::wrapped::PageDecompressor::aggregate]
+/// to compute one.
+/// For lossily compressed chunks, these describe the rounded numbers, i.e.
+/// what decompression returns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+  /// The latent of the smallest number.
+  pub min: DynLatent,
+  /// The latent of the largest number.
+  pub max: DynLatent,
+  /// The count of numbers.
+  pub count: usize,
+}
+
+impl Stats {
+  pub(crate) fn compute<L: Latent>(latents: &[L]) -> Self {
+    let mut min = L::MAX;
+    let mut max = L::ZERO;
+    for &latent in latents {
+      min = min.min(latent);
+      max = max.max(latent);
+    }
+    Self {
+      min: DynLatent::new(min).unwrap(),
+      max: DynLatent::new(max).unwrap(),
+      count: latents.len(),
+    }
+  }
+
+  /// Returns the smallest number, or `None` if `T` doesn't match the data
+  /// type's latent type.
+  pub fn min<T: Number>(&self) -> Option<T> {
+    let latent = self.min.downcast_ref::<T::L>()?;
+    Some(T::from_latent_ordered(*latent))
+  }
+
+  /// Returns the largest number, or `None` if `T` doesn't match the data
+  /// type's latent type.
+  pub fn max<T: Number>(&self) -> Option<T> {
+    let latent = self.max.downcast_ref::<T::L>()?;
+    Some(T::from_latent_ordered(*latent))
+  }
+
+  pub(crate) fn exact_bit_size(&self) -> Bitlen {
+    self.min.bits() + self.max.bits() + BITS_TO_ENCODE_STATS_COUNT
+  }
+
+  pub(crate) unsafe fn read_from(
+    reader: &mut BitReader,
+    latent_type: LatentType,
+  ) -> PcoResult<Self> {
+    match_latent_enum!(
+      latent_type,
+      LatentType<L> => {
+        let min = reader.read_uint::<L>(L::BITS);
+        let max = reader.read_uint::<L>(L::BITS);
+        if min > max {
+          reader.check_in_bounds()?;
+          return Err(PcoError::corruption(format!(
+            "stats min latent {} exceeds max latent {}",
+            min, max,
+          )));
+        }
+        let count = reader.read_usize(BITS_TO_ENCODE_STATS_COUNT);
+        if count > MAX_ENTRIES {
+          reader.check_in_bounds()?;
+          return Err(PcoError::corruption(format!(
+            "stats count {} exceeds max count {}",
+            count, MAX_ENTRIES,
+          )));
+        }
+        Ok(Self {
+          min: DynLatent::new(min).unwrap(),
+          max: DynLatent::new(max).unwrap(),
+          count,
+        })
+      }
+    )
+  }
+
+  pub(crate) unsafe fn write_to<W: Write>(&self, writer: &mut BitWriter<W>) {
+    self.min.write_uncompressed_to(writer);
+    self.max.write_uncompressed_to(writer);
+    writer.write_usize(self.count, BITS_TO_ENCODE_STATS_COUNT);
+  }
+}
@@ -0,0 +1,33 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::metadata::per_latent_var::{LatentVarKey, PerLatentVar, PerLatentVarBuilder};
+
+// Applies `f` to each item, concurrently if the `parallel` feature is
+// enabled. Results are always returned in the same order as the items, so
+// callers produce the same bytes either way.
+#[cfg(feature = "parallel")]
+pub(crate) fn map<I: Send, O: Send, F: Fn(I) -> O + Send + Sync>(items: Vec<I>, f: F) -> Vec<O> {
+  items.into_par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map<I, O, F: Fn(I) -> O>(items: Vec<I>, f: F) -> Vec<O> {
+  items.into_iter().map(f).collect()
+}
+
+// like `PerLatentVar::map`, but possibly concurrent across latent variables
+pub(crate) fn map_per_latent_var<T: Send, S: Send, F: Fn(LatentVarKey, T) -> S + Send + Sync>(
+  per_latent_var: PerLatentVar<T>,
+  f: F,
+) -> PerLatentVar<S> {
+  let mut res = PerLatentVarBuilder::default();
+  let outputs = map(
+    per_latent_var.enumerated(),
+    |(key, value)| (key, f(key, value)),
+  );
+  for (key, output) in outputs {
+    res.set(key, output);
+  }
+  res.into()
+}
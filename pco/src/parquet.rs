@@ -0,0 +1,755 @@
+use std::collections::VecDeque;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, ArrayRef, ArrowPrimitiveType, BinaryArray, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::arrow_reader::{
+  ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReaderBuilder,
+};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Encoding;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::ChunkReader;
+use parquet::schema::types::ColumnPath;
+
+use crate::arrow::{match_arrow_type, nullable_primitive};
+use crate::data_types::Number;
+use crate::errors::{PcoError, PcoResult};
+use crate::{wrapped, ChunkConfig, PagingSpec};
+
+// field metadata marking a binary column as pco data, along with the
+// version of this layout and the original field's data type and nullability
+const VERSION_KEY: &str = "pco.version";
+const DATA_TYPE_KEY: &str = "pco.data_type";
+const NULLABLE_KEY: &str = "pco.nullable";
+// bump this whenever the layout of pco columns changes incompatibly
+const VERSION: &str = "1";
+
+fn parquet_error(err: ParquetError) -> PcoError {
+  match err {
+    ParquetError::EOF(message) => PcoError::insufficient_data(message),
+    ParquetError::NeedMoreData(n) => {
+      PcoError::insufficient_data(format!("Parquet data needs {} bytes", n))
+    }
+    ParquetError::NYI(message) => PcoError::compatibility(message),
+    other => PcoError::corruption(other.to_string()),
+  }
+}
+
+fn arrow_error(err: ArrowError) -> PcoError {
+  PcoError::invalid_argument(err.to_string())
+}
+
+fn check_supported<P: ArrowPrimitiveType>() -> PcoResult<()>
+where
+  P::Native: Number,
+{
+  Ok(())
+}
+
+fn is_supported(data_type: &DataType) -> bool {
+  let res: PcoResult<()> = match_arrow_type!(data_type, check_supported());
+  res.is_ok()
+}
+
+fn pco_field(field: &Field) -> Field {
+  let mut metadata = field.metadata().clone();
+  metadata.insert(VERSION_KEY.to_string(), VERSION.to_string());
+  metadata.insert(
+    DATA_TYPE_KEY.to_string(),
+    field.data_type().to_string(),
+  );
+  metadata.insert(
+    NULLABLE_KEY.to_string(),
+    field.is_nullable().to_string(),
+  );
+  Field::new(field.name(), DataType::Binary, true).with_metadata(metadata)
+}
+
+// returns the original field of a column written as pco data, or None if
+// the column was written normally
+fn original_field(field: &Field) -> PcoResult<Option<Field>> {
+  let metadata = field.metadata();
+  let data_type_str = match metadata.get(DATA_TYPE_KEY) {
+    Some(data_type_str) => data_type_str,
+    None => return Ok(None),
+  };
+  match metadata.get(VERSION_KEY) {
+    Some(version) if version == VERSION => (),
+    version => {
+      return Err(PcoError::compatibility(format!(
+        "Parquet column {} has pco version {:?}, but only version {} is supported",
+        field.name(),
+        version,
+        VERSION,
+      )))
+    }
+  }
+  let nullable_str = metadata.get(NULLABLE_KEY).ok_or_else(|| {
+    PcoError::corruption(format!(
+      "missing pco nullability for Parquet column {}",
+      field.name(),
+    ))
+  })?;
+
+  let data_type = DataType::from_str(data_type_str).map_err(|_| {
+    PcoError::corruption(format!(
+      "invalid pco data type for Parquet column {}: {}",
+      field.name(),
+      data_type_str,
+    ))
+  })?;
+  let nullable = bool::from_str(nullable_str).map_err(|_| {
+    PcoError::corruption(format!(
+      "invalid pco nullability for Parquet column {}: {}",
+      field.name(),
+      nullable_str,
+    ))
+  })?;
+  let mut original_metadata = metadata.clone();
+  original_metadata.remove(VERSION_KEY);
+  original_metadata.remove(DATA_TYPE_KEY);
+  original_metadata.remove(NULLABLE_KEY);
+  Ok(Some(
+    Field::new(field.name(), data_type, nullable).with_metadata(original_metadata),
+  ))
+}
+
+// Compresses the non-null numbers of a row group's pieces as a wrapped
+// header, chunk metadata, and single page, and puts those bytes in the
+// first non-null row.
+// The other non-null rows are empty, so each piece keeps its validity.
+fn encode_column<P: ArrowPrimitiveType>(
+  pieces: &[ArrayRef],
+  config: &ChunkConfig,
+) -> PcoResult<Vec<ArrayRef>>
+where
+  P::Native: Number,
+{
+  let pieces = pieces
+    .iter()
+    .map(|piece| piece.as_primitive::<P>())
+    .collect::<Vec<_>>();
+  let mut values = Vec::new();
+  for piece in &pieces {
+    if piece.null_count() == 0 {
+      values.extend_from_slice(piece.values());
+    } else {
+      values.extend(piece.iter().flatten());
+    }
+  }
+
+  let mut payload = Vec::new();
+  if !values.is_empty() {
+    let mut config = config.clone();
+    config.paging_spec = PagingSpec::Exact(vec![values.len()]);
+    let file_compressor = wrapped::FileCompressor::default();
+    let chunk_compressor = file_compressor.chunk_compressor(&values, &config)?;
+    payload = file_compressor.write_header(payload)?;
+    payload = chunk_compressor.write_chunk_meta(payload)?;
+    payload = chunk_compressor.write_page(0, payload)?;
+  }
+
+  let mut payload = Some(payload);
+  let res = pieces
+    .iter()
+    .map(|piece| {
+      let binary = (0..piece.len())
+        .map(|i| {
+          if piece.is_null(i) {
+            None
+          } else {
+            Some(payload.take().unwrap_or_default())
+          }
+        })
+        .collect::<BinaryArray>();
+      Arc::new(binary) as ArrayRef
+    })
+    .collect();
+  Ok(res)
+}
+
+fn decode_column<P: ArrowPrimitiveType>(
+  binary: &BinaryArray,
+  data_type: &DataType,
+) -> PcoResult<ArrayRef>
+where
+  P::Native: Number,
+{
+  let n = binary.len() - binary.null_count();
+  let mut values = Vec::with_capacity(n);
+  if let Some(payload) = binary.iter().flatten().next() {
+    let (file_decompressor, src) = wrapped::FileDecompressor::new(payload)?;
+    let (chunk_decompressor, src) = file_decompressor.chunk_decompressor::<P::Native, _>(src)?;
+    let mut page_decompressor = chunk_decompressor.page_decompressor(src, n)?;
+    values.resize(n, P::Native::default());
+    page_decompressor.decompress(&mut values)?;
+  }
+
+  Ok(nullable_primitive::<P>(
+    values,
+    binary.nulls().cloned(),
+    data_type,
+  ))
+}
+
+/// Writes Arrow record batches to a Parquet file, storing each top-level
+/// numeric column as pco data.
+///
+/// Example:
+/// ```
+/// use std::sync::Arc;
+/// use arrow_array::{Int64Array, RecordBatch};
+/// use pco::ChunkConfig;
+/// use pco::parquet::{ParquetReader, ParquetWriter};
+/// # use pco::errors::PcoResult;
+///
+/// # fn main() -> PcoResult<()> {
+/// let batch = RecordBatch::try_from_iter([(
+///   "x",
+///   Arc::new(Int64Array::from_iter_values(0..1000)) as _,
+/// )]).unwrap();
+/// let mut writer = ParquetWriter::new(Vec::new(), batch.schema(), &ChunkConfig::default())?;
+/// writer.write(&batch)?;
+/// let compressed = writer.finish()?;
+///
+/// let reader = ParquetReader::new(bytes::Bytes::from(compressed))?;
+/// for recovered in reader {
+///   assert_eq!(recovered?, batch);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// This is a convention for storing pco data in a Parquet container, not a
+/// Parquet encoding.
+/// Each pco column is written as a Parquet binary column with the same
+/// validity as the original, where the first non-null row holds a wrapped
+/// pco chunk and the other non-null rows are empty.
+/// Its field metadata records the version of this layout (currently 1) and
+/// the original data type so that [`ParquetReader`] can restore it; other
+/// Parquet readers only see opaque binary values.
+/// Columns of other data types are written as usual.
+///
+/// Each Parquet row group holds a single pco chunk and page, so the writer
+/// buffers rows across batches and cuts row groups according to the
+/// config's `paging_spec`, which applies to all rows written:
+/// * With [`PagingSpec::EqualPagesUpTo`], row groups hold up to that many
+///   rows, and the last few are balanced when the writer finishes.
+/// * With [`PagingSpec::Exact`], row groups hold exactly the given counts,
+///   which must sum to the total number of rows written.
+pub struct ParquetWriter<W: Write + Send> {
+  inner: ArrowWriter<W>,
+  schema: SchemaRef,
+  parquet_schema: SchemaRef,
+  is_pco: Vec<bool>,
+  config: ChunkConfig,
+  pending: VecDeque<RecordBatch>,
+  n_pending: usize,
+  n_row_groups: usize,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+  /// Creates a writer for batches with the given schema.
+  ///
+  /// Will return an error if the paging spec is invalid, if the Parquet
+  /// writer can't be created, or if the provided `Write` errors.
+  pub fn new(dst: W, schema: SchemaRef, config: &ChunkConfig) -> PcoResult<Self> {
+    match &config.paging_spec {
+      PagingSpec::EqualPagesUpTo(0) => {
+        return Err(PcoError::invalid_argument(
+          "cannot write row groups of up to 0 rows",
+        ))
+      }
+      PagingSpec::EqualPagesUpTo(_) => (),
+      PagingSpec::Exact(row_group_ns) => {
+        config.paging_spec.n_per_page(row_group_ns.iter().sum())?;
+      }
+    }
+
+    let is_pco = schema
+      .fields()
+      .iter()
+      .map(|field| is_supported(field.data_type()))
+      .collect::<Vec<_>>();
+
+    // we cut row groups ourselves
+    let mut properties = WriterProperties::builder().set_max_row_group_size(usize::MAX);
+    let mut fields = Vec::with_capacity(is_pco.len());
+    for (field, &is_pco) in schema.fields().iter().zip(&is_pco) {
+      if is_pco {
+        // lengths of empty rows are cheap to store with delta encoding
+        let path = ColumnPath::from(field.name().as_str());
+        properties = properties
+          .set_column_dictionary_enabled(path.clone(), false)
+          .set_column_encoding(path, Encoding::DELTA_LENGTH_BYTE_ARRAY);
+        fields.push(pco_field(field));
+      } else {
+        fields.push(field.as_ref().clone());
+      }
+    }
+    let parquet_schema = Arc::new(Schema::new_with_metadata(
+      fields,
+      schema.metadata().clone(),
+    ));
+
+    let inner = ArrowWriter::try_new(
+      dst,
+      parquet_schema.clone(),
+      Some(properties.build()),
+    )
+    .map_err(parquet_error)?;
+    Ok(Self {
+      inner,
+      schema,
+      parquet_schema,
+      is_pco,
+      config: config.clone(),
+      pending: VecDeque::new(),
+      n_pending: 0,
+      n_row_groups: 0,
+    })
+  }
+
+  // returns the size of the next row group, if enough rows are pending to
+  // write it before finishing
+  fn next_row_group_n(&self) -> PcoResult<Option<usize>> {
+    match &self.config.paging_spec {
+      // keep more than a full row group pending so the last ones can be
+      // balanced
+      PagingSpec::EqualPagesUpTo(max_n) => {
+        if self.n_pending >= max_n.saturating_mul(2) {
+          Ok(Some(*max_n))
+        } else {
+          Ok(None)
+        }
+      }
+      PagingSpec::Exact(row_group_ns) => match row_group_ns.get(self.n_row_groups) {
+        Some(&n) if self.n_pending >= n => Ok(Some(n)),
+        Some(_) => Ok(None),
+        None if self.n_pending == 0 => Ok(None),
+        None => Err(PcoError::invalid_argument(format!(
+          "paging spec suggests {} rows but more were given",
+          row_group_ns.iter().sum::<usize>(),
+        ))),
+      },
+    }
+  }
+
+  fn write_row_group(&mut self, n: usize) -> PcoResult<()> {
+    let mut pieces = Vec::new();
+    let mut remaining = n;
+    while remaining > 0 {
+      let batch = self.pending.pop_front().unwrap();
+      if batch.num_rows() > remaining {
+        pieces.push(batch.slice(0, remaining));
+        self
+          .pending
+          .push_front(batch.slice(remaining, batch.num_rows() - remaining));
+        remaining = 0;
+      } else {
+        remaining -= batch.num_rows();
+        pieces.push(batch);
+      }
+    }
+    self.n_pending -= n;
+
+    let mut columns = Vec::with_capacity(self.is_pco.len());
+    for (col_idx, &is_pco) in self.is_pco.iter().enumerate() {
+      let column_pieces = pieces
+        .iter()
+        .map(|piece| piece.column(col_idx).clone())
+        .collect::<Vec<_>>();
+      if is_pco {
+        columns.push(match_arrow_type!(
+          self.schema.field(col_idx).data_type(),
+          encode_column(&column_pieces, &self.config)
+        )?);
+      } else {
+        columns.push(column_pieces);
+      }
+    }
+
+    for piece_idx in 0..pieces.len() {
+      let piece_columns = columns
+        .iter()
+        .map(|column_pieces| column_pieces[piece_idx].clone())
+        .collect();
+      let parquet_batch =
+        RecordBatch::try_new(self.parquet_schema.clone(), piece_columns).map_err(arrow_error)?;
+      self.inner.write(&parquet_batch).map_err(parquet_error)?;
+    }
+    self.inner.flush().map_err(parquet_error)?;
+    self.n_row_groups += 1;
+    Ok(())
+  }
+
+  /// Buffers the batch's rows, writing any row groups that are complete.
+  ///
+  /// Will return an error if the batch's schema doesn't match the writer's,
+  /// if more rows are written than an exact paging spec allows, if the
+  /// compressor config is invalid, or if the provided `Write` errors.
+  pub fn write(&mut self, batch: &RecordBatch) -> PcoResult<()> {
+    if batch.schema() != self.schema {
+      return Err(PcoError::invalid_argument(
+        "batch schema does not match the writer's schema",
+      ));
+    }
+    if batch.num_rows() == 0 {
+      return Ok(());
+    }
+
+    self.pending.push_back(batch.clone());
+    self.n_pending += batch.num_rows();
+    while let Some(n) = self.next_row_group_n()? {
+      self.write_row_group(n)?;
+    }
+    Ok(())
+  }
+
+  /// Writes the remaining rows and the Parquet footer, then returns the
+  /// destination.
+  ///
+  /// Will return an error if the rows written don't match an exact paging
+  /// spec, if the compressor config is invalid, or if the provided `Write`
+  /// errors.
+  pub fn finish(mut self) -> PcoResult<W> {
+    let remaining_spec = match &self.config.paging_spec {
+      PagingSpec::Exact(row_group_ns) => {
+        PagingSpec::Exact(row_group_ns[self.n_row_groups..].to_vec())
+      }
+      other => other.clone(),
+    };
+    for n in remaining_spec.n_per_page(self.n_pending)? {
+      self.write_row_group(n)?;
+    }
+    self.inner.into_inner().map_err(parquet_error)
+  }
+}
+
+/// Reads Arrow record batches from a Parquet file, one per row group,
+/// decompressing any columns written by [`ParquetWriter`] as pco data.
+///
+/// Columns that weren't written as pco data are read as usual, so this
+/// works on any Parquet file the `parquet` crate can read into Arrow.
+///
+/// The source gets cloned for each row group, so it should be cheap to
+/// clone, like `bytes::Bytes`.
+pub struct ParquetReader<R: ChunkReader + Clone + 'static> {
+  src: R,
+  metadata: ArrowReaderMetadata,
+  schema: SchemaRef,
+  pco_data_types: Vec<Option<DataType>>,
+  next_row_group: usize,
+}
+
+impl<R: ChunkReader + Clone + 'static> ParquetReader<R> {
+  /// Reads the Parquet footer and schema.
+  ///
+  /// Will return an error if the Parquet metadata or pco field metadata is
+  /// corrupt, or if a pco column was written with an unsupported version.
+  pub fn new(src: R) -> PcoResult<Self> {
+    let metadata =
+      ArrowReaderMetadata::load(&src, ArrowReaderOptions::default()).map_err(parquet_error)?;
+    let parquet_schema = metadata.schema();
+    let mut fields = Vec::with_capacity(parquet_schema.fields().len());
+    let mut pco_data_types = Vec::with_capacity(parquet_schema.fields().len());
+    for field in parquet_schema.fields() {
+      match original_field(field)? {
+        Some(original) if field.data_type() == &DataType::Binary => {
+          pco_data_types.push(Some(original.data_type().clone()));
+          fields.push(original);
+        }
+        _ => {
+          pco_data_types.push(None);
+          fields.push(field.as_ref().clone());
+        }
+      }
+    }
+    let schema = Arc::new(Schema::new_with_metadata(
+      fields,
+      parquet_schema.metadata().clone(),
+    ));
+
+    Ok(Self {
+      src,
+      metadata,
+      schema,
+      pco_data_types,
+      next_row_group: 0,
+    })
+  }
+
+  /// Returns the schema of the batches, with pco columns restored to their
+  /// original data types.
+  pub fn schema(&self) -> &SchemaRef {
+    &self.schema
+  }
+
+  /// Returns the count of row groups, each of which is read as one batch.
+  pub fn n_row_groups(&self) -> usize {
+    self.metadata.metadata().num_row_groups()
+  }
+
+  /// Reads the row group at the given index as a batch.
+  ///
+  /// Will return an error if the index is out of bounds or if there are
+  /// any corruption or insufficient data issues.
+  pub fn read_row_group(&self, row_group_idx: usize) -> PcoResult<RecordBatch> {
+    if row_group_idx >= self.n_row_groups() {
+      return Err(PcoError::invalid_argument(format!(
+        "row group index {} is out of bounds for {} row groups",
+        row_group_idx,
+        self.n_row_groups(),
+      )));
+    }
+
+    let n = self.metadata.metadata().row_group(row_group_idx).num_rows() as usize;
+    let mut batches =
+      ParquetRecordBatchReaderBuilder::new_with_metadata(self.src.clone(), self.metadata.clone())
+        .with_row_groups(vec![row_group_idx])
+        .with_batch_size(n.max(1))
+        .build()
+        .map_err(parquet_error)?;
+    let parquet_batch = match batches.next() {
+      Some(batch) => batch.map_err(arrow_error)?,
+      None => RecordBatch::new_empty(self.metadata.schema().clone()),
+    };
+
+    let columns = parquet_batch
+      .columns()
+      .iter()
+      .zip(&self.pco_data_types)
+      .map(|(column, data_type)| match data_type {
+        Some(data_type) => match_arrow_type!(
+          data_type,
+          decode_column(column.as_binary::<i32>(), data_type)
+        ),
+        None => Ok(column.clone()),
+      })
+      .collect::<PcoResult<Vec<_>>>()?;
+    RecordBatch::try_new(self.schema.clone(), columns).map_err(|err| {
+      PcoError::corruption(format!(
+        "pco columns don't match their Parquet schema: {}",
+        err
+      ))
+    })
+  }
+}
+
+impl<R: ChunkReader + Clone + 'static> Iterator for ParquetReader<R> {
+  type Item = PcoResult<RecordBatch>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.next_row_group >= self.n_row_groups() {
+      return None;
+    }
+
+    let res = self.read_row_group(self.next_row_group);
+    self.next_row_group += 1;
+    Some(res)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use arrow_array::{Float32Array, Int32Array, StringArray, TimestampMicrosecondArray};
+  use bytes::Bytes;
+  use parquet::arrow::arrow_reader::ParquetRecordBatchReader;
+
+  use super::*;
+  use crate::errors::ErrorKind;
+
+  fn make_batch() -> RecordBatch {
+    let n = 3000;
+    let ints = Int32Array::from_iter_values((0..n).map(|i| i % 100));
+    let floats = Float32Array::from(
+      (0..n)
+        .map(|i| {
+          if i % 9 == 0 {
+            None
+          } else {
+            Some(i as f32 / 4.0)
+          }
+        })
+        .collect::<Vec<_>>(),
+    );
+    let timestamps =
+      TimestampMicrosecondArray::from_iter_values((0..n as i64).map(|i| 1_700_000_000_000_000 + i))
+        .with_timezone("UTC");
+    let strings = StringArray::from_iter_values((0..n).map(|i| format!("s{}", i % 7)));
+    let schema = Schema::new(vec![
+      Field::new("ints", DataType::Int32, false),
+      Field::new("floats", DataType::Float32, true),
+      Field::new(
+        "timestamps",
+        timestamps.data_type().clone(),
+        false,
+      )
+      .with_metadata(HashMap::from([(
+        "unit".to_string(),
+        "us".to_string(),
+      )])),
+      Field::new("strings", DataType::Utf8, false),
+    ]);
+    RecordBatch::try_new(
+      Arc::new(schema),
+      vec![
+        Arc::new(ints),
+        Arc::new(floats),
+        Arc::new(timestamps),
+        Arc::new(strings),
+      ],
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn test_roundtrip() -> PcoResult<()> {
+    let batch = make_batch();
+    let config = ChunkConfig::default().with_paging_spec(PagingSpec::EqualPagesUpTo(1000));
+    let mut writer = ParquetWriter::new(Vec::new(), batch.schema(), &config)?;
+    writer.write(&batch)?;
+    writer.write(&RecordBatch::new_empty(batch.schema()))?;
+    let compressed = Bytes::from(writer.finish()?);
+
+    let reader = ParquetReader::new(compressed.clone())?;
+    assert_eq!(reader.schema(), &batch.schema());
+    assert_eq!(reader.n_row_groups(), 3);
+    assert!(reader.read_row_group(3).is_err());
+    let recovered = reader.collect::<PcoResult<Vec<_>>>()?;
+    for (i, recovered) in recovered.iter().enumerate() {
+      assert_eq!(recovered, &batch.slice(i * 1000, 1000));
+    }
+
+    // other Parquet readers see binary pco columns and normal string columns
+    let mut plain_reader =
+      ParquetRecordBatchReader::try_new(compressed, 1000).map_err(parquet_error)?;
+    let plain_batch = plain_reader.next().unwrap().map_err(arrow_error)?;
+    assert_eq!(
+      plain_batch.column(0).data_type(),
+      &DataType::Binary
+    );
+    assert_eq!(plain_batch.column(1).null_count(), 112);
+    assert_eq!(
+      plain_batch.column(3),
+      batch.slice(0, 1000).column(3)
+    );
+    Ok(())
+  }
+
+  fn write_in_small_batches(batch: &RecordBatch, config: &ChunkConfig) -> PcoResult<Bytes> {
+    let mut writer = ParquetWriter::new(Vec::new(), batch.schema(), config)?;
+    for start in (0..batch.num_rows()).step_by(100) {
+      writer.write(&batch.slice(start, 100))?;
+    }
+    Ok(Bytes::from(writer.finish()?))
+  }
+
+  #[test]
+  fn test_buffers_small_batches() -> PcoResult<()> {
+    let batch = make_batch().slice(0, 2500);
+
+    // 2 row groups of 1000 would leave 500, so the last 1500 get balanced
+    let config = ChunkConfig::default().with_paging_spec(PagingSpec::EqualPagesUpTo(1000));
+    let reader = ParquetReader::new(write_in_small_batches(&batch, &config)?)?;
+    let row_group_ns = reader
+      .map(|recovered| recovered.map(|b| b.num_rows()))
+      .collect::<PcoResult<Vec<_>>>()?;
+    assert_eq!(row_group_ns, vec![1000, 750, 750]);
+
+    let config = ChunkConfig::default().with_paging_spec(PagingSpec::Exact(vec![1250, 50, 1200]));
+    let reader = ParquetReader::new(write_in_small_batches(&batch, &config)?)?;
+    let mut start = 0;
+    for (recovered, n) in reader.zip([1250, 50, 1200]) {
+      assert_eq!(recovered?, batch.slice(start, n));
+      start += n;
+    }
+    assert_eq!(start, 2500);
+    Ok(())
+  }
+
+  #[test]
+  fn test_exact_paging_mismatch() -> PcoResult<()> {
+    let batch = make_batch().slice(0, 2500);
+    let too_few = ChunkConfig::default().with_paging_spec(PagingSpec::Exact(vec![1000, 1000]));
+    assert!(write_in_small_batches(&batch, &too_few).is_err());
+    let too_many = ChunkConfig::default().with_paging_spec(PagingSpec::Exact(vec![1000, 2000]));
+    assert!(write_in_small_batches(&batch, &too_many).is_err());
+    for invalid in [
+      PagingSpec::Exact(vec![1000, 0]),
+      PagingSpec::EqualPagesUpTo(0),
+    ] {
+      let config = ChunkConfig::default().with_paging_spec(invalid);
+      assert!(ParquetWriter::new(Vec::new(), batch.schema(), &config).is_err());
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn test_read_plain_parquet() -> PcoResult<()> {
+    let batch = make_batch();
+    let mut writer =
+      ArrowWriter::try_new(Vec::new(), batch.schema(), None).map_err(parquet_error)?;
+    writer.write(&batch).map_err(parquet_error)?;
+    let compressed = Bytes::from(writer.into_inner().map_err(parquet_error)?);
+
+    let mut reader = ParquetReader::new(compressed)?;
+    assert_eq!(reader.next().unwrap()?, batch);
+    assert!(reader.next().is_none());
+    Ok(())
+  }
+
+  #[test]
+  fn test_schema_mismatch() -> PcoResult<()> {
+    let batch = make_batch();
+    let mut writer = ParquetWriter::new(
+      Vec::new(),
+      batch.schema(),
+      &ChunkConfig::default(),
+    )?;
+    let other = RecordBatch::try_from_iter([(
+      "x",
+      Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+    )])
+    .unwrap();
+    assert!(writer.write(&other).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_unsupported_version() -> PcoResult<()> {
+    let batch = make_batch();
+    let mut writer = ParquetWriter::new(
+      Vec::new(),
+      batch.schema(),
+      &ChunkConfig::default(),
+    )?;
+    writer.write(&batch)?;
+    let compressed = Bytes::from(writer.finish()?);
+    let reader = ParquetReader::new(compressed)?;
+    let parquet_field = reader.metadata.schema().field(0);
+    assert_eq!(
+      parquet_field.metadata().get(VERSION_KEY),
+      Some(&VERSION.to_string())
+    );
+
+    for version in [Some("2"), None] {
+      let mut metadata = parquet_field.metadata().clone();
+      match version {
+        Some(version) => metadata.insert(VERSION_KEY.to_string(), version.to_string()),
+        None => metadata.remove(VERSION_KEY),
+      };
+      let field = parquet_field.clone().with_metadata(metadata);
+      let err = original_field(&field).unwrap_err();
+      assert!(matches!(err.kind, ErrorKind::Compatibility));
+    }
+    Ok(())
+  }
+}
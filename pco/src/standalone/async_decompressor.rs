@@ -0,0 +1,359 @@
+use std::cmp::{max, min};
+use std::io;
+
+use better_io::{AsyncBetterBufRead, BetterBufRead};
+
+use crate::constants::{CHUNK_META_PADDING, FULL_BATCH_N, PAGE_PADDING};
+use crate::data_types::Number;
+use crate::errors::{ErrorKind, PcoError, PcoResult};
+use crate::metadata::ChunkMeta;
+use crate::progress::Progress;
+use crate::standalone::checksum::ChecksumBufRead;
+use crate::standalone::constants::*;
+use crate::standalone::decompressor::{ChunkDecompressor, FileDecompressor};
+
+// Decompressing a batch reads each of up to 3 latent variables and then the
+// end of the page, and each of those reads fills PAGE_PADDING bytes.
+const BATCH_PREFILL: usize = 4 * PAGE_PADDING;
+
+// Lets pco's synchronous decompression read from an async source.
+// Bytes must be filled ahead of time, and instead of blocking, trying to read
+// past them is an error.
+struct PrefilledBufRead<R: AsyncBetterBufRead + Unpin> {
+  inner: R,
+  reached_eof: bool,
+}
+
+impl<R: AsyncBetterBufRead + Unpin> PrefilledBufRead<R> {
+  fn new(inner: R) -> Self {
+    Self {
+      inner,
+      reached_eof: false,
+    }
+  }
+
+  async fn prefill(&mut self, n_bytes: usize) -> PcoResult<()> {
+    if self.reached_eof || self.inner.buffer().len() >= n_bytes {
+      return Ok(());
+    }
+
+    if let Some(capacity) = self.inner.capacity() {
+      if capacity < n_bytes {
+        self.inner.resize_capacity(n_bytes);
+      }
+    }
+    self.inner.fill_or_eof(n_bytes).await?;
+    self.reached_eof = self.inner.buffer().len() < n_bytes;
+    Ok(())
+  }
+
+  // Runs a synchronous parse over the buffered bytes, filling more and
+  // retrying whenever it runs out of data, and returns the parsed value along
+  // with the count of bytes it read.
+  // This suits metadata, which is cheap to reparse but has no useful size
+  // bound.
+  async fn parse_buffered<Y, F: Fn(&[u8]) -> PcoResult<(Y, &[u8])>>(
+    &mut self,
+    initial_n_bytes: usize,
+    parse: F,
+  ) -> PcoResult<(Y, usize)> {
+    let mut n_bytes = initial_n_bytes;
+    loop {
+      self.prefill(n_bytes).await?;
+      let buffer = self.inner.buffer();
+      match parse(buffer) {
+        Ok((res, rest)) => return Ok((res, buffer.len() - rest.len())),
+        Err(PcoError {
+          kind: ErrorKind::InsufficientData,
+          ..
+        }) if !self.reached_eof => {
+          n_bytes = max(2 * n_bytes, buffer.len() + 1);
+        }
+        Err(e) => return Err(e),
+      }
+    }
+  }
+}
+
+impl<R: AsyncBetterBufRead + Unpin> BetterBufRead for PrefilledBufRead<R> {
+  fn fill_or_eof(&mut self, n_bytes: usize) -> io::Result<()> {
+    if self.reached_eof || self.inner.buffer().len() >= n_bytes {
+      Ok(())
+    } else {
+      Err(io::Error::new(
+        io::ErrorKind::WouldBlock,
+        "async source was not filled before a synchronous read",
+      ))
+    }
+  }
+
+  fn buffer(&self) -> &[u8] {
+    self.inner.buffer()
+  }
+
+  fn consume(&mut self, n_bytes: usize) {
+    self.inner.consume(n_bytes);
+  }
+
+  fn capacity(&self) -> Option<usize> {
+    self.inner.capacity()
+  }
+
+  fn resize_capacity(&mut self, desired: usize) {
+    self.inner.resize_capacity(desired);
+  }
+}
+
+/// The outcome of starting a new chunk of a standalone file from an async
+/// source.
+#[allow(clippy::large_enum_variant)]
+pub enum MaybeAsyncChunkDecompressor<T: Number, R: AsyncBetterBufRead + Unpin> {
+  /// We get an `AsyncChunkDecompressor` when there is another chunk as
+  /// evidenced by the data type byte.
+  Some(AsyncChunkDecompressor<T, R>),
+  /// We are at the end of the pco data if we encounter a null byte instead of
+  /// a data type byte.
+  EndOfData(R),
+}
+
+impl FileDecompressor {
+  /// Reads a short header from an async source and returns a
+  /// `FileDecompressor` and the remaining input.
+  ///
+  /// Will return an error if any corruptions, version incompatibilities, or
+  /// insufficient data are found.
+  ///
+  /// This is the async counterpart of [`new`][Self::new].
+  pub async fn new_async<R: AsyncBetterBufRead + Unpin>(src: R) -> PcoResult<(Self, R)> {
+    let mut src = PrefilledBufRead::new(src);
+    let (file_decompressor, n_bytes) = src
+      .parse_buffered(STANDALONE_HEADER_PADDING, |buffer| {
+        FileDecompressor::new(buffer)
+      })
+      .await?;
+    src.inner.consume(n_bytes);
+    Ok((file_decompressor, src.inner))
+  }
+
+  /// Reads a chunk's metadata from an async source and returns either an
+  /// `AsyncChunkDecompressor` or the rest of the source if at the end of the
+  /// pco file.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// This is the async counterpart of
+  /// [`chunk_decompressor`][Self::chunk_decompressor].
+  pub async fn chunk_decompressor_async<T: Number, R: AsyncBetterBufRead + Unpin>(
+    &self,
+    src: R,
+  ) -> PcoResult<MaybeAsyncChunkDecompressor<T, R>> {
+    let mut src = PrefilledBufRead::new(src);
+    let (chunk_start, n_bytes) = src
+      .parse_buffered(
+        STANDALONE_CHUNK_PREAMBLE_PADDING + CHUNK_META_PADDING,
+        |buffer| self.read_chunk_start::<T, _>(buffer),
+      )
+      .await?;
+    // consuming through the checksum includes the chunk's metadata in it
+    let mut src = ChecksumBufRead::new(src, self.has_checksums());
+    src.consume(n_bytes);
+
+    let res = match chunk_start {
+      Some((n, inner_cd)) => {
+        src.inner_mut().prefill(BATCH_PREFILL).await?;
+        MaybeAsyncChunkDecompressor::Some(AsyncChunkDecompressor {
+          inner: ChunkDecompressor::new(inner_cd, src, n)?,
+        })
+      }
+      None => MaybeAsyncChunkDecompressor::EndOfData(src.into_inner().inner),
+    };
+    Ok(res)
+  }
+}
+
+/// Holds metadata about a chunk and supports decompression from an async
+/// source.
+///
+/// Each batch of numbers is decoded as soon as its bytes have arrived.
+pub struct AsyncChunkDecompressor<T: Number, R: AsyncBetterBufRead + Unpin> {
+  inner: ChunkDecompressor<T, PrefilledBufRead<R>>,
+}
+
+impl<T: Number, R: AsyncBetterBufRead + Unpin> AsyncChunkDecompressor<T, R> {
+  /// Returns pre-computed information about the chunk.
+  pub fn meta(&self) -> &ChunkMeta {
+    self.inner.meta()
+  }
+
+  /// Returns the count of numbers in the chunk.
+  pub fn n(&self) -> usize {
+    self.inner.n()
+  }
+
+  /// Reads the next decompressed numbers into the destination, returning
+  /// progress into the chunk and advancing along the compressed data.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// `dst` must have length either a multiple of 256 or be at least the count
+  /// of numbers remaining in the chunk.
+  pub async fn decompress(&mut self, dst: &mut [T]) -> PcoResult<Progress> {
+    let n_remaining = self.inner.n_remaining();
+    if dst.len() % FULL_BATCH_N != 0 && dst.len() < n_remaining {
+      return Err(PcoError::invalid_argument(format!(
+        "dst's length must either be a multiple of {} or be \
+         at least the count of numbers remaining ({} < {})",
+        FULL_BATCH_N,
+        dst.len(),
+        n_remaining,
+      )));
+    }
+
+    let n_to_process = min(dst.len(), n_remaining);
+    let mut n_processed = 0;
+    while n_processed < n_to_process {
+      let batch_end = min(n_processed + FULL_BATCH_N, n_to_process);
+      self.inner.src_mut().prefill(BATCH_PREFILL).await?;
+      self.inner.decompress(&mut dst[n_processed..batch_end])?;
+      n_processed = batch_end;
+    }
+
+    Ok(Progress {
+      n_processed,
+      finished: self.inner.n_remaining() == 0,
+    })
+  }
+
+  /// Returns the rest of the compressed data source.
+  pub fn into_src(self) -> R {
+    self.inner.into_src().inner
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::pin::Pin;
+  use std::task::{Context, Poll};
+
+  use better_io::AsyncBetterBufReader;
+  use futures::executor::block_on;
+  use futures::io::AsyncRead;
+
+  use super::*;
+  use crate::standalone::FileCompressor;
+  use crate::ChunkConfig;
+
+  // yields at most 100 bytes per read, and is pending every other poll
+  struct TrickleRead {
+    data: Vec<u8>,
+    pos: usize,
+    ready: bool,
+  }
+
+  impl AsyncRead for TrickleRead {
+    fn poll_read(
+      mut self: Pin<&mut Self>,
+      cx: &mut Context<'_>,
+      buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+      if !self.ready {
+        self.ready = true;
+        cx.waker().wake_by_ref();
+        return Poll::Pending;
+      }
+      self.ready = false;
+      let n = buf.len().min(100).min(self.data.len() - self.pos);
+      buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+      self.pos += n;
+      Poll::Ready(Ok(n))
+    }
+  }
+
+  fn compress(nums: &[f64], checksums: bool) -> PcoResult<Vec<u8>> {
+    let file_compressor = FileCompressor::default().with_checksums(checksums);
+    let mut compressed = Vec::new();
+    file_compressor.write_header(&mut compressed)?;
+    for chunk in nums.chunks(2000) {
+      file_compressor
+        .chunk_compressor(chunk, &ChunkConfig::default())?
+        .write_chunk(&mut compressed)?;
+    }
+    file_compressor.write_footer(&mut compressed)?;
+    Ok(compressed)
+  }
+
+  async fn decompress<R: AsyncBetterBufRead + Unpin>(src: R) -> PcoResult<(Vec<f64>, R)> {
+    let (file_decompressor, mut src) = FileDecompressor::new_async(src).await?;
+    let mut res = Vec::new();
+    // an awkward batch size, to cover partial reads of chunks
+    let mut dst = vec![0.0; 768];
+    loop {
+      match file_decompressor
+        .chunk_decompressor_async::<f64, _>(src)
+        .await?
+      {
+        MaybeAsyncChunkDecompressor::Some(mut chunk_decompressor) => {
+          let mut finished = false;
+          while !finished {
+            let progress = chunk_decompressor.decompress(&mut dst).await?;
+            res.extend(&dst[..progress.n_processed]);
+            finished = progress.finished;
+          }
+          src = chunk_decompressor.into_src();
+        }
+        MaybeAsyncChunkDecompressor::EndOfData(rest) => return Ok((res, rest)),
+      }
+    }
+  }
+
+  #[test]
+  fn test_async_decompress() -> PcoResult<()> {
+    let nums = (0..5000)
+      .map(|i| (i as f64).sin() * 1000.0)
+      .collect::<Vec<_>>();
+    for checksums in [false, true] {
+      let compressed = compress(&nums, checksums)?;
+
+      let trickle = TrickleRead {
+        data: compressed.clone(),
+        pos: 0,
+        ready: false,
+      };
+      let src = AsyncBetterBufReader::new(&[], trickle, 64);
+      let (recovered, rest) = block_on(decompress(src))?;
+      assert_eq!(recovered, nums);
+      assert!(rest.buffer().is_empty());
+      assert_eq!(rest.into_inner().pos, compressed.len());
+
+      let (recovered, rest) = block_on(decompress(compressed.as_slice()))?;
+      assert_eq!(recovered, nums);
+      assert!(rest.is_empty());
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn test_async_insufficient_data() -> PcoResult<()> {
+    let nums = (0..5000).map(|i| i as f64).collect::<Vec<_>>();
+    let compressed = compress(&nums, false)?;
+    for len in [3, 20, compressed.len() / 2, compressed.len() - 1] {
+      let trickle = TrickleRead {
+        data: compressed[..len].to_vec(),
+        pos: 0,
+        ready: false,
+      };
+      let src = AsyncBetterBufReader::new(&[], trickle, 64);
+      let err = match block_on(decompress(src)) {
+        Ok(_) => panic!("expected an error for len={}", len),
+        Err(err) => err,
+      };
+      assert!(
+        matches!(err.kind, ErrorKind::InsufficientData),
+        "len={} err={:?}",
+        len,
+        err
+      );
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,169 @@
+use std::io::Write;
+
+use better_io::BetterBufRead;
+
+use crate::errors::{PcoError, PcoResult};
+
+pub const CHECKSUM_SIZE: usize = 4;
+
+// reversed Castagnoli polynomial
+const CRC32C_POLY: u32 = 0x82f63b78;
+static CRC32C_TABLE: [u32; 256] = make_crc32c_table();
+
+const fn make_crc32c_table() -> [u32; 256] {
+  let mut table = [0; 256];
+  let mut i = 0;
+  while i < 256 {
+    let mut crc = i as u32;
+    let mut j = 0;
+    while j < 8 {
+      crc = if crc & 1 == 1 {
+        (crc >> 1) ^ CRC32C_POLY
+      } else {
+        crc >> 1
+      };
+      j += 1;
+    }
+    table[i] = crc;
+    i += 1;
+  }
+  table
+}
+
+// A CRC-32C hasher.
+// This is byte-at-a-time, which is slower than hardware-accelerated
+// implementations, but checksums are opt-in and we'd rather not add a
+// dependency.
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32c {
+  state: u32,
+}
+
+impl Default for Crc32c {
+  fn default() -> Self {
+    Self { state: !0 }
+  }
+}
+
+impl Crc32c {
+  pub fn update(&mut self, bytes: &[u8]) {
+    let mut state = self.state;
+    for &byte in bytes {
+      state = CRC32C_TABLE[((state ^ byte as u32) & 0xff) as usize] ^ (state >> 8);
+    }
+    self.state = state;
+  }
+
+  pub fn finish(&self) -> u32 {
+    !self.state
+  }
+}
+
+// Hashes all bytes written through it.
+pub struct ChecksumWriter<W: Write> {
+  pub inner: W,
+  pub hasher: Crc32c,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    let n = self.inner.write(buf)?;
+    self.hasher.update(&buf[..n]);
+    Ok(n)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+// Hashes all bytes consumed through it, if enabled.
+pub struct ChecksumBufRead<R: BetterBufRead> {
+  inner: R,
+  hasher: Option<Crc32c>,
+}
+
+impl<R: BetterBufRead> ChecksumBufRead<R> {
+  pub fn new(inner: R, enabled: bool) -> Self {
+    Self {
+      inner,
+      hasher: enabled.then(Crc32c::default),
+    }
+  }
+
+  pub fn into_inner(self) -> R {
+    self.inner
+  }
+
+  #[cfg(feature = "async")]
+  pub fn inner_mut(&mut self) -> &mut R {
+    &mut self.inner
+  }
+
+  // Reads the stored checksum following the consumed bytes, if enabled, and
+  // verifies it.
+  pub fn verify(&mut self) -> PcoResult<()> {
+    let hasher = match self.hasher.take() {
+      Some(hasher) => hasher,
+      None => return Ok(()),
+    };
+
+    self.inner.fill_or_eof(CHECKSUM_SIZE)?;
+    let buffer = self.inner.buffer();
+    if buffer.len() < CHECKSUM_SIZE {
+      return Err(PcoError::insufficient_data(
+        "unable to read chunk checksum",
+      ));
+    }
+    let stored = u32::from_le_bytes(buffer[..CHECKSUM_SIZE].try_into().unwrap());
+    self.inner.consume(CHECKSUM_SIZE);
+
+    let computed = hasher.finish();
+    if stored != computed {
+      return Err(PcoError::checksum_mismatch(format!(
+        "chunk checksum {:#010x} does not match stored checksum {:#010x}",
+        computed, stored,
+      )));
+    }
+    Ok(())
+  }
+}
+
+impl<R: BetterBufRead> BetterBufRead for ChecksumBufRead<R> {
+  fn fill_or_eof(&mut self, n_bytes: usize) -> std::io::Result<()> {
+    self.inner.fill_or_eof(n_bytes)
+  }
+
+  fn buffer(&self) -> &[u8] {
+    self.inner.buffer()
+  }
+
+  fn consume(&mut self, n_bytes: usize) {
+    if let Some(hasher) = &mut self.hasher {
+      hasher.update(&self.inner.buffer()[..n_bytes]);
+    }
+    self.inner.consume(n_bytes);
+  }
+
+  fn capacity(&self) -> Option<usize> {
+    self.inner.capacity()
+  }
+
+  fn resize_capacity(&mut self, desired: usize) {
+    self.inner.resize_capacity(desired);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_crc32c() {
+    let mut hasher = Crc32c::default();
+    assert_eq!(hasher.finish(), 0);
+    hasher.update(b"1234");
+    hasher.update(b"56789");
+    assert_eq!(hasher.finish(), 0xe3069283);
+  }
+}
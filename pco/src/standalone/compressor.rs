@@ -2,10 +2,15 @@ use std::io::Write;
 
 use crate::bit_writer::BitWriter;
 use crate::chunk_config::PagingSpec;
+use crate::constants::MAX_ENTRIES;
+use crate::counting_writer::CountingWriter;
 use crate::data_types::Number;
-use crate::errors::PcoResult;
+use crate::errors::{PcoError, PcoResult};
 use crate::metadata::ChunkMeta;
+use crate::standalone::checksum::{ChecksumWriter, Crc32c, CHECKSUM_SIZE};
 use crate::standalone::constants::*;
+use crate::standalone::index::FileIndex;
+use crate::standalone::nullable;
 use crate::{bits, wrapped, ChunkConfig};
 
 unsafe fn write_varint<W: Write>(n: u64, writer: &mut BitWriter<W>) {
@@ -45,6 +50,8 @@ unsafe fn write_varint<W: Write>(n: u64, writer: &mut BitWriter<W>) {
 pub struct FileCompressor {
   inner: wrapped::FileCompressor,
   n_hint: usize,
+  checksums: bool,
+  nullable: bool,
 }
 
 impl FileCompressor {
@@ -53,6 +60,33 @@ impl FileCompressor {
     self
   }
 
+  /// Sets whether each chunk should be followed by a CRC-32C checksum of its
+  /// bytes (default: false).
+  ///
+  /// Decompressors verify these checksums upon finishing each chunk,
+  /// returning a [`ChecksumMismatch`][crate::errors::ErrorKind::ChecksumMismatch]
+  /// error if the data was corrupted.
+  pub fn with_checksums(mut self, checksums: bool) -> Self {
+    self.checksums = checksums;
+    self
+  }
+
+  /// Sets whether the file stores the validity of each row, so that it can
+  /// hold a nullable column (default: false).
+  ///
+  /// Chunks of a nullable file contain only the non-null numbers, and
+  /// [`write_validity`][Self::write_validity] must be called right after
+  /// writing the footer.
+  /// Decompressors that ignore validity simply see the non-null numbers.
+  pub fn with_nullable(mut self, nullable: bool) -> Self {
+    self.nullable = nullable;
+    self
+  }
+
+  pub(crate) fn is_nullable(&self) -> bool {
+    self.nullable
+  }
+
   /// Writes a short header to the destination.
   ///
   /// Will return an error if the provided `Write` errors.
@@ -67,6 +101,14 @@ impl FileCompressor {
       write_varint(self.n_hint as u64, &mut writer);
     }
     writer.finish_byte();
+    let mut flags = 0;
+    if self.checksums {
+      flags |= CHECKSUMS_FLAG;
+    }
+    if self.nullable {
+      flags |= NULLABLE_FLAG;
+    }
+    writer.write_aligned_bytes(&[flags])?;
     writer.flush()?;
     let dst = writer.into_inner();
     self.inner.write_header(dst)
@@ -90,6 +132,30 @@ impl FileCompressor {
     Ok(ChunkCompressor {
       inner: self.inner.chunk_compressor(nums, &config)?,
       number_type_byte: T::NUMBER_TYPE_BYTE,
+      checksum: self.checksums,
+    })
+  }
+
+  /// Creates a `ChunkCompressor` that reuses the mode, delta encoding, and
+  /// bins of an existing chunk's metadata.
+  ///
+  /// See [`wrapped::FileCompressor::chunk_compressor_reusing_meta`] for
+  /// details.
+  pub fn chunk_compressor_reusing_meta<T: Number>(
+    &self,
+    nums: &[T],
+    meta: &ChunkMeta,
+    config: &ChunkConfig,
+  ) -> PcoResult<ChunkCompressor> {
+    let mut config = config.clone();
+    config.paging_spec = PagingSpec::Exact(vec![nums.len()]);
+
+    Ok(ChunkCompressor {
+      inner: self
+        .inner
+        .chunk_compressor_reusing_meta(nums, meta, &config)?,
+      number_type_byte: T::NUMBER_TYPE_BYTE,
+      checksum: self.checksums,
     })
   }
 
@@ -102,6 +168,69 @@ impl FileCompressor {
     writer.flush()?;
     Ok(writer.into_inner())
   }
+
+  /// Writes a short footer to the destination, followed by an index of the
+  /// chunks written with
+  /// [`ChunkCompressor::write_chunk_indexed`].
+  ///
+  /// Will return an error if the provided `Write` errors.
+  ///
+  /// The index is optional and backward compatible; see [`FileIndex`].
+  pub fn write_footer_with_index<W: Write>(&self, index: &FileIndex, dst: W) -> PcoResult<W> {
+    let dst = self.write_footer(dst)?;
+    self.write_index(index, dst)
+  }
+
+  /// Writes an index of the chunks written with
+  /// [`ChunkCompressor::write_chunk_indexed`].
+  ///
+  /// Will return an error if the provided `Write` errors.
+  ///
+  /// This is only needed for nullable files, where the index goes after the
+  /// validity; otherwise, use
+  /// [`write_footer_with_index`][Self::write_footer_with_index].
+  pub fn write_index<W: Write>(&self, index: &FileIndex, dst: W) -> PcoResult<W> {
+    index.write_to(dst)
+  }
+
+  /// Writes the validity of every row in a nullable file, where `true`
+  /// means the row is non-null.
+  ///
+  /// Will return an error if the file is not nullable or if the provided
+  /// `Write` errors.
+  ///
+  /// This must be called right after [`write_footer`][Self::write_footer],
+  /// and the count of `true`s must equal the count of numbers written in
+  /// chunks.
+  /// Validity is stored as run lengths of valid and null rows, which are
+  /// themselves compressed with pco.
+  pub fn write_validity<W: Write>(&self, validity: &[bool], mut dst: W) -> PcoResult<W> {
+    if !self.nullable {
+      return Err(PcoError::invalid_argument(
+        "cannot write validity to a non-nullable file",
+      ));
+    }
+
+    let config = ChunkConfig::default();
+    // each chunk of runs may cover at most MAX_ENTRIES rows
+    for rows in validity.chunks(MAX_ENTRIES) {
+      let mut runs = nullable::runs_from_validity(rows);
+      // the next rows start with a valid run, so we end with a null one
+      if runs.len() % 2 == 1 {
+        runs.push(0);
+      }
+      let mut start = 0;
+      for run_n in config.paging_spec.n_per_page(runs.len())? {
+        let end = start + run_n;
+        dst = self
+          .chunk_compressor(&runs[start..end], &config)?
+          .write_chunk(dst)?;
+        start = end;
+      }
+    }
+    // the run chunks are terminated the same way as the file's chunks
+    self.write_footer(dst)
+  }
 }
 
 /// Holds metadata about a chunk and supports compression.
@@ -109,6 +238,7 @@ impl FileCompressor {
 pub struct ChunkCompressor {
   inner: wrapped::ChunkCompressor,
   number_type_byte: u8,
+  checksum: bool,
 }
 
 impl ChunkCompressor {
@@ -125,12 +255,27 @@ impl ChunkCompressor {
     1 + BITS_TO_ENCODE_N_ENTRIES.div_ceil(8) as usize
       + self.inner.chunk_meta_size_hint()
       + self.inner.page_size_hint(0)
+      + if self.checksum { CHECKSUM_SIZE } else { 0 }
   }
 
   /// Writes an entire chunk to the destination.
   ///
   /// Will return an error if the provided `Write` errors.
   pub fn write_chunk<W: Write>(&self, dst: W) -> PcoResult<W> {
+    if !self.checksum {
+      return self.write_chunk_body(dst);
+    }
+
+    let checksum_dst = self.write_chunk_body(ChecksumWriter {
+      inner: dst,
+      hasher: Crc32c::default(),
+    })?;
+    let mut dst = checksum_dst.inner;
+    dst.write_all(&checksum_dst.hasher.finish().to_le_bytes())?;
+    Ok(dst)
+  }
+
+  fn write_chunk_body<W: Write>(&self, dst: W) -> PcoResult<W> {
     let mut writer = BitWriter::new(dst, STANDALONE_CHUNK_PREAMBLE_PADDING);
     writer.write_aligned_bytes(&[self.number_type_byte])?;
     let n = self.inner.n_per_page()[0];
@@ -143,4 +288,26 @@ impl ChunkCompressor {
     let dst = self.inner.write_chunk_meta(dst)?;
     self.inner.write_page(0, dst)
   }
+
+  /// Writes an entire chunk to the destination, recording its position
+  /// in the index.
+  ///
+  /// Will return an error if the provided `Write` errors.
+  ///
+  /// All chunks of the file must be written this way, in order, for the
+  /// index to be valid.
+  pub fn write_chunk_indexed<W: Write>(&self, dst: W, index: &mut FileIndex) -> PcoResult<W> {
+    let counting_dst = self.write_chunk(CountingWriter {
+      inner: dst,
+      count: 0,
+    })?;
+    let meta_size =
+      1 + BITS_TO_ENCODE_N_ENTRIES.div_ceil(8) as usize + self.inner.chunk_meta_size_hint();
+    index.push(
+      counting_dst.count,
+      meta_size as u64,
+      self.inner.n_per_page()[0],
+    );
+    Ok(counting_dst.inner)
+  }
 }
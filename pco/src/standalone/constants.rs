@@ -6,7 +6,13 @@ pub const MAGIC_TERMINATION_BYTE: u8 = 0;
 pub const BITS_TO_ENCODE_N_ENTRIES: Bitlen = 24;
 pub const BITS_TO_ENCODE_STANDALONE_VERSION: Bitlen = 8;
 pub const BITS_TO_ENCODE_VARINT_POWER: Bitlen = 6;
-pub const CURRENT_STANDALONE_VERSION: usize = 2;
+pub const CURRENT_STANDALONE_VERSION: usize = 3;
+pub const CHECKSUMS_FLAG: u8 = 1;
+pub const NULLABLE_FLAG: u8 = 2;
+// ascii for pcoi; must not end in the termination byte
+pub const MAGIC_INDEX_TRAILER: [u8; 4] = [112, 99, 111, 105];
+pub const INDEX_ENTRY_SIZE: usize = 20;
+pub const INDEX_TRAILER_SIZE: usize = 8;
 
 // padding
 pub const STANDALONE_CHUNK_PREAMBLE_PADDING: usize =
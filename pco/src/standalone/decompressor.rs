@@ -1,12 +1,21 @@
+use std::cmp::{max, min};
+use std::ops::Range;
+
 use better_io::BetterBufRead;
 
+use crate::aggregate::Aggregates;
 use crate::bit_reader::{BitReader, BitReaderBuilder};
-use crate::constants::Bitlen;
+use crate::constants::{Bitlen, FULL_BATCH_N};
 use crate::data_types::Number;
 use crate::errors::{PcoError, PcoResult};
 use crate::metadata::ChunkMeta;
+#[cfg(feature = "parallel")]
+use crate::parallel;
 use crate::progress::Progress;
+use crate::standalone::checksum::ChecksumBufRead;
 use crate::standalone::constants::*;
+use crate::standalone::index::FileIndex;
+use crate::standalone::nullable;
 use crate::standalone::NumberTypeOrTermination;
 use crate::{bit_reader, wrapped};
 
@@ -17,6 +26,26 @@ unsafe fn read_varint(reader: &mut BitReader) -> PcoResult<u64> {
   Ok(res)
 }
 
+fn validate_range(range: &Range<usize>, dst_len: usize) -> PcoResult<()> {
+  if range.end < range.start {
+    return Err(PcoError::invalid_argument(format!(
+      "range end must not be less than start ({} < {})",
+      range.end, range.start,
+    )));
+  }
+  if dst_len < range.len() {
+    return Err(PcoError::invalid_argument(format!(
+      "dst's length must be at least the length of the range ({} < {})",
+      dst_len,
+      range.len(),
+    )));
+  }
+  Ok(())
+}
+
+// a chunk's count of numbers and its wrapped decompressor
+type ChunkStart<T> = (usize, wrapped::ChunkDecompressor<T>);
+
 /// Top-level entry point for decompressing standalone .pco files.
 ///
 /// Example of the lowest level API for reading a .pco file:
@@ -44,6 +73,8 @@ unsafe fn read_varint(reader: &mut BitReader) -> PcoResult<u64> {
 #[derive(Clone, Debug)]
 pub struct FileDecompressor {
   n_hint: usize,
+  checksums: bool,
+  nullable: bool,
   inner: wrapped::FileDecompressor,
 }
 
@@ -99,8 +130,30 @@ impl FileDecompressor {
       )));
     }
 
+    let flags = if standalone_version >= 3 {
+      reader_builder.with_reader(|reader| Ok(reader.read_aligned_bytes(1)?[0]))?
+    } else {
+      0
+    };
+    if flags & !(CHECKSUMS_FLAG | NULLABLE_FLAG) != 0 {
+      return Err(PcoError::compatibility(format!(
+        "unknown standalone flags {:#04x}; consider upgrading pco",
+        flags,
+      )));
+    }
+    let checksums = flags & CHECKSUMS_FLAG != 0;
+    let nullable = flags & NULLABLE_FLAG != 0;
+
     let (inner, rest) = wrapped::FileDecompressor::new(reader_builder.into_inner())?;
-    Ok((Self { inner, n_hint }, rest))
+    Ok((
+      Self {
+        inner,
+        n_hint,
+        checksums,
+        nullable,
+      },
+      rest,
+    ))
   }
 
   pub fn format_version(&self) -> u8 {
@@ -111,6 +164,56 @@ impl FileDecompressor {
     self.n_hint
   }
 
+  /// Returns whether each chunk of the file is followed by a checksum.
+  pub fn has_checksums(&self) -> bool {
+    self.checksums
+  }
+
+  /// Returns whether the file stores the validity of each row after its
+  /// chunks.
+  pub fn is_nullable(&self) -> bool {
+    self.nullable
+  }
+
+  /// Reads the validity of every row of a nullable file and returns it
+  /// along with the rest of the source.
+  ///
+  /// `src` must be right after the file's termination byte, e.g. as returned
+  /// by [`MaybeChunkDecompressor::EndOfData`].
+  /// Will return an error if the file is not nullable or if corruptions or
+  /// insufficient data are found.
+  pub fn read_validity<R: BetterBufRead>(&self, mut src: R) -> PcoResult<(Vec<bool>, R)> {
+    if !self.nullable {
+      return Err(PcoError::invalid_argument(
+        "cannot read validity from a non-nullable file",
+      ));
+    }
+
+    let mut runs = Vec::new();
+    let mut n_rows = 0_usize;
+    loop {
+      match self.chunk_decompressor::<u64, _>(src)? {
+        MaybeChunkDecompressor::Some(mut cd) => {
+          let chunk_start = runs.len();
+          cd.decompress_remaining_extend(&mut runs)?;
+          let chunk_n_rows = nullable::count_chunk_rows(&runs[chunk_start..])?;
+          n_rows = n_rows.checked_add(chunk_n_rows).ok_or_else(|| {
+            PcoError::corruption("validity covers more rows than can be addressed")
+          })?;
+          src = cd.into_src();
+        }
+        MaybeChunkDecompressor::EndOfData(rest) => {
+          src = rest;
+          break;
+        }
+      }
+    }
+    Ok((
+      nullable::validity_from_runs(&runs, n_rows),
+      src,
+    ))
+  }
+
   /// Peeks at what's next in the file, returning whether it's a termination
   /// or chunk with some data type.
   ///
@@ -134,13 +237,28 @@ impl FileDecompressor {
     mut src: R,
   ) -> PcoResult<MaybeChunkDecompressor<T, R>> {
     bit_reader::ensure_buf_read_capacity(&mut src, STANDALONE_CHUNK_PREAMBLE_PADDING);
+    let src = ChecksumBufRead::new(src, self.checksums);
+    let (chunk_start, src) = self.read_chunk_start::<T, _>(src)?;
+    let res = match chunk_start {
+      Some((n, inner_cd)) => {
+        MaybeChunkDecompressor::Some(ChunkDecompressor::new(inner_cd, src, n)?)
+      }
+      None => MaybeChunkDecompressor::EndOfData(src.into_inner()),
+    };
+    Ok(res)
+  }
+
+  // Reads the data type or termination byte and, if there is a chunk, its
+  // count of numbers and metadata, leaving src at the start of its page.
+  pub(crate) fn read_chunk_start<T: Number, R: BetterBufRead>(
+    &self,
+    src: R,
+  ) -> PcoResult<(Option<ChunkStart<T>>, R)> {
     let mut reader_builder = BitReaderBuilder::new(src, STANDALONE_CHUNK_PREAMBLE_PADDING, 0);
     let type_or_termination_byte =
       reader_builder.with_reader(|reader| Ok(reader.read_aligned_bytes(1)?[0]))?;
     if type_or_termination_byte == MAGIC_TERMINATION_BYTE {
-      return Ok(MaybeChunkDecompressor::EndOfData(
-        reader_builder.into_inner(),
-      ));
+      return Ok((None, reader_builder.into_inner()));
     }
 
     if type_or_termination_byte != T::NUMBER_TYPE_BYTE {
@@ -154,16 +272,8 @@ impl FileDecompressor {
     let n = reader_builder
       .with_reader(|reader| unsafe { Ok(reader.read_usize(BITS_TO_ENCODE_N_ENTRIES) + 1) })?;
     let src = reader_builder.into_inner();
-    let (inner_cd, src) = self.inner.chunk_decompressor::<T, R>(src)?;
-    let inner_pd = inner_cd.page_decompressor(src, n)?;
-
-    let res = ChunkDecompressor {
-      inner_cd,
-      inner_pd,
-      n,
-      n_processed: 0,
-    };
-    Ok(MaybeChunkDecompressor::Some(res))
+    let (inner_cd, src) = self.inner.chunk_decompressor::<T, _>(src)?;
+    Ok((Some((n, inner_cd)), src))
   }
 
   /// Takes in compressed bytes (after the header, at the start of the chunks)
@@ -185,17 +295,275 @@ impl FileDecompressor {
     }
     Ok(res)
   }
+
+  /// Takes in compressed bytes (after the header, at the start of the chunks)
+  /// and returns the count, min, max, and sum of all the numbers in the file.
+  ///
+  /// Will return an error if there are any compatibility, corruption,
+  /// or insufficient data issues.
+  ///
+  /// This streams through each chunk a batch at a time rather than
+  /// materializing the file's numbers, and takes the min and max from chunk
+  /// statistics when they were stored.
+  pub fn aggregate<T: Number>(&self, mut src: &[u8]) -> PcoResult<Aggregates<T>> {
+    let mut res = Aggregates::empty();
+    while let MaybeChunkDecompressor::Some(mut chunk_decompressor) = self.chunk_decompressor(src)? {
+      res.merge(&chunk_decompressor.aggregate()?);
+      src = chunk_decompressor.into_src();
+    }
+    Ok(res)
+  }
+
+  /// Takes in compressed bytes (after the header, at the start of the chunks)
+  /// and writes the numbers at indices `range` of the file into
+  /// `dst[..range.len()]`, returning progress.
+  ///
+  /// Will return an error if `dst` is shorter than the range or if there are
+  /// any compatibility, corruption, or insufficient data issues.
+  ///
+  /// Chunks ending before the range are skipped by their counts without
+  /// converting or copying any numbers, only the batches overlapping the
+  /// range are written, and no chunks after the range are read.
+  /// However, since chunks do not record their compressed size, every chunk
+  /// before the range must still be read through.
+  /// If the file has checksums, the chunk containing the end of the range is
+  /// also read through (without converting any more numbers) so that its
+  /// checksum can be verified.
+  ///
+  /// The returned `Progress` has `n_processed` equal to the count of numbers
+  /// written, which is less than the length of the range only if the file
+  /// ends first, and `finished` set if the end of the file was reached.
+  pub fn decompress_range<T: Number>(
+    &self,
+    src: &[u8],
+    range: Range<usize>,
+    dst: &mut [T],
+  ) -> PcoResult<Progress> {
+    self.decompress_range_from(src, 0, range, dst)
+  }
+
+  /// Loads the optional chunk index of the file, if it has one.
+  ///
+  /// `file_suffix` must end at the end of the file and contain at least the
+  /// whole index (the entire file also works).
+  /// The index's size is [`FileIndex::exact_size`], which can be found
+  /// from its fixed-size 8 byte trailer.
+  ///
+  /// Will return an error if the index is corrupt or if the suffix is too
+  /// short to contain it.
+  pub fn read_index(&self, file_suffix: &[u8]) -> PcoResult<Option<FileIndex>> {
+    FileIndex::read_from_suffix(file_suffix)
+  }
+
+  /// Behaves like [`decompress_range`][Self::decompress_range], but uses the
+  /// file's index to jump straight to the chunk containing the start of
+  /// the range, without reading through any preceding chunks.
+  ///
+  /// `src` must be the compressed bytes after the header, like with
+  /// [`decompress_range`][Self::decompress_range].
+  pub fn decompress_range_indexed<T: Number>(
+    &self,
+    src: &[u8],
+    index: &FileIndex,
+    range: Range<usize>,
+    dst: &mut [T],
+  ) -> PcoResult<Progress> {
+    validate_range(&range, dst.len())?;
+    let (chunk_idx, chunk_start) = match index.find_chunk(range.start) {
+      Some(found) => found,
+      // the range starts at or past the end of the file
+      None => {
+        return Ok(Progress {
+          n_processed: 0,
+          finished: true,
+        })
+      }
+    };
+
+    let offset = index.chunks[chunk_idx].offset as usize;
+    if offset >= src.len() {
+      return Err(PcoError::insufficient_data(format!(
+        "indexed chunk offset {} exceeds available data length {}",
+        offset,
+        src.len(),
+      )));
+    }
+    self.decompress_range_from(&src[offset..], chunk_start, range, dst)
+  }
+
+  /// Takes in compressed bytes (after the header, at the start of the chunks)
+  /// and writes every number in the file into `dst[..index.n()]`,
+  /// decompressing chunks concurrently.
+  ///
+  /// Will return an error if `dst` is shorter than the file or if there are
+  /// any compatibility, corruption, or insufficient data issues.
+  ///
+  /// Chunks do not record their compressed size, so the file's index (see
+  /// [`read_index`][Self::read_index]) is needed to locate each chunk
+  /// without decoding the ones before it.
+  #[cfg(feature = "parallel")]
+  pub fn decompress_indexed_parallel<T: Number>(
+    &self,
+    src: &[u8],
+    index: &FileIndex,
+    dst: &mut [T],
+  ) -> PcoResult<Progress> {
+    let n = index.n();
+    if dst.len() < n {
+      return Err(PcoError::invalid_argument(format!(
+        "dst's length must be at least the count of numbers in the file ({} < {})",
+        dst.len(),
+        n,
+      )));
+    }
+
+    let mut chunk_dsts = Vec::with_capacity(index.chunks.len());
+    let mut rest = &mut dst[..n];
+    for entry in &index.chunks {
+      let (chunk_dst, new_rest) = rest.split_at_mut(entry.n);
+      chunk_dsts.push((entry, chunk_dst));
+      rest = new_rest;
+    }
+
+    let results = parallel::map(chunk_dsts, |(entry, chunk_dst)| {
+      let offset = entry.offset as usize;
+      if offset >= src.len() {
+        return Err(PcoError::insufficient_data(format!(
+          "indexed chunk offset {} exceeds available data length {}",
+          offset,
+          src.len(),
+        )));
+      }
+      let mut chunk_decompressor = match self.chunk_decompressor::<T, _>(&src[offset..])? {
+        MaybeChunkDecompressor::Some(cd) => cd,
+        MaybeChunkDecompressor::EndOfData(_) => {
+          return Err(PcoError::corruption(format!(
+            "index has a chunk at offset {} but the file ended there",
+            offset,
+          )))
+        }
+      };
+      if chunk_decompressor.n() != entry.n {
+        return Err(PcoError::corruption(format!(
+          "index has a chunk of {} numbers at offset {}, but it has {}",
+          entry.n,
+          offset,
+          chunk_decompressor.n(),
+        )));
+      }
+      chunk_decompressor.decompress(chunk_dst)?;
+      Ok(())
+    });
+    for result in results {
+      result?;
+    }
+
+    Ok(Progress {
+      n_processed: n,
+      finished: true,
+    })
+  }
+
+  // src must be at the start of a chunk beginning at number index
+  // chunk_start, which must not be after range.start
+  fn decompress_range_from<T: Number>(
+    &self,
+    mut src: &[u8],
+    mut chunk_start: usize,
+    range: Range<usize>,
+    dst: &mut [T],
+  ) -> PcoResult<Progress> {
+    validate_range(&range, dst.len())?;
+    let Range { start, end } = range;
+
+    let mut batch_buffer = Vec::new();
+    while chunk_start < end {
+      let mut chunk_decompressor = match self.chunk_decompressor::<T, _>(src)? {
+        MaybeChunkDecompressor::Some(cd) => cd,
+        MaybeChunkDecompressor::EndOfData(_) => {
+          return Ok(Progress {
+            n_processed: chunk_start.saturating_sub(start),
+            finished: true,
+          })
+        }
+      };
+      let chunk_end = chunk_start + chunk_decompressor.n();
+
+      if chunk_end <= start {
+        chunk_decompressor.skip(chunk_decompressor.n())?;
+      } else {
+        // jump to the batch containing the start of the range
+        let skip_n = start.saturating_sub(chunk_start) / FULL_BATCH_N * FULL_BATCH_N;
+        chunk_decompressor.skip(skip_n)?;
+        let mut pos = chunk_start + skip_n;
+        let stop = min(chunk_end, end);
+        if batch_buffer.is_empty() {
+          batch_buffer.resize(FULL_BATCH_N, T::default());
+        }
+
+        while pos < stop {
+          let direct_n = if pos < start {
+            0
+          } else if stop == chunk_end {
+            stop - pos
+          } else {
+            (stop - pos) / FULL_BATCH_N * FULL_BATCH_N
+          };
+
+          if direct_n > 0 {
+            let progress =
+              chunk_decompressor.decompress(&mut dst[pos - start..pos - start + direct_n])?;
+            pos += progress.n_processed;
+          } else {
+            let progress = chunk_decompressor.decompress(&mut batch_buffer)?;
+            let copy_start = max(pos, start);
+            let copy_end = min(pos + progress.n_processed, stop);
+            dst[copy_start - start..copy_end - start]
+              .copy_from_slice(&batch_buffer[copy_start - pos..copy_end - pos]);
+            pos += progress.n_processed;
+          }
+        }
+
+        // the checksum covers the whole chunk
+        if self.checksums && chunk_decompressor.n_remaining() > 0 {
+          chunk_decompressor.skip(chunk_decompressor.n_remaining())?;
+        }
+      }
+
+      chunk_start = chunk_end;
+      src = chunk_decompressor.into_src();
+    }
+
+    Ok(Progress {
+      n_processed: end - start,
+      finished: false,
+    })
+  }
 }
 
 /// Holds metadata about a chunk and supports decompression.
 pub struct ChunkDecompressor<T: Number, R: BetterBufRead> {
   inner_cd: wrapped::ChunkDecompressor<T>,
-  inner_pd: wrapped::PageDecompressor<T, R>,
+  inner_pd: wrapped::PageDecompressor<T, ChecksumBufRead<R>>,
   n: usize,
   n_processed: usize,
 }
 
 impl<T: Number, R: BetterBufRead> ChunkDecompressor<T, R> {
+  pub(crate) fn new(
+    inner_cd: wrapped::ChunkDecompressor<T>,
+    src: ChecksumBufRead<R>,
+    n: usize,
+  ) -> PcoResult<Self> {
+    let inner_pd = inner_cd.page_decompressor(src, n)?;
+    Ok(Self {
+      inner_cd,
+      inner_pd,
+      n,
+      n_processed: 0,
+    })
+  }
+
   /// Returns pre-computed information about the chunk.
   pub fn meta(&self) -> &ChunkMeta {
     &self.inner_cd.meta
@@ -213,17 +581,80 @@ impl<T: Number, R: BetterBufRead> ChunkDecompressor<T, R> {
   ///
   /// `dst` must have length either a multiple of 256 or be at least the count
   /// of numbers remaining in the chunk.
+  /// If the file has checksums, the chunk's checksum is verified only once
+  /// its last number is read (or skipped).
   pub fn decompress(&mut self, dst: &mut [T]) -> PcoResult<Progress> {
     let progress = self.inner_pd.decompress(dst)?;
+    self.update_progress(progress)
+  }
 
-    self.n_processed += progress.n_processed;
+  /// Reads past the next `n` numbers without writing them anywhere,
+  /// returning progress into the chunk.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// `n` must be either a multiple of 256 or be at least the count of numbers
+  /// remaining in the chunk.
+  /// Pco's entropy coding is sequential, so this still needs to decode the
+  /// skipped latents, but it avoids converting them into numbers.
+  pub fn skip(&mut self, n: usize) -> PcoResult<Progress> {
+    let progress = self.inner_pd.skip(n)?;
+    self.update_progress(progress)
+  }
+
+  /// Reads through the rest of the chunk, returning the count, min, max, and
+  /// sum of its numbers without writing them anywhere.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// See [`wrapped::PageDecompressor::aggregate`] for details.
+  pub fn aggregate(&mut self) -> PcoResult<Aggregates<T>> {
+    let aggregates = self.inner_pd.aggregate()?;
+    self.update_progress(Progress {
+      n_processed: aggregates.count,
+      finished: true,
+    })?;
+    Ok(aggregates)
+  }
 
+  /// Reads through the rest of the chunk, returning the indices of the
+  /// numbers `x` with `lo <= x < hi`, counted from the start of the chunk.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// See [`wrapped::PageDecompressor::filter_range`] for details.
+  pub fn filter_range(&mut self, lo: T, hi: T) -> PcoResult<Vec<u32>> {
+    let n_remaining = self.n_remaining();
+    let indices = self.inner_pd.filter_range(lo, hi)?;
+    self.update_progress(Progress {
+      n_processed: n_remaining,
+      finished: true,
+    })?;
+    Ok(indices)
+  }
+
+  fn update_progress(&mut self, progress: Progress) -> PcoResult<Progress> {
+    self.n_processed += progress.n_processed;
+    if progress.finished {
+      self.inner_pd.src_mut().verify()?;
+    }
     Ok(progress)
   }
 
   /// Returns the rest of the compressed data source.
   pub fn into_src(self) -> R {
-    self.inner_pd.into_src()
+    self.inner_pd.into_src().into_inner()
+  }
+
+  // Only safe to use for adding data to the source's buffer, since the page
+  // decompressor may be holding onto its position.
+  #[cfg(feature = "async")]
+  pub(crate) fn src_mut(&mut self) -> &mut R {
+    self.inner_pd.src_mut().inner_mut()
+  }
+
+  pub(crate) fn n_remaining(&self) -> usize {
+    self.n - self.n_processed
   }
 
   // a helper for some internal things
@@ -239,3 +670,304 @@ impl<T: Number, R: BetterBufRead> ChunkDecompressor<T, R> {
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::chunk_config::DeltaSpec;
+  use crate::errors::ErrorKind;
+  use crate::standalone::checksum::CHECKSUM_SIZE;
+  #[cfg(feature = "parallel")]
+  use crate::standalone::simple_decompress_parallel;
+  use crate::standalone::{simple_compress, FileCompressor};
+  use crate::{ChunkConfig, NumberSum, PagingSpec};
+
+  #[test]
+  fn test_decompress_range() -> PcoResult<()> {
+    let nums = (0..2000_i64).map(|x| x * x).collect::<Vec<_>>();
+    let src = simple_compress(
+      &nums,
+      &ChunkConfig {
+        delta_spec: DeltaSpec::TryConsecutive(1),
+        paging_spec: PagingSpec::Exact(vec![300, 700, 1000]),
+        ..Default::default()
+      },
+    )?;
+    let (file_decompressor, src) = FileDecompressor::new(src.as_slice())?;
+
+    for (start, end) in [
+      (0, 0),
+      (0, 2000),
+      (5, 7),
+      (256, 512),
+      (299, 301),
+      (300, 1000),
+      (250, 1777),
+      (1999, 2000),
+    ] {
+      let mut dst = vec![0; end - start];
+      let progress = file_decompressor.decompress_range(src, start..end, &mut dst)?;
+      assert_eq!(progress.n_processed, end - start);
+      assert_eq!(
+        dst,
+        &nums[start..end],
+        "start={} end={}",
+        start,
+        end
+      );
+    }
+
+    // past the end of the file
+    let mut dst = vec![0; 100];
+    let progress = file_decompressor.decompress_range(src, 1950..2050, &mut dst)?;
+    assert_eq!(progress.n_processed, 50);
+    assert!(progress.finished);
+    assert_eq!(&dst[..50], &nums[1950..]);
+
+    // dst too short
+    assert!(file_decompressor
+      .decompress_range(src, 0..10, &mut dst[..5])
+      .is_err());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_index() -> PcoResult<()> {
+    let nums = (0..2000_u32).map(|x| x % 77).collect::<Vec<_>>();
+    let file_compressor = FileCompressor::default();
+    let mut compressed = Vec::new();
+    file_compressor.write_header(&mut compressed)?;
+    let mut index = FileIndex::default();
+    for chunk in nums.chunks(700) {
+      let chunk_compressor = file_compressor.chunk_compressor(chunk, &ChunkConfig::default())?;
+      chunk_compressor.write_chunk_indexed(&mut compressed, &mut index)?;
+    }
+    file_compressor.write_footer_with_index(&index, &mut compressed)?;
+
+    // files with an index are still readable without it
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    assert_eq!(
+      file_decompressor.simple_decompress::<u32>(src)?,
+      nums
+    );
+
+    // the index can be read from just the end of the file
+    let suffix = &compressed[compressed.len() - index.exact_size()..];
+    let read_index = file_decompressor.read_index(suffix)?.unwrap();
+    assert_eq!(read_index.chunks, index.chunks);
+    assert_eq!(read_index.n(), 2000);
+    assert!(file_decompressor.read_index(&suffix[1..]).is_err());
+
+    // we can jump straight to a chunk or page
+    let entry = &read_index.chunks[2];
+    assert_eq!(entry.n, 600);
+    match file_decompressor.chunk_decompressor::<u32, _>(&src[entry.offset as usize..])? {
+      MaybeChunkDecompressor::Some(mut chunk_decompressor) => {
+        let mut dst = vec![0; 600];
+        chunk_decompressor.decompress(&mut dst)?;
+        assert_eq!(dst, &nums[1400..]);
+      }
+      MaybeChunkDecompressor::EndOfData(_) => panic!("expected a chunk"),
+    }
+
+    let mut dst = vec![0; 300];
+    let progress =
+      file_decompressor.decompress_range_indexed(src, &read_index, 1000..1300, &mut dst)?;
+    assert_eq!(progress.n_processed, 300);
+    assert_eq!(dst, &nums[1000..1300]);
+    let progress =
+      file_decompressor.decompress_range_indexed(src, &read_index, 2000..2300, &mut dst)?;
+    assert_eq!(progress.n_processed, 0);
+    assert!(progress.finished);
+
+    // files without an index
+    let compressed = simple_compress(&nums, &ChunkConfig::default())?;
+    let (file_decompressor, _) = FileDecompressor::new(compressed.as_slice())?;
+    assert!(file_decompressor.read_index(&compressed)?.is_none());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_corrupt_index() -> PcoResult<()> {
+    let nums = (0..1000_u32).collect::<Vec<_>>();
+    let file_compressor = FileCompressor::default();
+    let mut compressed = Vec::new();
+    file_compressor.write_header(&mut compressed)?;
+    let mut index = FileIndex::default();
+    let chunk_compressor = file_compressor.chunk_compressor(&nums, &ChunkConfig::default())?;
+    chunk_compressor.write_chunk_indexed(&mut compressed, &mut index)?;
+    file_compressor.write_footer_with_index(&index, &mut compressed)?;
+    let (file_decompressor, _) = FileDecompressor::new(compressed.as_slice())?;
+    assert!(file_decompressor.read_index(&compressed)?.is_some());
+
+    // a chunk claiming more numbers than any chunk can have
+    let trailer_start = compressed.len() - 8;
+    let mut corrupt = compressed.clone();
+    corrupt[trailer_start - 4..trailer_start].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = file_decompressor.read_index(&corrupt).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::Corruption));
+
+    // a chunk count too large for the file
+    let mut corrupt = compressed.clone();
+    corrupt[trailer_start..trailer_start + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = file_decompressor.read_index(&corrupt).unwrap_err();
+    assert!(matches!(
+      err.kind,
+      ErrorKind::InsufficientData
+    ));
+
+    Ok(())
+  }
+
+  #[cfg(feature = "parallel")]
+  #[test]
+  fn test_decompress_indexed_parallel() -> PcoResult<()> {
+    let nums = (0..5000_i64).map(|x| x * x % 997).collect::<Vec<_>>();
+    let file_compressor = FileCompressor::default().with_checksums(true);
+    let mut compressed = Vec::new();
+    file_compressor.write_header(&mut compressed)?;
+    let mut index = FileIndex::default();
+    for chunk in nums.chunks(1100) {
+      let chunk_compressor = file_compressor.chunk_compressor(chunk, &ChunkConfig::default())?;
+      chunk_compressor.write_chunk_indexed(&mut compressed, &mut index)?;
+    }
+    file_compressor.write_footer_with_index(&index, &mut compressed)?;
+
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    let index = file_decompressor.read_index(src)?.unwrap();
+    let mut dst = vec![0; 5001];
+    let progress = file_decompressor.decompress_indexed_parallel(src, &index, &mut dst)?;
+    assert_eq!(progress.n_processed, 5000);
+    assert_eq!(&dst[..5000], &nums);
+    assert_eq!(
+      simple_decompress_parallel::<i64>(&compressed)?,
+      nums
+    );
+
+    // dst too short
+    assert!(file_decompressor
+      .decompress_indexed_parallel(src, &index, &mut dst[..4999])
+      .is_err());
+
+    // an index that disagrees with the chunks
+    let mut bad_index = index.clone();
+    bad_index.chunks[1].n += 1;
+    assert!(matches!(
+      file_decompressor
+        .decompress_indexed_parallel(src, &bad_index, &mut dst)
+        .unwrap_err()
+        .kind,
+      ErrorKind::Corruption
+    ));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_aggregate() -> PcoResult<()> {
+    let nums = (0..3000)
+      .map(|x| ((x * 13) % 1000) as f64 / 8.0 - 20.0)
+      .collect::<Vec<_>>();
+    for store_stats in [false, true] {
+      let config = ChunkConfig::default()
+        .with_store_stats(store_stats)
+        .with_paging_spec(PagingSpec::EqualPagesUpTo(1000));
+      let compressed = simple_compress(&nums, &config)?;
+      let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+      let aggs = file_decompressor.aggregate::<f64>(src)?;
+      assert_eq!(aggs.count, 3000);
+      assert_eq!(aggs.min, Some(-20.0));
+      assert_eq!(aggs.max, Some(999.0 / 8.0 - 20.0));
+      assert_eq!(aggs.sum, NumberSum::Float(nums.iter().sum()));
+      assert_eq!(
+        aggs.mean(),
+        Some(nums.iter().sum::<f64>() / 3000.0)
+      );
+    }
+
+    let compressed = simple_compress::<u16>(&[], &ChunkConfig::default())?;
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    let aggs = file_decompressor.aggregate::<u16>(src)?;
+    assert_eq!(aggs.count, 0);
+    assert_eq!(aggs.min, None);
+    assert_eq!(aggs.sum, NumberSum::Int(0));
+    Ok(())
+  }
+
+  #[test]
+  fn test_filter_range() -> PcoResult<()> {
+    let nums = (0..1000).map(|x| (x % 10) as f32 - 4.5).collect::<Vec<_>>();
+    let compressed = simple_compress(&nums, &ChunkConfig::default())?;
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    let mut chunk_decompressor = match file_decompressor.chunk_decompressor::<f32, _>(src)? {
+      MaybeChunkDecompressor::Some(chunk_decompressor) => chunk_decompressor,
+      MaybeChunkDecompressor::EndOfData(_) => panic!("expected a chunk"),
+    };
+    let indices = chunk_decompressor.filter_range(-0.5, 1.5)?;
+    let expected = (0..1000)
+      .filter(|i| i % 10 == 4 || i % 10 == 5)
+      .collect::<Vec<_>>();
+    assert_eq!(indices, expected);
+    let src = chunk_decompressor.into_src();
+    assert!(matches!(
+      file_decompressor.chunk_decompressor::<f32, _>(src)?,
+      MaybeChunkDecompressor::EndOfData(_)
+    ));
+    Ok(())
+  }
+
+  #[test]
+  fn test_checksums() -> PcoResult<()> {
+    let nums = (0..1000_i32).map(|x| x / 3 - 100).collect::<Vec<_>>();
+    let file_compressor = FileCompressor::default().with_checksums(true);
+    let mut compressed = Vec::new();
+    file_compressor.write_header(&mut compressed)?;
+    for chunk in nums.chunks(400) {
+      file_compressor
+        .chunk_compressor(chunk, &ChunkConfig::default())?
+        .write_chunk(&mut compressed)?;
+    }
+    file_compressor.write_footer(&mut compressed)?;
+
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    assert!(file_decompressor.has_checksums());
+    assert_eq!(
+      file_decompressor.simple_decompress::<i32>(src)?,
+      nums
+    );
+    let mut dst = vec![0; 200];
+    file_decompressor.decompress_range(src, 700..900, &mut dst)?;
+    assert_eq!(dst, &nums[700..900]);
+
+    // corrupt the last chunk's checksum
+    let checksum_idx = compressed.len() - 1 - CHECKSUM_SIZE;
+    compressed[checksum_idx] ^= 1;
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    let err = file_decompressor.simple_decompress::<i32>(src).unwrap_err();
+    assert!(matches!(
+      err.kind,
+      ErrorKind::ChecksumMismatch
+    ));
+    // even when the range ends partway through the chunk
+    let err = file_decompressor
+      .decompress_range(src, 850..900, &mut dst)
+      .unwrap_err();
+    assert!(matches!(
+      err.kind,
+      ErrorKind::ChecksumMismatch
+    ));
+
+    // files without checksums
+    let compressed = simple_compress(&nums, &ChunkConfig::default())?;
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    assert!(!file_decompressor.has_checksums());
+    assert_eq!(
+      file_decompressor.simple_decompress::<i32>(src)?,
+      nums
+    );
+
+    Ok(())
+  }
+}
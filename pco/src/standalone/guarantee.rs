@@ -1,5 +1,6 @@
 use crate::data_types::Latent;
 use crate::errors::PcoResult;
+use crate::standalone::checksum::CHECKSUM_SIZE;
 use crate::standalone::constants::{
   BITS_TO_ENCODE_N_ENTRIES, BITS_TO_ENCODE_STANDALONE_VERSION, BITS_TO_ENCODE_VARINT_POWER,
   MAGIC_HEADER,
@@ -12,13 +13,19 @@ pub fn header_size() -> usize {
   let max_varint_bits = BITS_TO_ENCODE_VARINT_POWER + 64;
   MAGIC_HEADER.len()
     + (max_varint_bits + BITS_TO_ENCODE_STANDALONE_VERSION).div_ceil(8) as usize
+    + 1 // flags
     + wrapped_guarantee::header_size()
 }
 
 /// Returns the maximum possible byte size of a standalone chunk for a given
 /// latent type (e.g. u32 or u64) and count of numbers.
+///
+/// This includes room for the chunk's checksum, in case checksums are
+/// enabled.
 pub fn chunk_size<L: Latent>(n: usize) -> usize {
-  1 + BITS_TO_ENCODE_N_ENTRIES.div_ceil(8) as usize + wrapped_guarantee::chunk_size::<L>(n)
+  1 + BITS_TO_ENCODE_N_ENTRIES.div_ceil(8) as usize
+    + wrapped_guarantee::chunk_size::<L>(n)
+    + CHECKSUM_SIZE
 }
 
 /// Returns the maximum possible byte size of a standalone file given a
@@ -0,0 +1,142 @@
+use std::io::Write;
+
+use crate::constants::MAX_ENTRIES;
+use crate::errors::{PcoError, PcoResult};
+use crate::standalone::constants::*;
+
+/// The location and size of a single chunk within a standalone file.
+///
+/// Offsets are measured in bytes from the start of the first chunk, i.e. from
+/// the position of the source returned by
+/// [`FileDecompressor::new`][crate::standalone::FileDecompressor::new].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkIndexEntry {
+  /// Where the chunk (starting with its data type byte) begins.
+  pub offset: u64,
+  /// Where the chunk's only page begins, after its metadata.
+  pub page_offset: u64,
+  /// The count of numbers in the chunk.
+  pub n: usize,
+}
+
+/// An optional index of the chunks in a standalone file.
+///
+/// This can be written after the termination byte with
+/// [`FileCompressor::write_footer_with_index`][crate::standalone::FileCompressor::write_footer_with_index]
+/// and loaded with
+/// [`FileDecompressor::read_index`][crate::standalone::FileDecompressor::read_index],
+/// allowing readers to jump straight to any chunk instead of parsing every
+/// preceding one.
+/// Decompressors that don't know about the index simply stop reading at the
+/// termination byte.
+#[derive(Clone, Debug, Default)]
+pub struct FileIndex {
+  pub chunks: Vec<ChunkIndexEntry>,
+  // byte size of all chunks recorded so far
+  end_offset: u64,
+}
+
+impl FileIndex {
+  pub(crate) fn push(&mut self, chunk_size: u64, meta_size: u64, n: usize) {
+    let offset = self.end_offset;
+    self.chunks.push(ChunkIndexEntry {
+      offset,
+      page_offset: offset + meta_size,
+      n,
+    });
+    self.end_offset += chunk_size;
+  }
+
+  /// Returns the total count of numbers in the indexed chunks.
+  pub fn n(&self) -> usize {
+    self.chunks.iter().map(|chunk| chunk.n).sum()
+  }
+
+  /// Returns the index of the chunk containing the number at `idx` along
+  /// with the count of numbers preceding that chunk, or `None` if `idx` is
+  /// past the end of the file.
+  pub fn find_chunk(&self, idx: usize) -> Option<(usize, usize)> {
+    let mut chunk_start = 0;
+    for (chunk_idx, chunk) in self.chunks.iter().enumerate() {
+      if idx < chunk_start + chunk.n {
+        return Some((chunk_idx, chunk_start));
+      }
+      chunk_start += chunk.n;
+    }
+    None
+  }
+
+  /// Returns the byte size of the serialized index, including its trailer.
+  pub fn exact_size(&self) -> usize {
+    self.chunks.len() * INDEX_ENTRY_SIZE + INDEX_TRAILER_SIZE
+  }
+
+  pub(crate) fn write_to<W: Write>(&self, mut dst: W) -> PcoResult<W> {
+    let mut bytes = Vec::with_capacity(self.exact_size());
+    for chunk in &self.chunks {
+      bytes.extend(chunk.offset.to_le_bytes());
+      bytes.extend(chunk.page_offset.to_le_bytes());
+      bytes.extend((chunk.n as u32).to_le_bytes());
+    }
+    bytes.extend((self.chunks.len() as u32).to_le_bytes());
+    bytes.extend(MAGIC_INDEX_TRAILER);
+    dst.write_all(&bytes)?;
+    Ok(dst)
+  }
+
+  /// Parses an index from bytes ending at the end of the file.
+  ///
+  /// Returns `None` if the file has no index.
+  pub(crate) fn read_from_suffix(suffix: &[u8]) -> PcoResult<Option<Self>> {
+    if suffix.len() < INDEX_TRAILER_SIZE {
+      return Ok(None);
+    }
+    let trailer = &suffix[suffix.len() - INDEX_TRAILER_SIZE..];
+    if trailer[4..] != MAGIC_INDEX_TRAILER {
+      return Ok(None);
+    }
+
+    let n_chunks = u32::from_le_bytes(trailer[..4].try_into().unwrap()) as usize;
+    // the chunk count is untrusted, so we check for overflow
+    let size = n_chunks
+      .checked_mul(INDEX_ENTRY_SIZE)
+      .and_then(|entries_size| entries_size.checked_add(INDEX_TRAILER_SIZE));
+    let size = match size {
+      Some(size) if size <= suffix.len() => size,
+      _ => {
+        return Err(PcoError::insufficient_data(format!(
+          "index of {} chunks is longer than the {} bytes provided",
+          n_chunks,
+          suffix.len(),
+        )))
+      }
+    };
+
+    let mut res = Self::default();
+    for entry in
+      suffix[suffix.len() - size..suffix.len() - INDEX_TRAILER_SIZE].chunks_exact(INDEX_ENTRY_SIZE)
+    {
+      let offset = u64::from_le_bytes(entry[..8].try_into().unwrap());
+      let page_offset = u64::from_le_bytes(entry[8..16].try_into().unwrap());
+      let n = u32::from_le_bytes(entry[16..].try_into().unwrap()) as usize;
+      let min_offset = res.chunks.last().map_or(0, |prev| prev.page_offset);
+      let is_valid = if res.chunks.is_empty() {
+        offset == 0
+      } else {
+        offset >= min_offset
+      };
+      if !is_valid || page_offset <= offset || n == 0 || n > MAX_ENTRIES {
+        return Err(PcoError::corruption(format!(
+          "invalid index entry with offset {}, page offset {}, and n {}",
+          offset, page_offset, n,
+        )));
+      }
+      res.chunks.push(ChunkIndexEntry {
+        offset,
+        page_offset,
+        n,
+      });
+    }
+    Ok(Some(res))
+  }
+}
@@ -1,11 +1,24 @@
+#[cfg(feature = "async")]
+pub use async_decompressor::{AsyncChunkDecompressor, MaybeAsyncChunkDecompressor};
 pub use compressor::{ChunkCompressor, FileCompressor};
 pub use decompressor::{ChunkDecompressor, FileDecompressor, MaybeChunkDecompressor};
+pub use index::{ChunkIndexEntry, FileIndex};
+pub use nullable::{simple_compress_nullable, simple_decompress_nullable, NullableNums};
+pub use number_reader::NumberReader;
 pub use number_type_or_termination::NumberTypeOrTermination;
 pub use simple::*;
+pub use streaming::StreamingCompressor;
 
+#[cfg(feature = "async")]
+mod async_decompressor;
+mod checksum;
 mod compressor;
 mod constants;
 mod decompressor;
 pub mod guarantee;
+pub(crate) mod index;
+mod nullable;
+mod number_reader;
 mod number_type_or_termination;
 mod simple;
+mod streaming;
@@ -0,0 +1,290 @@
+use crate::chunk_config::ChunkConfig;
+use crate::constants::MAX_ENTRIES;
+use crate::data_types::Number;
+use crate::errors::{PcoError, PcoResult};
+use crate::standalone::compressor::FileCompressor;
+use crate::standalone::decompressor::{FileDecompressor, MaybeChunkDecompressor};
+use crate::PagingSpec;
+
+// Run lengths alternate between valid and null rows, starting with valid.
+// A column without nulls has a single run.
+pub(crate) fn runs_from_validity(validity: &[bool]) -> Vec<u64> {
+  if validity.is_empty() {
+    return Vec::new();
+  }
+
+  let mut runs = Vec::new();
+  let mut run_is_valid = true;
+  let mut run_n = 0;
+  for &is_valid in validity {
+    if is_valid != run_is_valid {
+      runs.push(run_n);
+      run_is_valid = is_valid;
+      run_n = 0;
+    }
+    run_n += 1;
+  }
+  runs.push(run_n);
+  runs
+}
+
+// Each chunk of runs covers at most MAX_ENTRIES rows, just like each chunk of
+// numbers holds at most MAX_ENTRIES numbers.
+// The runs are untrusted, so we check this before allocating any validity.
+pub(crate) fn count_chunk_rows(runs: &[u64]) -> PcoResult<usize> {
+  let n_rows = runs.iter().try_fold(0_u64, |n_rows, &run_n| {
+    n_rows.checked_add(run_n)
+  });
+  match n_rows {
+    Some(n_rows) if n_rows <= MAX_ENTRIES as u64 => Ok(n_rows as usize),
+    _ => Err(PcoError::corruption(format!(
+      "validity chunk covers more than the max of {} rows",
+      MAX_ENTRIES,
+    ))),
+  }
+}
+
+pub(crate) fn validity_from_runs(runs: &[u64], n_rows: usize) -> Vec<bool> {
+  let mut validity = Vec::with_capacity(n_rows);
+  for (run_idx, &run_n) in runs.iter().enumerate() {
+    let is_valid = run_idx % 2 == 0;
+    validity.resize(validity.len() + run_n as usize, is_valid);
+  }
+  validity
+}
+
+fn count_valid(validity: &[bool]) -> usize {
+  validity.iter().filter(|&&is_valid| is_valid).count()
+}
+
+/// The numbers of a nullable column, split into its non-null values and
+/// the validity of each row.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NullableNums<T> {
+  /// The non-null numbers, in order.
+  pub values: Vec<T>,
+  /// Whether each row is non-null.
+  /// The count of `true`s equals the length of `values`.
+  pub validity: Vec<bool>,
+}
+
+impl<T: Number> NullableNums<T> {
+  pub fn from_options(nums: &[Option<T>]) -> Self {
+    Self {
+      values: nums.iter().flatten().copied().collect(),
+      validity: nums.iter().map(Option::is_some).collect(),
+    }
+  }
+
+  pub fn to_options(&self) -> Vec<Option<T>> {
+    let mut values = self.values.iter();
+    self
+      .validity
+      .iter()
+      .map(|&is_valid| {
+        if is_valid {
+          values.next().copied()
+        } else {
+          None
+        }
+      })
+      .collect()
+  }
+
+  /// Returns the count of rows, including nulls.
+  pub fn len(&self) -> usize {
+    self.validity.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.validity.is_empty()
+  }
+}
+
+/// Takes in the non-null numbers of a nullable column along with the
+/// validity of each row and returns compressed bytes.
+///
+/// Will return an error if the count of `true`s in `validity` doesn't match
+/// the length of `values` or if the compressor config is invalid.
+/// Only the non-null numbers are stored in chunks, split according to the
+/// `PagingSpec` in `ChunkConfig`; nulls are stored compactly as run lengths.
+pub fn simple_compress_nullable<T: Number>(
+  values: &[T],
+  validity: &[bool],
+  config: &ChunkConfig,
+) -> PcoResult<Vec<u8>> {
+  let n_valid = count_valid(validity);
+  if n_valid != values.len() {
+    return Err(PcoError::invalid_argument(format!(
+      "validity has {} non-null rows but {} values were provided",
+      n_valid,
+      values.len(),
+    )));
+  }
+
+  let mut dst = Vec::new();
+  let file_compressor = FileCompressor::default()
+    .with_n_hint(values.len())
+    .with_nullable(true);
+  file_compressor.write_header(&mut dst)?;
+
+  let n_per_page = config.paging_spec.n_per_page(values.len())?;
+  let mut start = 0;
+  let mut this_chunk_config = config.clone();
+  for &page_n in &n_per_page {
+    let end = start + page_n;
+    this_chunk_config.paging_spec = PagingSpec::Exact(vec![page_n]);
+    file_compressor
+      .chunk_compressor(&values[start..end], &this_chunk_config)?
+      .write_chunk(&mut dst)?;
+    start = end;
+  }
+
+  file_compressor.write_footer(&mut dst)?;
+  file_compressor.write_validity(validity, &mut dst)?;
+  Ok(dst)
+}
+
+/// Takes in compressed bytes and returns the non-null numbers along with the
+/// validity of each row.
+///
+/// Will return an error if there are any compatibility, corruption,
+/// or insufficient data issues.
+/// Files that aren't nullable are treated as having no nulls.
+pub fn simple_decompress_nullable<T: Number>(src: &[u8]) -> PcoResult<NullableNums<T>> {
+  let (file_decompressor, mut src) = FileDecompressor::new(src)?;
+
+  let mut values = Vec::with_capacity(file_decompressor.n_hint());
+  let rest = loop {
+    match file_decompressor.chunk_decompressor(src)? {
+      MaybeChunkDecompressor::Some(mut cd) => {
+        cd.decompress_remaining_extend(&mut values)?;
+        src = cd.into_src();
+      }
+      MaybeChunkDecompressor::EndOfData(rest) => break rest,
+    }
+  };
+
+  if !file_decompressor.is_nullable() {
+    let validity = vec![true; values.len()];
+    return Ok(NullableNums { values, validity });
+  }
+
+  let (validity, _) = file_decompressor.read_validity(rest)?;
+  let n_valid = count_valid(&validity);
+  if n_valid != values.len() {
+    return Err(PcoError::corruption(format!(
+      "validity has {} non-null rows but the file has {} values",
+      n_valid,
+      values.len(),
+    )));
+  }
+  Ok(NullableNums { values, validity })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::errors::ErrorKind;
+  use crate::standalone::simple_decompress;
+
+  #[test]
+  fn test_runs() {
+    assert_eq!(runs_from_validity(&[]), Vec::<u64>::new());
+    assert_eq!(runs_from_validity(&[true, true]), vec![2]);
+    assert_eq!(runs_from_validity(&[false]), vec![0, 1]);
+    assert_eq!(
+      runs_from_validity(&[true, false, false, true]),
+      vec![1, 2, 1]
+    );
+    for validity in [vec![false, false, true], vec![true, false, true, false]] {
+      let runs = runs_from_validity(&validity);
+      assert_eq!(
+        validity_from_runs(&runs, validity.len()),
+        validity
+      );
+    }
+  }
+
+  #[test]
+  fn test_corrupt_runs() -> PcoResult<()> {
+    let config = ChunkConfig::default();
+    for runs in [
+      vec![1_u64 << 62, 5],
+      vec![u64::MAX, 1],
+      vec![MAX_ENTRIES as u64, 1],
+    ] {
+      let mut compressed = Vec::new();
+      let file_compressor = FileCompressor::default().with_nullable(true);
+      file_compressor.write_header(&mut compressed)?;
+      file_compressor.write_footer(&mut compressed)?;
+      // write the runs directly, bypassing write_validity's chunking
+      file_compressor
+        .chunk_compressor(&runs, &config)?
+        .write_chunk(&mut compressed)?;
+      file_compressor.write_footer(&mut compressed)?;
+
+      let err = simple_decompress_nullable::<u32>(&compressed).unwrap_err();
+      assert!(matches!(err.kind, ErrorKind::Corruption));
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn test_nullable_recovery() -> PcoResult<()> {
+    let nums = (0..3000)
+      .map(|i: i32| if i % 7 < 2 { None } else { Some(i) })
+      .collect::<Vec<_>>();
+    let nullable = NullableNums::from_options(&nums);
+    let config = ChunkConfig::default().with_paging_spec(PagingSpec::EqualPagesUpTo(1000));
+    let compressed = simple_compress_nullable(&nullable.values, &nullable.validity, &config)?;
+
+    let recovered = simple_decompress_nullable::<i32>(&compressed)?;
+    assert_eq!(recovered, nullable);
+    assert_eq!(recovered.to_options(), nums);
+
+    // readers that ignore validity still see the non-null values
+    assert_eq!(
+      simple_decompress::<i32>(&compressed)?,
+      nullable.values
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_nullable_edge_cases() -> PcoResult<()> {
+    let config = ChunkConfig::default();
+    for nums in [
+      vec![],
+      vec![None, None, None],
+      vec![Some(1.5_f32), Some(2.5)],
+    ] {
+      let nullable = NullableNums::from_options(&nums);
+      let compressed = simple_compress_nullable(&nullable.values, &nullable.validity, &config)?;
+      let recovered = simple_decompress_nullable::<f32>(&compressed)?;
+      assert_eq!(recovered.to_options(), nums);
+    }
+
+    // validity spanning more than one chunk's worth of rows
+    let validity = vec![false; MAX_ENTRIES + 5];
+    let compressed = simple_compress_nullable::<f32>(&[], &validity, &config)?;
+    let recovered = simple_decompress_nullable::<f32>(&compressed)?;
+    assert_eq!(recovered.validity, validity);
+
+    // non-nullable files have no nulls
+    let compressed = crate::standalone::simple_compress(&[1_u16, 2], &config)?;
+    let recovered = simple_decompress_nullable::<u16>(&compressed)?;
+    assert_eq!(recovered.validity, vec![true, true]);
+    Ok(())
+  }
+
+  #[test]
+  fn test_mismatched_validity() {
+    let res = simple_compress_nullable(
+      &[1_u32, 2],
+      &[true, false],
+      &ChunkConfig::default(),
+    );
+    assert!(res.is_err());
+  }
+}
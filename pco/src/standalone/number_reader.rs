@@ -0,0 +1,259 @@
+use std::cmp::min;
+use std::mem;
+
+use better_io::BetterBufRead;
+
+use crate::constants::FULL_BATCH_N;
+use crate::data_types::Number;
+use crate::errors::{PcoError, PcoResult};
+use crate::standalone::decompressor::{
+  ChunkDecompressor, FileDecompressor, MaybeChunkDecompressor,
+};
+
+enum State<T: Number, R: BetterBufRead> {
+  BetweenChunks(R),
+  InChunk(Box<ChunkDecompressor<T, R>>),
+  Finished(R),
+  // the source was lost to an error
+  Failed,
+}
+
+/// Reads the numbers of a standalone .pco file across chunk boundaries.
+///
+/// This manages chunk decompressors internally, so numbers can be read into
+/// buffers of any size via [`read`][Self::read], or iterated over in batches
+/// of `batch_n` numbers.
+/// Every batch but the last contains exactly `batch_n` numbers.
+///
+/// Example:
+/// ```
+/// use pco::standalone::{simple_compress, NumberReader};
+/// use pco::ChunkConfig;
+/// # use pco::errors::PcoResult;
+///
+/// # fn main() -> PcoResult<()> {
+/// let nums = (0..1000).collect::<Vec<i64>>();
+/// let compressed = simple_compress(&nums, &ChunkConfig::default())?;
+/// let reader = NumberReader::<i64, _>::new(compressed.as_slice(), 300)?;
+/// let mut n = 0;
+/// for batch in reader {
+///   n += batch?.len();
+/// }
+/// assert_eq!(n, 1000);
+/// # Ok(())
+/// # }
+/// ```
+pub struct NumberReader<T: Number, R: BetterBufRead> {
+  file_decompressor: FileDecompressor,
+  state: State<T, R>,
+  batch_n: usize,
+  // numbers decompressed from a chunk but not yet returned
+  pending: Vec<T>,
+  pending_idx: usize,
+}
+
+impl<T: Number, R: BetterBufRead> NumberReader<T, R> {
+  /// Reads the file's header and creates a `NumberReader`.
+  ///
+  /// Will return an error if `batch_n` is 0 or if any version
+  /// incompatibilities, corruptions, or insufficient data are found.
+  pub fn new(src: R, batch_n: usize) -> PcoResult<Self> {
+    if batch_n == 0 {
+      return Err(PcoError::invalid_argument(
+        "batch size must be positive",
+      ));
+    }
+    let (file_decompressor, src) = FileDecompressor::new(src)?;
+    Ok(Self {
+      file_decompressor,
+      state: State::BetweenChunks(src),
+      batch_n,
+      pending: Vec::new(),
+      pending_idx: 0,
+    })
+  }
+
+  pub fn file_decompressor(&self) -> &FileDecompressor {
+    &self.file_decompressor
+  }
+
+  fn copy_pending(&mut self, dst: &mut [T]) -> usize {
+    let n = min(
+      dst.len(),
+      self.pending.len() - self.pending_idx,
+    );
+    dst[..n].copy_from_slice(&self.pending[self.pending_idx..self.pending_idx + n]);
+    self.pending_idx += n;
+    n
+  }
+
+  // Decompresses at least one number into dst unless the chunk is empty,
+  // returning the count written and whether the chunk is done.
+  fn read_from_chunk(
+    &mut self,
+    cd: &mut ChunkDecompressor<T, R>,
+    dst: &mut [T],
+  ) -> PcoResult<(usize, bool)> {
+    let n_remaining = cd.n_remaining();
+    if dst.len() >= n_remaining || dst.len() >= FULL_BATCH_N {
+      // decompress directly into dst
+      let n = if dst.len() >= n_remaining {
+        n_remaining
+      } else {
+        dst.len() / FULL_BATCH_N * FULL_BATCH_N
+      };
+      let progress = cd.decompress(&mut dst[..n])?;
+      return Ok((progress.n_processed, progress.finished));
+    }
+
+    // dst is smaller than a batch, so we decompress one into the buffer
+    self.pending.resize(FULL_BATCH_N, T::default());
+    let progress = cd.decompress(&mut self.pending)?;
+    self.pending.truncate(progress.n_processed);
+    self.pending_idx = 0;
+    Ok((self.copy_pending(dst), progress.finished))
+  }
+
+  /// Reads the next numbers of the file into `dst`, returning the count
+  /// written.
+  ///
+  /// This fills `dst` entirely unless the end of the file is reached, so a
+  /// return value of 0 (for nonempty `dst`) means all numbers have been read.
+  /// Will return an error if corruptions or insufficient data are found, after
+  /// which the reader cannot be used further.
+  pub fn read(&mut self, dst: &mut [T]) -> PcoResult<usize> {
+    let mut n_read = self.copy_pending(dst);
+    while n_read < dst.len() {
+      match mem::replace(&mut self.state, State::Failed) {
+        State::BetweenChunks(src) => {
+          self.state = match self.file_decompressor.chunk_decompressor(src)? {
+            MaybeChunkDecompressor::Some(cd) => State::InChunk(Box::new(cd)),
+            MaybeChunkDecompressor::EndOfData(src) => State::Finished(src),
+          };
+        }
+        State::InChunk(mut cd) => {
+          let (n, finished) = self.read_from_chunk(&mut cd, &mut dst[n_read..])?;
+          n_read += n;
+          self.state = if finished {
+            State::BetweenChunks(cd.into_src())
+          } else {
+            State::InChunk(cd)
+          };
+        }
+        State::Finished(src) => {
+          self.state = State::Finished(src);
+          break;
+        }
+        State::Failed => {
+          return Err(PcoError::invalid_argument(
+            "cannot read from a NumberReader after it has errored",
+          ))
+        }
+      }
+    }
+    Ok(n_read)
+  }
+
+  /// Returns the rest of the compressed data source if all numbers have been
+  /// read, e.g. to read the file's validity afterward.
+  pub fn into_src(self) -> Option<R> {
+    match self.state {
+      State::Finished(src) => Some(src),
+      _ => None,
+    }
+  }
+}
+
+impl<T: Number, R: BetterBufRead> Iterator for NumberReader<T, R> {
+  type Item = PcoResult<Vec<T>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if matches!(self.state, State::Failed) {
+      return None;
+    }
+
+    let mut batch = vec![T::default(); self.batch_n];
+    match self.read(&mut batch) {
+      Ok(0) => None,
+      Ok(n) => {
+        batch.truncate(n);
+        Some(Ok(batch))
+      }
+      Err(e) => Some(Err(e)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::chunk_config::ChunkConfig;
+  use crate::standalone::{simple_compress, FileCompressor};
+
+  fn compress_chunks(chunk_ns: &[usize]) -> PcoResult<(Vec<i32>, Vec<u8>)> {
+    let fc = FileCompressor::default();
+    let config = ChunkConfig::default();
+    let mut nums = Vec::new();
+    let mut compressed = fc.write_header(Vec::new())?;
+    for &n in chunk_ns {
+      let chunk = (0..n)
+        .map(|i| (nums.len() + i) as i32 * 3)
+        .collect::<Vec<_>>();
+      compressed = fc
+        .chunk_compressor(&chunk, &config)?
+        .write_chunk(compressed)?;
+      nums.extend(chunk);
+    }
+    Ok((nums, fc.write_footer(compressed)?))
+  }
+
+  #[test]
+  fn test_batches_across_chunks() -> PcoResult<()> {
+    let (nums, compressed) = compress_chunks(&[700, 1, 300])?;
+    for batch_n in [1, 100, 256, 300, 1001, 5000] {
+      let batches = NumberReader::<i32, _>::new(compressed.as_slice(), batch_n)?
+        .collect::<PcoResult<Vec<_>>>()?;
+      assert_eq!(batches.len(), nums.len().div_ceil(batch_n));
+      assert!(batches[..batches.len() - 1]
+        .iter()
+        .all(|batch| batch.len() == batch_n));
+      assert_eq!(batches.concat(), nums);
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn test_read_uneven_sizes() -> PcoResult<()> {
+    let (nums, compressed) = compress_chunks(&[513, 20])?;
+    let mut reader = NumberReader::<i32, _>::new(compressed.as_slice(), 1)?;
+    let mut res = Vec::new();
+    let mut size = 1;
+    loop {
+      let mut dst = vec![0; size];
+      let n = reader.read(&mut dst)?;
+      if n == 0 {
+        break;
+      }
+      res.extend_from_slice(&dst[..n]);
+      size = size * 3 + 1;
+    }
+    assert_eq!(res, nums);
+    assert_eq!(reader.into_src(), Some(&[][..]));
+    Ok(())
+  }
+
+  #[test]
+  fn test_empty_and_errors() -> PcoResult<()> {
+    let compressed = simple_compress::<f32>(&[], &ChunkConfig::default())?;
+    let mut reader = NumberReader::<f32, _>::new(compressed.as_slice(), 10)?;
+    assert!(reader.next().is_none());
+    assert!(NumberReader::<f32, _>::new(compressed.as_slice(), 0).is_err());
+
+    let (_, compressed) = compress_chunks(&[1000])?;
+    let truncated = &compressed[..compressed.len() / 2];
+    let mut reader = NumberReader::<i32, _>::new(truncated, 1000)?;
+    assert!(matches!(reader.next(), Some(Err(_))));
+    assert!(reader.next().is_none());
+    Ok(())
+  }
+}
@@ -2,7 +2,11 @@ use std::cmp::min;
 
 use crate::chunk_config::ChunkConfig;
 use crate::data_types::Number;
+#[cfg(feature = "parallel")]
+use crate::errors::PcoError;
 use crate::errors::PcoResult;
+#[cfg(feature = "parallel")]
+use crate::parallel;
 use crate::progress::Progress;
 use crate::standalone::compressor::FileCompressor;
 use crate::standalone::decompressor::{FileDecompressor, MaybeChunkDecompressor};
@@ -85,6 +89,42 @@ pub fn simple_compress<T: Number>(nums: &[T], config: &ChunkConfig) -> PcoResult
   Ok(dst)
 }
 
+/// Takes in a slice of numbers and an exact configuration and returns
+/// compressed bytes, compressing its chunks concurrently.
+///
+/// Will return an error if the compressor config is invalid.
+/// The output is identical to that of [`simple_compress`], but all chunks are
+/// held in memory until they have been compressed.
+#[cfg(feature = "parallel")]
+pub fn simple_compress_parallel<T: Number>(nums: &[T], config: &ChunkConfig) -> PcoResult<Vec<u8>> {
+  let mut dst = Vec::new();
+  let file_compressor = FileCompressor::default().with_n_hint(nums.len());
+  file_compressor.write_header(&mut dst)?;
+
+  // here we use the paging spec to determine chunks; each chunk has 1 page
+  let n_per_page = config.paging_spec.n_per_page(nums.len())?;
+  let mut chunk_ranges = Vec::with_capacity(n_per_page.len());
+  let mut start = 0;
+  for &page_n in &n_per_page {
+    chunk_ranges.push(start..start + page_n);
+    start += page_n;
+  }
+
+  let chunks = parallel::map(chunk_ranges, |range| {
+    let mut this_chunk_config = config.clone();
+    this_chunk_config.paging_spec = PagingSpec::Exact(vec![range.len()]);
+    file_compressor
+      .chunk_compressor(&nums[range], &this_chunk_config)?
+      .write_chunk(Vec::new())
+  });
+  for chunk in chunks {
+    dst.extend(chunk?);
+  }
+
+  file_compressor.write_footer(&mut dst)?;
+  Ok(dst)
+}
+
 /// Takes in compressed bytes and writes numbers to the destination, returning
 /// progress into the file.
 ///
@@ -161,6 +201,37 @@ pub fn simple_decompress<T: Number>(src: &[u8]) -> PcoResult<Vec<T>> {
   file_decompressor.simple_decompress(src)
 }
 
+/// Takes in compressed bytes and returns a vector of numbers, decompressing
+/// chunks concurrently.
+///
+/// Will return an error if there are any compatibility, corruption,
+/// or insufficient data issues.
+/// Chunks can only be located ahead of time if the file has an index (see
+/// [`FileIndex`][crate::standalone::FileIndex]); files without one are
+/// decompressed serially, like with [`simple_decompress`].
+#[cfg(feature = "parallel")]
+pub fn simple_decompress_parallel<T: Number>(src: &[u8]) -> PcoResult<Vec<T>> {
+  let (file_decompressor, chunks_src) = FileDecompressor::new(src)?;
+  let index = match file_decompressor.read_index(src)? {
+    Some(index) => index,
+    None => return file_decompressor.simple_decompress(chunks_src),
+  };
+  // check the index against the data before trusting it with an allocation
+  if let Some(last) = index.chunks.last() {
+    if last.page_offset as usize >= chunks_src.len() {
+      return Err(PcoError::corruption(format!(
+        "index has a chunk at offset {} but the file only has {} bytes of chunks",
+        last.offset,
+        chunks_src.len(),
+      )));
+    }
+  }
+
+  let mut res = vec![T::default(); index.n()];
+  file_decompressor.decompress_indexed_parallel(chunks_src, &index, &mut res)?;
+  Ok(res)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -219,4 +290,23 @@ mod tests {
 
     Ok(())
   }
+
+  #[cfg(feature = "parallel")]
+  #[test]
+  fn test_simple_compress_parallel() -> PcoResult<()> {
+    let nums = (0..3000).map(|x| (x * x % 1009) as f32).collect::<Vec<_>>();
+    let config = ChunkConfig {
+      paging_spec: PagingSpec::Exact(vec![1000, 1, 1999]),
+      ..Default::default()
+    };
+    let compressed = simple_compress_parallel(&nums, &config)?;
+    assert_eq!(compressed, simple_compress(&nums, &config)?);
+    assert_eq!(simple_decompress::<f32>(&compressed)?, nums);
+    // this file has no index, so it is decompressed serially
+    assert_eq!(
+      simple_decompress_parallel::<f32>(&compressed)?,
+      nums
+    );
+    Ok(())
+  }
 }
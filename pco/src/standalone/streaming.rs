@@ -0,0 +1,345 @@
+use std::io::Write;
+use std::mem;
+
+use crate::chunk_config::ChunkConfig;
+use crate::constants::MAX_ENTRIES;
+use crate::data_types::Number;
+use crate::errors::{PcoError, PcoResult};
+use crate::standalone::compressor::FileCompressor;
+use crate::standalone::index::FileIndex;
+
+/// Compresses numbers into a standalone .pco file as they arrive.
+///
+/// Numbers are buffered until there are `chunk_n` of them, at which point
+/// they are compressed as a chunk and written to the destination.
+/// The file is only complete once [`finish`][Self::finish] has been called;
+/// numbers still buffered when this is dropped are lost.
+/// If writing a chunk fails, part of it may already be in the destination,
+/// so every later call returns an error instead of producing a corrupt file.
+///
+/// Example:
+/// ```
+/// use pco::ChunkConfig;
+/// use pco::standalone::{simple_decompress, FileCompressor, StreamingCompressor};
+/// # use pco::errors::PcoResult;
+///
+/// # fn main() -> PcoResult<()> {
+/// let mut compressor = StreamingCompressor::<i64, _>::new(
+///   FileCompressor::default(),
+///   &ChunkConfig::default(),
+///   1000,
+///   Vec::new(),
+/// )?;
+/// for i in 0..2500 {
+///   compressor.push(i)?;
+/// }
+/// let compressed = compressor.finish()?;
+/// // now `compressed` is a complete .pco file with 3 chunks
+/// assert_eq!(simple_decompress::<i64>(&compressed)?.len(), 2500);
+/// # Ok(())
+/// # }
+/// ```
+pub struct StreamingCompressor<T: Number, W: Write> {
+  file_compressor: FileCompressor,
+  config: ChunkConfig,
+  chunk_n: usize,
+  buffer: Vec<T>,
+  index: FileIndex,
+  dst: W,
+  poisoned: bool,
+}
+
+impl<T: Number, W: Write> StreamingCompressor<T, W> {
+  /// Creates a `StreamingCompressor` and writes the file's header to the
+  /// destination.
+  ///
+  /// Will return an error if `chunk_n` is 0 or exceeds the max count of
+  /// numbers per chunk, if the file compressor is nullable, or if the
+  /// provided `Write` errors.
+  ///
+  /// Every chunk but the last will contain exactly `chunk_n` numbers, and
+  /// each is compressed with `config`, ignoring its `PagingSpec`.
+  pub fn new(
+    file_compressor: FileCompressor,
+    config: &ChunkConfig,
+    chunk_n: usize,
+    dst: W,
+  ) -> PcoResult<Self> {
+    if chunk_n == 0 || chunk_n > MAX_ENTRIES {
+      return Err(PcoError::invalid_argument(format!(
+        "chunk_n must be between 1 and {} (was {})",
+        MAX_ENTRIES, chunk_n,
+      )));
+    }
+    if file_compressor.is_nullable() {
+      return Err(PcoError::invalid_argument(
+        "streaming compression does not support nullable files",
+      ));
+    }
+
+    let dst = file_compressor.write_header(dst)?;
+    Ok(Self {
+      file_compressor,
+      config: config.clone(),
+      chunk_n,
+      buffer: Vec::new(),
+      index: FileIndex::default(),
+      dst,
+      poisoned: false,
+    })
+  }
+
+  /// Returns the count of numbers pushed but not yet written in a chunk.
+  pub fn n_buffered(&self) -> usize {
+    self.buffer.len()
+  }
+
+  fn check_not_poisoned(&self) -> PcoResult<()> {
+    if self.poisoned {
+      return Err(PcoError::invalid_argument(
+        "streaming compressor already failed to write a chunk",
+      ));
+    }
+    Ok(())
+  }
+
+  fn write_chunk(&mut self, nums: &[T]) -> PcoResult<()> {
+    let res = self
+      .file_compressor
+      .chunk_compressor(nums, &self.config)
+      .and_then(|chunk_compressor| {
+        chunk_compressor.write_chunk_indexed(&mut self.dst, &mut self.index)
+      });
+    // the destination may now hold part of a chunk
+    if res.is_err() {
+      self.poisoned = true;
+    }
+    res.map(|_| ())
+  }
+
+  /// Buffers a number, writing a chunk to the destination if this fills it.
+  ///
+  /// Will return an error if the compressor config is invalid, if the
+  /// provided `Write` errors, or if an earlier chunk failed to write.
+  pub fn push(&mut self, num: T) -> PcoResult<()> {
+    self.check_not_poisoned()?;
+    self.buffer.push(num);
+    if self.buffer.len() == self.chunk_n {
+      self.flush_chunk()?;
+    }
+    Ok(())
+  }
+
+  /// Buffers numbers, writing a chunk to the destination each time one is
+  /// filled.
+  ///
+  /// Will return an error if the compressor config is invalid, if the
+  /// provided `Write` errors, or if an earlier chunk failed to write.
+  pub fn extend_from_slice(&mut self, mut nums: &[T]) -> PcoResult<()> {
+    self.check_not_poisoned()?;
+    while !nums.is_empty() {
+      if self.buffer.is_empty() && nums.len() >= self.chunk_n {
+        // compress straight from the input to avoid copying it
+        let (chunk, rest) = nums.split_at(self.chunk_n);
+        self.write_chunk(chunk)?;
+        nums = rest;
+        continue;
+      }
+
+      let n_to_buffer = (self.chunk_n - self.buffer.len()).min(nums.len());
+      self.buffer.extend_from_slice(&nums[..n_to_buffer]);
+      nums = &nums[n_to_buffer..];
+      if self.buffer.len() == self.chunk_n {
+        self.flush_chunk()?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Writes any buffered numbers to the destination as a chunk, even if it
+  /// is not full.
+  ///
+  /// Will return an error if the compressor config is invalid, if the
+  /// provided `Write` errors, or if an earlier chunk failed to write.
+  ///
+  /// This can be used to bound latency, at the cost of compressing smaller
+  /// chunks.
+  pub fn flush_chunk(&mut self) -> PcoResult<()> {
+    self.check_not_poisoned()?;
+    if self.buffer.is_empty() {
+      return Ok(());
+    }
+
+    let buffer = mem::take(&mut self.buffer);
+    self.write_chunk(&buffer)?;
+    // reuse the allocation for the next chunk
+    self.buffer = buffer;
+    self.buffer.clear();
+    Ok(())
+  }
+
+  /// Writes any buffered numbers and the footer to the destination,
+  /// returning it.
+  ///
+  /// Will return an error if the compressor config is invalid, if the
+  /// provided `Write` errors, or if an earlier chunk failed to write.
+  pub fn finish(mut self) -> PcoResult<W> {
+    self.flush_chunk()?;
+    self.file_compressor.write_footer(self.dst)
+  }
+
+  /// Like [`finish`][Self::finish], but also writes an index of all the
+  /// chunks after the footer.
+  ///
+  /// See [`FileIndex`] for how the index can be used.
+  pub fn finish_with_index(mut self) -> PcoResult<W> {
+    self.flush_chunk()?;
+    self
+      .file_compressor
+      .write_footer_with_index(&self.index, self.dst)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::errors::ErrorKind;
+  use crate::standalone::{simple_compress, simple_decompress, FileDecompressor};
+  use crate::PagingSpec;
+
+  #[test]
+  fn test_streaming_matches_chunked() -> PcoResult<()> {
+    let nums = (0..2500_u32).map(|x| x * x % 1013).collect::<Vec<_>>();
+    let config = ChunkConfig::default();
+    let mut compressor = StreamingCompressor::new(
+      FileCompressor::default(),
+      &config,
+      1000,
+      Vec::new(),
+    )?;
+    compressor.extend_from_slice(&nums[..10])?;
+    for &x in &nums[10..1500] {
+      compressor.push(x)?;
+    }
+    assert_eq!(compressor.n_buffered(), 500);
+    compressor.extend_from_slice(&nums[1500..])?;
+    assert_eq!(compressor.n_buffered(), 500);
+    let compressed = compressor.finish()?;
+
+    let expected = simple_compress(
+      &nums,
+      &config
+        .clone()
+        .with_paging_spec(PagingSpec::Exact(vec![1000, 1000, 500])),
+    )?;
+    // the streaming compressor doesn't know the count of numbers ahead of
+    // time, so only its header's size hint differs
+    let (_, chunks) = FileDecompressor::new(compressed.as_slice())?;
+    let (_, expected_chunks) = FileDecompressor::new(expected.as_slice())?;
+    assert_eq!(chunks, expected_chunks);
+    assert_eq!(simple_decompress::<u32>(&compressed)?, nums);
+    Ok(())
+  }
+
+  #[test]
+  fn test_streaming_index_and_flush() -> PcoResult<()> {
+    let mut compressor = StreamingCompressor::new(
+      FileCompressor::default(),
+      &ChunkConfig::default(),
+      300,
+      Vec::new(),
+    )?;
+    compressor.extend_from_slice(&(0..700_i64).collect::<Vec<_>>())?;
+    compressor.flush_chunk()?;
+    assert_eq!(compressor.n_buffered(), 0);
+    compressor.push(700)?;
+    let compressed = compressor.finish_with_index()?;
+
+    let (file_decompressor, src) = FileDecompressor::new(compressed.as_slice())?;
+    let index = file_decompressor.read_index(src)?.unwrap();
+    assert_eq!(
+      index.chunks.iter().map(|chunk| chunk.n).collect::<Vec<_>>(),
+      vec![300, 300, 100, 1]
+    );
+    assert_eq!(
+      file_decompressor.simple_decompress::<i64>(src)?,
+      (0..701).collect::<Vec<_>>()
+    );
+    Ok(())
+  }
+
+  struct FailingWriter {
+    n_bytes_left: usize,
+  }
+
+  impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+      if buf.len() > self.n_bytes_left {
+        return Err(std::io::Error::other("out of space"));
+      }
+      self.n_bytes_left -= buf.len();
+      Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn test_streaming_poisoned_by_failed_write() -> PcoResult<()> {
+    // leave room for the header but not the first chunk
+    let header_size = FileCompressor::default().write_header(Vec::new())?.len();
+    let mut compressor = StreamingCompressor::new(
+      FileCompressor::default(),
+      &ChunkConfig::default(),
+      100,
+      FailingWriter {
+        n_bytes_left: header_size,
+      },
+    )?;
+    compressor.extend_from_slice(&(0..99_i64).collect::<Vec<_>>())?;
+    assert!(compressor.push(99).is_err());
+    assert!(compressor.push(100).is_err());
+    assert!(compressor.flush_chunk().is_err());
+    assert!(compressor.finish().is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_streaming_edge_cases() -> PcoResult<()> {
+    let config = ChunkConfig::default();
+    let compressed = StreamingCompressor::<f32, _>::new(
+      FileCompressor::default(),
+      &config,
+      10,
+      Vec::new(),
+    )?
+    .finish()?;
+    assert_eq!(
+      simple_decompress::<f32>(&compressed)?,
+      Vec::<f32>::new()
+    );
+
+    for chunk_n in [0, MAX_ENTRIES + 1] {
+      let res = StreamingCompressor::<f32, _>::new(
+        FileCompressor::default(),
+        &config,
+        chunk_n,
+        Vec::new(),
+      );
+      assert!(matches!(
+        res.err().unwrap().kind,
+        ErrorKind::InvalidArgument
+      ));
+    }
+    let res = StreamingCompressor::<f32, _>::new(
+      FileCompressor::default().with_nullable(true),
+      &config,
+      10,
+      Vec::new(),
+    );
+    assert!(res.is_err());
+    Ok(())
+  }
+}
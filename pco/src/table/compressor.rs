@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::counting_writer::CountingWriter;
+use crate::data_types::{Number, NumberType};
+use crate::errors::{PcoError, PcoResult};
+use crate::table::constants::*;
+use crate::table::ColumnSchema;
+use crate::{wrapped, ChunkConfig, PagingSpec};
+
+/// Top-level entry point for compressing pco tables.
+///
+/// A table file should contain a header, followed by any number of row
+/// groups, followed by a footer.
+/// Each row group contains one chunk per column, in the schema's order.
+#[derive(Clone, Debug)]
+pub struct TableCompressor {
+  columns: Vec<ColumnSchema>,
+  inner: wrapped::FileCompressor,
+  // byte position in the file of everything written so far
+  offset: u64,
+  row_group_ns: Vec<usize>,
+  // for each row group, the byte position of each column chunk
+  column_offsets: Vec<Vec<u64>>,
+}
+
+impl TableCompressor {
+  /// Creates a compressor for a table with the given columns.
+  ///
+  /// Will return an error if there are no columns or if any column names are
+  /// repeated or too long.
+  pub fn new(columns: Vec<ColumnSchema>) -> PcoResult<Self> {
+    if columns.is_empty() {
+      return Err(PcoError::invalid_argument(
+        "table must have at least one column",
+      ));
+    }
+
+    let mut names = HashSet::new();
+    for column in &columns {
+      if column.name.len() > MAX_COLUMN_NAME_LEN {
+        return Err(PcoError::invalid_argument(format!(
+          "column name of {} bytes exceeds the max of {}",
+          column.name.len(),
+          MAX_COLUMN_NAME_LEN,
+        )));
+      }
+      if !names.insert(column.name.as_str()) {
+        return Err(PcoError::invalid_argument(format!(
+          "column name {} is repeated",
+          column.name,
+        )));
+      }
+    }
+
+    Ok(Self {
+      columns,
+      inner: wrapped::FileCompressor::default(),
+      offset: 0,
+      row_group_ns: Vec::new(),
+      column_offsets: Vec::new(),
+    })
+  }
+
+  pub fn columns(&self) -> &[ColumnSchema] {
+    &self.columns
+  }
+
+  fn counting<W: Write>(
+    &mut self,
+    dst: W,
+    f: impl FnOnce(CountingWriter<W>) -> PcoResult<CountingWriter<W>>,
+  ) -> PcoResult<W> {
+    let counting_dst = f(CountingWriter {
+      inner: dst,
+      count: 0,
+    })?;
+    self.offset += counting_dst.count;
+    Ok(counting_dst.inner)
+  }
+
+  /// Writes the header, including the table's schema, to the destination.
+  ///
+  /// Will return an error if the provided `Write` errors.
+  pub fn write_header<W: Write>(&mut self, dst: W) -> PcoResult<W> {
+    let mut bytes = Vec::new();
+    bytes.extend(MAGIC_TABLE_HEADER);
+    bytes.push(CURRENT_TABLE_VERSION);
+    let mut bytes = self.inner.write_header(bytes)?;
+    bytes.extend((self.columns.len() as u32).to_le_bytes());
+    for column in &self.columns {
+      bytes.push(column.number_type as u8);
+      bytes.extend((column.name.len() as u16).to_le_bytes());
+      bytes.extend(column.name.as_bytes());
+    }
+
+    self.counting(dst, |mut dst| {
+      dst.write_all(&bytes)?;
+      Ok(dst)
+    })
+  }
+
+  /// Creates a `RowGroupCompressor` to which each column's numbers for the
+  /// next row group can be added.
+  pub fn row_group_compressor(&self) -> RowGroupCompressor {
+    RowGroupCompressor {
+      column_types: self
+        .columns
+        .iter()
+        .map(|column| column.number_type)
+        .collect(),
+      inner: self.inner.clone(),
+      n: None,
+      chunk_compressors: Vec::new(),
+    }
+  }
+
+  /// Writes a row group to the destination.
+  ///
+  /// Will return an error if the row group is missing any columns or if the
+  /// provided `Write` errors.
+  pub fn write_row_group<W: Write>(
+    &mut self,
+    row_group: RowGroupCompressor,
+    dst: W,
+  ) -> PcoResult<W> {
+    let n_columns = self.columns.len();
+    let n_added = row_group.chunk_compressors.len();
+    let n = match row_group.n {
+      Some(n) if n_added == n_columns => n,
+      _ => {
+        return Err(PcoError::invalid_argument(format!(
+          "row group has {} of the table's {} columns",
+          n_added, n_columns,
+        )))
+      }
+    };
+
+    let mut dst = dst;
+    let mut column_offsets = Vec::with_capacity(n_columns);
+    for cc in &row_group.chunk_compressors {
+      column_offsets.push(self.offset);
+      dst = self.counting(dst, |dst| {
+        let dst = cc.write_chunk_meta(dst)?;
+        cc.write_page(0, dst)
+      })?;
+    }
+    self.row_group_ns.push(n);
+    self.column_offsets.push(column_offsets);
+    Ok(dst)
+  }
+
+  /// Writes the footer, which locates every column chunk, to the
+  /// destination.
+  ///
+  /// Will return an error if the provided `Write` errors.
+  pub fn write_footer<W: Write>(&mut self, mut dst: W) -> PcoResult<W> {
+    let n_row_groups = self.row_group_ns.len();
+    let mut bytes = Vec::with_capacity(
+      n_row_groups * (ROW_GROUP_N_SIZE + self.columns.len() * COLUMN_OFFSET_SIZE)
+        + TABLE_TRAILER_SIZE,
+    );
+    for (&n, column_offsets) in self.row_group_ns.iter().zip(&self.column_offsets) {
+      bytes.extend((n as u32).to_le_bytes());
+      for offset in column_offsets {
+        bytes.extend(offset.to_le_bytes());
+      }
+    }
+    bytes.extend((n_row_groups as u32).to_le_bytes());
+    bytes.extend(MAGIC_TABLE_TRAILER);
+    dst.write_all(&bytes)?;
+    self.offset += bytes.len() as u64;
+    Ok(dst)
+  }
+}
+
+/// Holds the compressed columns of a single row group until it is written by
+/// [`TableCompressor::write_row_group`].
+///
+/// Columns must be added in the table schema's order, and each must have the
+/// same count of rows.
+#[derive(Clone, Debug)]
+pub struct RowGroupCompressor {
+  column_types: Vec<NumberType>,
+  inner: wrapped::FileCompressor,
+  n: Option<usize>,
+  chunk_compressors: Vec<wrapped::ChunkCompressor>,
+}
+
+impl RowGroupCompressor {
+  /// Compresses the numbers of the next column in the row group.
+  ///
+  /// Will return an error if the numbers' type doesn't match the column's,
+  /// if the count of numbers differs from that of previous columns, if the
+  /// row group is empty, or if the compressor config is invalid.
+  ///
+  /// Each column chunk is written as a single page, so the config's
+  /// `PagingSpec` is ignored.
+  pub fn add_column<T: Number>(&mut self, nums: &[T], config: &ChunkConfig) -> PcoResult<()> {
+    let col_idx = self.chunk_compressors.len();
+    let number_type = match self.column_types.get(col_idx) {
+      Some(&number_type) => number_type,
+      None => {
+        return Err(PcoError::invalid_argument(format!(
+          "row group already has all {} columns",
+          col_idx,
+        )))
+      }
+    };
+    if T::NUMBER_TYPE_BYTE != number_type as u8 {
+      return Err(PcoError::invalid_argument(format!(
+        "column {} has type {:?} but numbers of type byte {} were provided",
+        col_idx,
+        number_type,
+        T::NUMBER_TYPE_BYTE,
+      )));
+    }
+    if nums.is_empty() {
+      return Err(PcoError::invalid_argument(
+        "cannot compress an empty row group",
+      ));
+    }
+    if let Some(n) = self.n {
+      if nums.len() != n {
+        return Err(PcoError::invalid_argument(format!(
+          "column {} has {} rows but previous columns have {}",
+          col_idx,
+          nums.len(),
+          n,
+        )));
+      }
+    }
+
+    let mut config = config.clone();
+    config.paging_spec = PagingSpec::Exact(vec![nums.len()]);
+    let cc = self.inner.chunk_compressor(nums, &config)?;
+    self.chunk_compressors.push(cc);
+    self.n = Some(nums.len());
+    Ok(())
+  }
+
+  /// Returns the count of rows in the row group, if any columns have been
+  /// added.
+  pub fn n(&self) -> Option<usize> {
+    self.n
+  }
+}
@@ -0,0 +1,10 @@
+// ascii for pcot
+pub const MAGIC_TABLE_HEADER: [u8; 4] = [112, 99, 111, 116];
+pub const MAGIC_TABLE_TRAILER: [u8; 4] = [112, 99, 111, 116];
+pub const CURRENT_TABLE_VERSION: u8 = 1;
+pub const MAX_COLUMN_NAME_LEN: usize = u16::MAX as usize;
+// n_row_groups and the magic trailer
+pub const TABLE_TRAILER_SIZE: usize = 8;
+// the row group's n, then an offset per column
+pub const ROW_GROUP_N_SIZE: usize = 4;
+pub const COLUMN_OFFSET_SIZE: usize = 8;
@@ -0,0 +1,293 @@
+use crate::constants::MAX_ENTRIES;
+use crate::data_types::{Number, NumberType};
+use crate::errors::{PcoError, PcoResult};
+use crate::macros::match_number_enum;
+use crate::table::constants::*;
+use crate::table::{ColumnSchema, DynNumbers};
+use crate::wrapped;
+
+fn read_bytes<'a>(src: &mut &'a [u8], n: usize) -> PcoResult<&'a [u8]> {
+  if src.len() < n {
+    return Err(PcoError::insufficient_data(format!(
+      "table header requires {} more bytes but only {} remain",
+      n,
+      src.len(),
+    )));
+  }
+  let (bytes, rest) = src.split_at(n);
+  *src = rest;
+  Ok(bytes)
+}
+
+/// Top-level entry point for decompressing pco tables.
+///
+/// Since column chunks are located via the table's footer, this requires the
+/// entire file to be in memory.
+/// Each column or column chunk can then be decompressed independently of
+/// the others.
+#[derive(Clone, Debug)]
+pub struct TableDecompressor {
+  columns: Vec<ColumnSchema>,
+  inner: wrapped::FileDecompressor,
+  row_group_ns: Vec<usize>,
+  // for each row group, the byte position of each column chunk
+  column_offsets: Vec<Vec<u64>>,
+}
+
+impl TableDecompressor {
+  /// Reads the header and footer of a complete table file.
+  ///
+  /// Will return an error if any version incompatibilities, corruptions, or
+  /// insufficient data are found.
+  pub fn new(src: &[u8]) -> PcoResult<Self> {
+    let mut header = src;
+    let magic = read_bytes(&mut header, MAGIC_TABLE_HEADER.len())?;
+    if magic != MAGIC_TABLE_HEADER {
+      return Err(PcoError::corruption(format!(
+        "magic header does not match {:?}; instead found {:?}",
+        MAGIC_TABLE_HEADER, magic,
+      )));
+    }
+    let version = read_bytes(&mut header, 1)?[0];
+    if version > CURRENT_TABLE_VERSION {
+      return Err(PcoError::compatibility(format!(
+        "table version {} is greater than the max supported version {}; \
+        consider upgrading pco",
+        version, CURRENT_TABLE_VERSION,
+      )));
+    }
+    let (inner, rest) = wrapped::FileDecompressor::new(header)?;
+    header = rest;
+
+    let n_columns = u32::from_le_bytes(read_bytes(&mut header, 4)?.try_into().unwrap()) as usize;
+    let mut columns = Vec::with_capacity(n_columns.min(header.len()));
+    for _ in 0..n_columns {
+      let number_type_byte = read_bytes(&mut header, 1)?[0];
+      let number_type = match NumberType::from_descriminant(number_type_byte) {
+        Some(number_type) => number_type,
+        None => {
+          return Err(PcoError::compatibility(format!(
+            "unrecognized number type byte {}",
+            number_type_byte,
+          )))
+        }
+      };
+      let name_len = u16::from_le_bytes(read_bytes(&mut header, 2)?.try_into().unwrap()) as usize;
+      let name = match String::from_utf8(read_bytes(&mut header, name_len)?.to_vec()) {
+        Ok(name) => name,
+        Err(_) => {
+          return Err(PcoError::corruption(
+            "column name is not valid UTF-8",
+          ))
+        }
+      };
+      columns.push(ColumnSchema { name, number_type });
+    }
+    let header_size = src.len() - header.len();
+
+    if src.len() < header_size + TABLE_TRAILER_SIZE {
+      return Err(PcoError::insufficient_data(
+        "table is missing its footer",
+      ));
+    }
+    let trailer = &src[src.len() - TABLE_TRAILER_SIZE..];
+    if trailer[4..] != MAGIC_TABLE_TRAILER {
+      return Err(PcoError::corruption(format!(
+        "magic trailer does not match {:?}; instead found {:?}",
+        MAGIC_TABLE_TRAILER,
+        &trailer[4..],
+      )));
+    }
+    let n_row_groups = u32::from_le_bytes(trailer[..4].try_into().unwrap()) as usize;
+    // the counts are untrusted, so we check for overflow
+    let row_group_entry_size = n_columns
+      .checked_mul(COLUMN_OFFSET_SIZE)
+      .and_then(|offsets_size| offsets_size.checked_add(ROW_GROUP_N_SIZE));
+    let footer_size = row_group_entry_size
+      .and_then(|entry_size| n_row_groups.checked_mul(entry_size))
+      .and_then(|entries_size| entries_size.checked_add(TABLE_TRAILER_SIZE));
+    let (row_group_entry_size, footer_size) = match (row_group_entry_size, footer_size) {
+      (Some(entry_size), Some(footer_size)) if footer_size <= src.len() - header_size => {
+        (entry_size, footer_size)
+      }
+      _ => {
+        return Err(PcoError::insufficient_data(format!(
+          "footer of {} row groups is longer than the {} bytes remaining after the header",
+          n_row_groups,
+          src.len() - header_size,
+        )))
+      }
+    };
+    let footer_start = src.len() - footer_size;
+
+    let mut row_group_ns = Vec::with_capacity(n_row_groups);
+    let mut column_offsets = Vec::with_capacity(n_row_groups);
+    for entry in
+      src[footer_start..src.len() - TABLE_TRAILER_SIZE].chunks_exact(row_group_entry_size)
+    {
+      let n = u32::from_le_bytes(entry[..ROW_GROUP_N_SIZE].try_into().unwrap()) as usize;
+      if n > MAX_ENTRIES {
+        return Err(PcoError::corruption(format!(
+          "row group of {} rows exceeds the max of {}",
+          n, MAX_ENTRIES,
+        )));
+      }
+      let mut offsets = Vec::with_capacity(n_columns);
+      for offset_bytes in entry[ROW_GROUP_N_SIZE..].chunks_exact(COLUMN_OFFSET_SIZE) {
+        let offset = u64::from_le_bytes(offset_bytes.try_into().unwrap());
+        if offset < header_size as u64 || offset >= footer_start as u64 {
+          return Err(PcoError::corruption(format!(
+            "column chunk offset {} is outside the table's data",
+            offset,
+          )));
+        }
+        offsets.push(offset);
+      }
+      row_group_ns.push(n);
+      column_offsets.push(offsets);
+    }
+
+    Ok(Self {
+      columns,
+      inner,
+      row_group_ns,
+      column_offsets,
+    })
+  }
+
+  pub fn columns(&self) -> &[ColumnSchema] {
+    &self.columns
+  }
+
+  /// Returns the index of the column with the given name, if it exists.
+  pub fn column_idx(&self, name: &str) -> Option<usize> {
+    self.columns.iter().position(|column| column.name == name)
+  }
+
+  /// Returns the count of rows in each row group.
+  pub fn row_group_ns(&self) -> Vec<usize> {
+    self.row_group_ns.clone()
+  }
+
+  /// Returns the total count of rows in the table.
+  pub fn n_rows(&self) -> usize {
+    self.row_group_ns.iter().sum()
+  }
+
+  fn check_column<T: Number>(&self, col_idx: usize) -> PcoResult<()> {
+    let column = match self.columns.get(col_idx) {
+      Some(column) => column,
+      None => {
+        return Err(PcoError::invalid_argument(format!(
+          "column index {} is out of bounds for a table of {} columns",
+          col_idx,
+          self.columns.len(),
+        )))
+      }
+    };
+    if T::NUMBER_TYPE_BYTE != column.number_type as u8 {
+      return Err(PcoError::invalid_argument(format!(
+        "column {} has type {:?} but type byte {} was requested",
+        column.name,
+        column.number_type,
+        T::NUMBER_TYPE_BYTE,
+      )));
+    }
+    Ok(())
+  }
+
+  fn decompress_column_chunk_extend<T: Number>(
+    &self,
+    src: &[u8],
+    row_group_idx: usize,
+    col_idx: usize,
+    dst: &mut Vec<T>,
+  ) -> PcoResult<()> {
+    let n = self.row_group_ns[row_group_idx];
+    let offset = self.column_offsets[row_group_idx][col_idx] as usize;
+    if offset >= src.len() {
+      return Err(PcoError::insufficient_data(format!(
+        "column chunk starts at byte {} but only {} bytes were provided",
+        offset,
+        src.len(),
+      )));
+    }
+
+    let (cd, rest) = self.inner.chunk_decompressor::<T, _>(&src[offset..])?;
+    let mut pd = cd.page_decompressor(rest, n)?;
+    let start = dst.len();
+    dst.resize(start + n, T::default());
+    pd.decompress(&mut dst[start..])?;
+    Ok(())
+  }
+
+  /// Decompresses a single column of a single row group.
+  ///
+  /// `src` must be the entire table file.
+  /// Will return an error if the type doesn't match the column's or if
+  /// corruptions or insufficient data are found.
+  pub fn decompress_column_chunk<T: Number>(
+    &self,
+    src: &[u8],
+    row_group_idx: usize,
+    col_idx: usize,
+  ) -> PcoResult<Vec<T>> {
+    self.check_column::<T>(col_idx)?;
+    if row_group_idx >= self.row_group_ns.len() {
+      return Err(PcoError::invalid_argument(format!(
+        "row group index {} is out of bounds for a table of {} row groups",
+        row_group_idx,
+        self.row_group_ns.len(),
+      )));
+    }
+
+    let mut res = Vec::with_capacity(self.row_group_ns[row_group_idx]);
+    self.decompress_column_chunk_extend(src, row_group_idx, col_idx, &mut res)?;
+    Ok(res)
+  }
+
+  /// Decompresses every row of a single column.
+  ///
+  /// `src` must be the entire table file.
+  /// Will return an error if the type doesn't match the column's or if
+  /// corruptions or insufficient data are found.
+  pub fn decompress_column<T: Number>(&self, src: &[u8], col_idx: usize) -> PcoResult<Vec<T>> {
+    self.check_column::<T>(col_idx)?;
+
+    let mut res = Vec::with_capacity(self.n_rows());
+    for row_group_idx in 0..self.row_group_ns.len() {
+      self.decompress_column_chunk_extend(src, row_group_idx, col_idx, &mut res)?;
+    }
+    Ok(res)
+  }
+
+  /// Decompresses every row of the named columns, returning them in the
+  /// order requested.
+  ///
+  /// `src` must be the entire table file.
+  /// Columns that aren't requested are skipped without being decoded.
+  /// Will return an error if any column doesn't exist or if corruptions or
+  /// insufficient data are found.
+  pub fn project(&self, src: &[u8], names: &[&str]) -> PcoResult<Vec<DynNumbers>> {
+    let mut res = Vec::with_capacity(names.len());
+    for &name in names {
+      let col_idx = match self.column_idx(name) {
+        Some(col_idx) => col_idx,
+        None => {
+          return Err(PcoError::invalid_argument(format!(
+            "table has no column named {}",
+            name,
+          )))
+        }
+      };
+      let nums = match_number_enum!(
+        self.columns[col_idx].number_type,
+        NumberType<T> => {
+          DynNumbers::new(self.decompress_column::<T>(src, col_idx)?).unwrap()
+        }
+      );
+      res.push(nums);
+    }
+    Ok(res)
+  }
+}
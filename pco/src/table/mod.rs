@@ -0,0 +1,195 @@
+//! A container for tables of named, typed numerical columns.
+//!
+//! A table file stores its rows in row groups.
+//! Each row group holds one wrapped pco chunk per column, all with the same
+//! count of rows, and a footer records where each of these column chunks
+//! begins.
+//! This lets readers project a subset of columns without decoding the rest.
+//!
+//! Example:
+//! ```
+//! use pco::ChunkConfig;
+//! use pco::data_types::NumberType;
+//! use pco::table::{ColumnSchema, TableCompressor, TableDecompressor};
+//! # use pco::errors::PcoResult;
+//!
+//! # fn main() -> PcoResult<()> {
+//! let mut tc = TableCompressor::new(vec![
+//!   ColumnSchema::new("timestamp", NumberType::I64),
+//!   ColumnSchema::new("temperature", NumberType::F32),
+//! ])?;
+//! let config = ChunkConfig::default();
+//! let mut compressed = tc.write_header(Vec::new())?;
+//! let mut row_group = tc.row_group_compressor();
+//! row_group.add_column(&[1700000000_i64, 1700000060], &config)?;
+//! row_group.add_column(&[21.5_f32, 21.7], &config)?;
+//! compressed = tc.write_row_group(row_group, compressed)?;
+//! compressed = tc.write_footer(compressed)?;
+//!
+//! let td = TableDecompressor::new(&compressed)?;
+//! let temperatures = td.decompress_column::<f32>(&compressed, 1)?;
+//! assert_eq!(temperatures, vec![21.5, 21.7]);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use compressor::{RowGroupCompressor, TableCompressor};
+pub use decompressor::TableDecompressor;
+
+use crate::data_types::{Number, NumberType};
+use crate::macros::define_number_enum;
+
+mod compressor;
+mod constants;
+mod decompressor;
+
+/// The name and data type of a column in a table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnSchema {
+  pub name: String,
+  pub number_type: NumberType,
+}
+
+impl ColumnSchema {
+  pub fn new(name: impl Into<String>, number_type: NumberType) -> Self {
+    Self {
+      name: name.into(),
+      number_type,
+    }
+  }
+}
+
+define_number_enum!(
+  #[derive(Clone, Debug, PartialEq)]
+  pub DynNumbers(Vec)
+);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::errors::{ErrorKind, PcoResult};
+  use crate::table::constants::*;
+  use crate::ChunkConfig;
+
+  fn compress_table(row_group_ns: &[usize]) -> PcoResult<Vec<u8>> {
+    let mut tc = TableCompressor::new(vec![
+      ColumnSchema::new("a", NumberType::I64),
+      ColumnSchema::new("b", NumberType::F32),
+      ColumnSchema::new("c", NumberType::U8),
+    ])?;
+    let config = ChunkConfig::default();
+    let mut compressed = tc.write_header(Vec::new())?;
+    let mut start = 0;
+    for &n in row_group_ns {
+      let end = start + n;
+      let a = (start..end).map(|i| i as i64 * 1000).collect::<Vec<_>>();
+      let b = (start..end).map(|i| i as f32 / 4.0).collect::<Vec<_>>();
+      let c = (start..end).map(|i| (i % 3) as u8).collect::<Vec<_>>();
+      let mut row_group = tc.row_group_compressor();
+      row_group.add_column(&a, &config)?;
+      row_group.add_column(&b, &config)?;
+      row_group.add_column(&c, &config)?;
+      compressed = tc.write_row_group(row_group, compressed)?;
+      start = end;
+    }
+    tc.write_footer(compressed)
+  }
+
+  #[test]
+  fn test_recovery_and_projection() -> PcoResult<()> {
+    let compressed = compress_table(&[300, 1, 700])?;
+    let td = TableDecompressor::new(&compressed)?;
+    assert_eq!(td.columns().len(), 3);
+    assert_eq!(td.row_group_ns(), vec![300, 1, 700]);
+    assert_eq!(td.n_rows(), 1001);
+
+    let a = td.decompress_column::<i64>(&compressed, 0)?;
+    assert_eq!(
+      a,
+      (0..1001).map(|i| i * 1000).collect::<Vec<_>>()
+    );
+    let c_group = td.decompress_column_chunk::<u8>(&compressed, 2, 2)?;
+    assert_eq!(
+      c_group,
+      (301..1001).map(|i| (i % 3) as u8).collect::<Vec<_>>()
+    );
+
+    let projected = td.project(&compressed, &["c", "b"])?;
+    assert_eq!(projected.len(), 2);
+    let b = projected[1].downcast_ref::<f32>().unwrap();
+    assert_eq!(
+      b,
+      &(0..1001).map(|i| i as f32 / 4.0).collect::<Vec<_>>()
+    );
+    assert!(td.project(&compressed, &["d"]).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_empty_table() -> PcoResult<()> {
+    let compressed = compress_table(&[])?;
+    let td = TableDecompressor::new(&compressed)?;
+    assert_eq!(td.n_rows(), 0);
+    assert_eq!(
+      td.decompress_column::<f32>(&compressed, 1)?,
+      Vec::<f32>::new()
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_invalid_usage() -> PcoResult<()> {
+    let duplicate_names = TableCompressor::new(vec![
+      ColumnSchema::new("a", NumberType::I64),
+      ColumnSchema::new("a", NumberType::F32),
+    ]);
+    assert!(matches!(
+      duplicate_names.unwrap_err().kind,
+      ErrorKind::InvalidArgument
+    ));
+
+    let mut tc = TableCompressor::new(vec![
+      ColumnSchema::new("a", NumberType::I64),
+      ColumnSchema::new("b", NumberType::F32),
+    ])?;
+    let config = ChunkConfig::default();
+    let mut row_group = tc.row_group_compressor();
+    // wrong type
+    assert!(row_group.add_column(&[1_i32], &config).is_err());
+    row_group.add_column(&[1_i64, 2], &config)?;
+    // wrong length
+    assert!(row_group.add_column(&[1.0_f32], &config).is_err());
+    // missing column
+    assert!(tc.write_row_group(row_group, Vec::new()).is_err());
+
+    let compressed = compress_table(&[10])?;
+    let td = TableDecompressor::new(&compressed)?;
+    assert!(td.decompress_column::<i32>(&compressed, 0).is_err());
+    assert!(TableDecompressor::new(&compressed[..compressed.len() - 1]).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_corrupt_footer() -> PcoResult<()> {
+    let compressed = compress_table(&[10])?;
+    let trailer_start = compressed.len() - TABLE_TRAILER_SIZE;
+    let entry_start = trailer_start - ROW_GROUP_N_SIZE - 3 * COLUMN_OFFSET_SIZE;
+
+    // a row group claiming more rows than any chunk can have
+    let mut corrupt = compressed.clone();
+    corrupt[entry_start..entry_start + ROW_GROUP_N_SIZE].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+      TableDecompressor::new(&corrupt).unwrap_err().kind,
+      ErrorKind::Corruption
+    ));
+
+    // a row group count too large for the file
+    let mut corrupt = compressed.clone();
+    corrupt[trailer_start..trailer_start + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+      TableDecompressor::new(&corrupt).unwrap_err().kind,
+      ErrorKind::InsufficientData
+    ));
+    Ok(())
+  }
+}
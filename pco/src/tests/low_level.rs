@@ -7,7 +7,7 @@ use better_io::{BetterBufRead, BetterBufReader};
 use crate::chunk_config::{ChunkConfig, DeltaSpec};
 use crate::errors::PcoResult;
 use crate::wrapped::{FileCompressor, FileDecompressor, PageDecompressor};
-use crate::{PagingSpec, FULL_BATCH_N};
+use crate::{NumberSum, PagingSpec, FULL_BATCH_N};
 
 struct Chunk {
   nums: Vec<u32>,
@@ -127,3 +127,212 @@ fn test_low_level_wrapped() -> PcoResult<()> {
     },
   ])
 }
+
+#[test]
+fn test_low_level_stats() -> PcoResult<()> {
+  let nums = (0..1000_u32).map(|x| (x * 7) % 500).collect::<Vec<_>>();
+  let config = ChunkConfig {
+    paging_spec: PagingSpec::Exact(vec![50, 950]),
+    store_stats: true,
+    ..Default::default()
+  };
+  let chunks = [Chunk {
+    nums: nums.clone(),
+    config: config.clone(),
+  }];
+  let mut compressed = Vec::new();
+  test_wrapped_compress(&chunks, &mut compressed)?;
+  test_wrapped_decompress(&chunks, compressed.as_slice())?;
+
+  let (fd, src) = FileDecompressor::new(compressed.as_slice())?;
+  let (cd, src) = fd.chunk_decompressor::<u32, _>(src)?;
+  let chunk_stats = cd.meta().stats.unwrap();
+  assert_eq!(chunk_stats.min::<u32>(), Some(0));
+  assert_eq!(chunk_stats.max::<u32>(), Some(499));
+  assert_eq!(chunk_stats.count, 1000);
+  assert_eq!(chunk_stats.min::<u64>(), None);
+
+  let pd = cd.page_decompressor(src, 50)?;
+  let page_stats = pd.stats().unwrap();
+  assert_eq!(page_stats.min::<u32>(), Some(0));
+  assert_eq!(page_stats.max::<u32>(), Some(343));
+  assert_eq!(page_stats.count, 50);
+
+  // stats are not stored by default
+  let mut compressed = Vec::new();
+  test_wrapped_compress(
+    &[Chunk {
+      nums,
+      config: ChunkConfig::default(),
+    }],
+    &mut compressed,
+  )?;
+  let (fd, src) = FileDecompressor::new(compressed.as_slice())?;
+  let (cd, _) = fd.chunk_decompressor::<u32, _>(src)?;
+  assert!(cd.meta().stats.is_none());
+  Ok(())
+}
+
+#[test]
+fn test_low_level_shared_meta() -> PcoResult<()> {
+  let fc = FileCompressor::default();
+  let config = ChunkConfig::default();
+  let sample = (0..2000_u32).map(|x| (x * 7) % 500).collect::<Vec<_>>();
+  let shared_meta = fc.chunk_compressor(&sample, &config)?.meta().clone();
+
+  let mut compressed = fc.write_header(Vec::new())?;
+  compressed = fc.write_shared_meta(&shared_meta, compressed)?;
+  let chunks = (0..3_u32)
+    .map(|i| {
+      (0..300 + i)
+        .map(|x| ((x + 100 * i) * 7) % 500)
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+  for chunk in &chunks {
+    // only the pages are written for each chunk
+    let cc = fc.chunk_compressor_with_shared_meta(chunk, &shared_meta, &config)?;
+    assert_eq!(cc.meta(), &shared_meta);
+    compressed = cc.write_page(0, compressed)?;
+  }
+
+  let (mut fd, src) = FileDecompressor::new(compressed.as_slice())?;
+  let mut src = fd.register_shared_meta::<u32, _>(7, src)?;
+  for chunk in &chunks {
+    let cd = fd.shared_chunk_decompressor::<u32>(7)?;
+    let mut pd = cd.page_decompressor(src, chunk.len())?;
+    let mut page_nums = vec![0; chunk.len()];
+    pd.decompress(&mut page_nums)?;
+    assert_eq!(&page_nums, chunk);
+    src = pd.into_src();
+  }
+  assert!(src.is_empty());
+
+  // misuse
+  assert!(fd.shared_chunk_decompressor::<u32>(8).is_err());
+  assert!(fd.shared_chunk_decompressor::<u64>(7).is_err());
+  let outliers = vec![1_u32 << 20; 10];
+  assert!(fc
+    .chunk_compressor_with_shared_meta(&outliers, &shared_meta, &config)
+    .is_err());
+  let stats_config = ChunkConfig::default().with_store_stats(true);
+  let stats_meta = fc.chunk_compressor(&sample, &stats_config)?.meta().clone();
+  assert!(fc.write_shared_meta(&stats_meta, Vec::new()).is_err());
+  Ok(())
+}
+
+#[test]
+fn test_low_level_aggregate() -> PcoResult<()> {
+  let nums = (0..1000_i32)
+    .map(|x| (x * 7) % 500 - 100)
+    .collect::<Vec<_>>();
+  let expected_sum = nums.iter().map(|&x| x as i128).sum::<i128>();
+  for store_stats in [false, true] {
+    let config = ChunkConfig {
+      paging_spec: PagingSpec::Exact(vec![300, 700]),
+      delta_spec: DeltaSpec::None,
+      store_stats,
+      ..Default::default()
+    };
+    let fc = FileCompressor::default();
+    let cc = fc.chunk_compressor(&nums, &config)?;
+    let mut compressed = fc.write_header(Vec::new())?;
+    compressed = cc.write_chunk_meta(compressed)?;
+    compressed = cc.write_page(0, compressed)?;
+    compressed = cc.write_page(1, compressed)?;
+
+    let (fd, src) = FileDecompressor::new(compressed.as_slice())?;
+    let (cd, src) = fd.chunk_decompressor::<i32, _>(src)?;
+    let (lower, upper) = cd.meta().bounds::<i32>().unwrap();
+    assert!(lower <= -100 && upper >= 399);
+    let bin_bounds = cd.meta().bin_bounds::<i32>().unwrap();
+    assert!(nums.iter().all(|x| bin_bounds
+      .iter()
+      .any(|bounds| bounds.lower <= *x && *x <= bounds.upper)));
+
+    let mut pd = cd.page_decompressor(src, 300)?;
+    let aggs = pd.aggregate()?;
+    assert_eq!(aggs.count, 300);
+    assert_eq!(aggs.min, nums[..300].iter().min().copied());
+    assert_eq!(aggs.max, nums[..300].iter().max().copied());
+
+    // aggregating the rest of a partially read page
+    let mut pd = cd.page_decompressor(pd.into_src(), 700)?;
+    let mut first_batch = vec![0; FULL_BATCH_N];
+    pd.decompress(&mut first_batch)?;
+    let rest_aggs = pd.aggregate()?;
+    assert_eq!(rest_aggs.count, 700 - FULL_BATCH_N);
+    assert_eq!(
+      rest_aggs.max,
+      nums[300 + FULL_BATCH_N..].iter().max().copied()
+    );
+    let first_batch_sum = first_batch.iter().map(|&x| x as i128).sum::<i128>();
+    assert_eq!(
+      aggs.sum.to_f64() + rest_aggs.sum.to_f64() + first_batch_sum as f64,
+      expected_sum as f64
+    );
+    assert!(matches!(aggs.sum, NumberSum::Int(_)));
+    assert!(pd.into_src().is_empty());
+  }
+  Ok(())
+}
+
+#[test]
+fn test_low_level_filter_range() -> PcoResult<()> {
+  // two clusters of numbers, with a gap in between
+  let nums = (0..1000_i64)
+    .map(|x| {
+      if x % 3 == 0 {
+        10_000 + (x * 7) % 100
+      } else {
+        (x * 13) % 200 - 100
+      }
+    })
+    .collect::<Vec<_>>();
+  let predicates = [
+    (-50, 50),
+    (10_050, 20_000),
+    (1_000, 5_000),
+    (-1_000, 1_000_000),
+    (7, 7),
+    (i64::MIN, i64::MAX),
+  ];
+  for (delta_spec, store_stats) in [
+    (DeltaSpec::None, false),
+    (DeltaSpec::None, true),
+    (DeltaSpec::TryConsecutive(1), false),
+  ] {
+    let config = ChunkConfig {
+      delta_spec,
+      store_stats,
+      ..Default::default()
+    };
+    let fc = FileCompressor::default();
+    let cc = fc.chunk_compressor(&nums, &config)?;
+    let mut header = fc.write_header(Vec::new())?;
+    header = cc.write_chunk_meta(header)?;
+    let page = cc.write_page(0, Vec::new())?;
+
+    let (fd, src) = FileDecompressor::new(header.as_slice())?;
+    let (cd, _) = fd.chunk_decompressor::<i64, _>(src)?;
+    for (lo, hi) in predicates {
+      let mut pd = cd.page_decompressor(page.as_slice(), nums.len())?;
+      let indices = pd.filter_range(lo, hi)?;
+      let expected = (0..nums.len() as u32)
+        .filter(|&i| lo <= nums[i as usize] && nums[i as usize] < hi)
+        .collect::<Vec<_>>();
+      assert_eq!(indices, expected, "lo={} hi={}", lo, hi);
+      assert!(pd.into_src().is_empty());
+    }
+
+    // filtering the rest of a partially read page
+    let mut pd = cd.page_decompressor(page.as_slice(), nums.len())?;
+    pd.skip(FULL_BATCH_N)?;
+    let indices = pd.filter_range(-1_000, 1_000_000)?;
+    assert_eq!(
+      indices,
+      (FULL_BATCH_N as u32..nums.len() as u32).collect::<Vec<_>>()
+    );
+  }
+  Ok(())
+}
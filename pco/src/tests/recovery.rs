@@ -1,14 +1,16 @@
-use half::f16;
+use half::{bf16, f16};
 use rand::Rng;
 use rand_xoshiro::rand_core::SeedableRng;
 
 use crate::chunk_config::{ChunkConfig, DeltaSpec};
 use crate::constants::Bitlen;
-use crate::data_types::Number;
+use crate::data_types::{Latent, Number};
 use crate::errors::PcoResult;
-use crate::metadata::{ChunkMeta, DeltaEncoding, DynLatent, Mode};
-use crate::standalone::{simple_compress, simple_decompress, FileCompressor};
-use crate::ModeSpec;
+use crate::metadata::{ChunkMeta, DeltaEncoding, DynLatent, ErrorBound, Mode};
+use crate::standalone::{
+  simple_compress, simple_decompress, FileCompressor, FileDecompressor, MaybeChunkDecompressor,
+};
+use crate::{LossySpec, ModeSpec};
 
 fn compress_w_meta<T: Number>(nums: &[T], config: &ChunkConfig) -> PcoResult<(Vec<u8>, ChunkMeta)> {
   let mut compressed = Vec::new();
@@ -78,6 +80,7 @@ fn test_edge_cases() -> PcoResult<()> {
   assert_recovers(&Vec::<u32>::new(), 6, "empty u32 - 6")?;
   assert_recovers(&Vec::<u32>::new(), 0, "empty u32 - 0")?;
   assert_recovers(&Vec::<u16>::new(), 6, "empty u16 - 6")?;
+  assert_recovers(&Vec::<u8>::new(), 6, "empty u8 - 6")?;
   assert_recovers(
     &[
       f16::NEG_INFINITY,
@@ -118,6 +121,11 @@ fn test_sparse() -> PcoResult<()> {
   assert_recovers(&v, 1, "sparse")
 }
 
+#[test]
+fn test_u8_codec() -> PcoResult<()> {
+  assert_recovers(&[0_u8, u8::MAX, 2, 3, 4, 5], 1, "u8s")
+}
+
 #[test]
 fn test_u16_codec() -> PcoResult<()> {
   assert_recovers(&[0_u16, u16::MAX, 2, 3, 4, 5], 1, "u16s")
@@ -133,6 +141,16 @@ fn test_u64_codec() -> PcoResult<()> {
   assert_recovers(&[0_u64, u64::MAX, 3, 4, 5], 1, "u64s")
 }
 
+#[test]
+fn test_u128_codec() -> PcoResult<()> {
+  assert_recovers(&[0_u128, u128::MAX, 3, 4, 5], 1, "u128s")
+}
+
+#[test]
+fn test_i8_codec() -> PcoResult<()> {
+  assert_recovers(&[0_i8, -1, i8::MAX, i8::MIN, 7], 1, "i8s")
+}
+
 #[test]
 fn test_i16_codec() -> PcoResult<()> {
   assert_recovers(
@@ -160,6 +178,33 @@ fn test_i64_codec() -> PcoResult<()> {
   )
 }
 
+#[test]
+fn test_i128_codec() -> PcoResult<()> {
+  assert_recovers(
+    &[0_i128, -1, i128::MAX, i128::MIN, 7],
+    1,
+    "i128s",
+  )
+}
+
+#[test]
+fn test_i128_decimals() -> PcoResult<()> {
+  // e.g. Decimal128 mantissas for prices with 2 decimal places
+  let mut rng = rand_xoshiro::Xoroshiro128PlusPlus::seed_from_u64(0);
+  let mut nums = Vec::new();
+  for _ in 0..1000 {
+    nums.push(rng.gen_range(-1_000_000_i128..1_000_000) * 10_i128.pow(20));
+  }
+  let (compressed, meta) = compress_w_meta(&nums, &ChunkConfig::default())?;
+  assert_eq!(
+    meta.mode,
+    Mode::IntMult(DynLatent::U128(10_u128.pow(20)))
+  );
+  // about 21 bits per number
+  assert!(compressed.len() < 1000 * 3);
+  assert_recovers(&nums, 3, "i128 decimals")
+}
+
 #[test]
 fn test_f16_codec() -> PcoResult<()> {
   assert_recovers(
@@ -178,6 +223,24 @@ fn test_f16_codec() -> PcoResult<()> {
   )
 }
 
+#[test]
+fn test_bf16_codec() -> PcoResult<()> {
+  assert_recovers(
+    &[
+      bf16::MAX,
+      bf16::MIN,
+      bf16::NAN,
+      bf16::NEG_INFINITY,
+      bf16::INFINITY,
+      bf16::NEG_ZERO,
+      bf16::ZERO,
+      bf16::from_f32(77.7),
+    ],
+    1,
+    "bf16s",
+  )
+}
+
 #[test]
 fn test_f32_codec() -> PcoResult<()> {
   assert_recovers(
@@ -231,8 +294,8 @@ fn test_multi_chunk() -> PcoResult<()> {
   Ok(())
 }
 
-fn recover_with_alternating_nums(offset_bits: Bitlen, name: &str) -> PcoResult<()> {
-  let nums = [0_u64, 1 << (offset_bits - 1)].repeat(50);
+fn recover_with_alternating_nums<L: Latent>(offset_bits: Bitlen, name: &str) -> PcoResult<()> {
+  let nums = [L::ZERO, L::ONE << (offset_bits - 1)].repeat(50);
   let (compressed, meta) = compress_w_meta(
     &nums,
     &ChunkConfig {
@@ -244,7 +307,7 @@ fn recover_with_alternating_nums(offset_bits: Bitlen, name: &str) -> PcoResult<(
   assert!(meta.per_latent_var.delta.is_none());
   assert!(meta.per_latent_var.secondary.is_none());
   let latent_var = &meta.per_latent_var.primary;
-  let bins = latent_var.bins.downcast_ref::<u64>().unwrap();
+  let bins = latent_var.bins.downcast_ref::<L>().unwrap();
   assert_eq!(bins.len(), 1);
   assert_eq!(bins[0].offset_bits, offset_bits);
   let decompressed = simple_decompress(&compressed)?;
@@ -253,17 +316,32 @@ fn recover_with_alternating_nums(offset_bits: Bitlen, name: &str) -> PcoResult<(
 
 #[test]
 fn test_56_bit_offsets() -> PcoResult<()> {
-  recover_with_alternating_nums(56, "56 bit offsets")
+  recover_with_alternating_nums::<u64>(56, "56 bit offsets")
 }
 
 #[test]
 fn test_57_bit_offsets() -> PcoResult<()> {
-  recover_with_alternating_nums(57, "57 bit offsets")
+  recover_with_alternating_nums::<u64>(57, "57 bit offsets")
 }
 
 #[test]
 fn test_64_bit_offsets() -> PcoResult<()> {
-  recover_with_alternating_nums(64, "64 bit offsets")
+  recover_with_alternating_nums::<u64>(64, "64 bit offsets")
+}
+
+#[test]
+fn test_113_bit_offsets() -> PcoResult<()> {
+  recover_with_alternating_nums::<u128>(113, "113 bit offsets")
+}
+
+#[test]
+fn test_114_bit_offsets() -> PcoResult<()> {
+  recover_with_alternating_nums::<u128>(114, "114 bit offsets")
+}
+
+#[test]
+fn test_128_bit_offsets() -> PcoResult<()> {
+  recover_with_alternating_nums::<u128>(128, "128 bit offsets")
 }
 
 #[test]
@@ -344,6 +422,36 @@ fn test_f16_mult() -> PcoResult<()> {
   assert_recovers(&nums, 1, "f16 mult mode")
 }
 
+#[test]
+fn test_bf16_float_modes() -> PcoResult<()> {
+  let mut rng = rand_xoshiro::Xoroshiro128PlusPlus::seed_from_u64(0);
+
+  let nums = (0..1000)
+    .map(|_| bf16::from_f32(rng.gen_range(-100..100) as f32 * 0.25))
+    .collect::<Vec<_>>();
+  let config = ChunkConfig {
+    mode_spec: ModeSpec::TryFloatMult(0.25),
+    ..Default::default()
+  };
+  let (_, meta) = compress_w_meta(&nums, &config)?;
+  assert_eq!(
+    meta.mode,
+    Mode::float_mult(bf16::from_f32(0.25))
+  );
+  assert_recovers(&nums, 3, "bf16 float mult")?;
+
+  let nums = (0..1000)
+    .map(|_| bf16::from_bits(rng.gen::<u16>() & !0b111))
+    .collect::<Vec<_>>();
+  let config = ChunkConfig {
+    mode_spec: ModeSpec::TryFloatQuant(3),
+    ..Default::default()
+  };
+  let (_, meta) = compress_w_meta(&nums, &config)?;
+  assert_eq!(meta.mode, Mode::FloatQuant(3));
+  assert_recovers(&nums, 3, "bf16 float quant")
+}
+
 #[test]
 fn test_trivial_first_latent_var() -> PcoResult<()> {
   let mut nums = Vec::new();
@@ -376,3 +484,174 @@ fn test_lookback_delta_encoding() -> PcoResult<()> {
   assert_nums_eq(&decompressed, &nums, "trivial_first_latent")?;
   Ok(())
 }
+
+#[test]
+fn test_u8_int_mult_and_lookback() -> PcoResult<()> {
+  let mut nums = Vec::new();
+  for i in 0..1000_u32 {
+    nums.push(((i * 7) % 31 * 8) as u8);
+  }
+  let (compressed, meta) = compress_w_meta(
+    &nums,
+    &ChunkConfig::default().with_delta_spec(DeltaSpec::None),
+  )?;
+  assert_eq!(meta.mode, Mode::IntMult(DynLatent::U8(8_u8)));
+  let decompressed = simple_decompress(&compressed)?;
+  assert_nums_eq(&decompressed, &nums, "u8 int mult")?;
+
+  let nums = nums.iter().map(|&x| x as i8).collect::<Vec<_>>();
+  let (compressed, meta) = compress_w_meta(
+    &nums,
+    &ChunkConfig::default().with_delta_spec(DeltaSpec::TryLookback),
+  )?;
+  assert!(matches!(
+    meta.delta_encoding,
+    DeltaEncoding::Lookback(_)
+  ));
+  let decompressed = simple_decompress(&compressed)?;
+  assert_nums_eq(&decompressed, &nums, "i8 lookback")?;
+  Ok(())
+}
+
+fn assert_within_max_abs_error<T: Number + Into<f64>>(
+  nums: &[T],
+  max_abs_error: f64,
+) -> PcoResult<Vec<u8>> {
+  let config = ChunkConfig::default().with_lossy_spec(LossySpec::MaxAbsError(max_abs_error));
+  let (compressed, meta) = compress_w_meta(nums, &config)?;
+  assert_eq!(
+    meta.error_bound,
+    Some(ErrorBound::MaxAbsError(max_abs_error))
+  );
+
+  let decompressed = simple_decompress::<T>(&compressed)?;
+  assert_eq!(decompressed.len(), nums.len());
+  for (&x, &y) in decompressed.iter().zip(nums) {
+    let (x, y) = (x.into(), y.into());
+    if y.is_finite() {
+      assert!(
+        (x - y).abs() <= max_abs_error,
+        "{} vs {}",
+        x,
+        y
+      );
+    } else {
+      assert_eq!(x.to_bits(), y.to_bits());
+    }
+  }
+  Ok(compressed)
+}
+
+#[test]
+fn test_max_abs_error() -> PcoResult<()> {
+  let mut rng = rand_xoshiro::Xoroshiro128PlusPlus::seed_from_u64(0);
+  let mut nums = (0..2000)
+    .map(|i| (i as f64 * 0.01).sin() * 30.0 + rng.gen_range(-1.0..1.0))
+    .collect::<Vec<_>>();
+  nums.extend([f64::NAN, f64::INFINITY, -0.0, 1e300]);
+
+  let lossless_size = simple_compress(&nums, &ChunkConfig::default())?.len();
+  let compressed = assert_within_max_abs_error(&nums, 1e-4)?;
+  // about 20 bits per number instead of about 50
+  assert!(compressed.len() * 2 < lossless_size);
+
+  let nums_f32 = nums.iter().map(|&x| x as f32).collect::<Vec<_>>();
+  assert_within_max_abs_error(&nums_f32, 1e-4)?;
+  assert_within_max_abs_error(&nums_f32, 0.3)?;
+
+  // the bound is stored in the chunk metadata
+  let (compressed, _) = compress_w_meta(
+    &nums_f32,
+    &ChunkConfig::default().with_lossy_spec(LossySpec::MaxAbsError(0.5)),
+  )?;
+  let (fd, src) = FileDecompressor::new(compressed.as_slice())?;
+  let cd = match fd.chunk_decompressor::<f32, _>(src)? {
+    MaybeChunkDecompressor::Some(cd) => cd,
+    MaybeChunkDecompressor::EndOfData(_) => panic!("expected a chunk"),
+  };
+  assert_eq!(
+    cd.meta()
+      .error_bound
+      .and_then(|bound| bound.max_abs_error()),
+    Some(0.5)
+  );
+  Ok(())
+}
+
+#[test]
+fn test_max_abs_error_invalid() {
+  for (max_abs_error, mode_spec) in [
+    (0.0, ModeSpec::Auto),
+    (f64::NAN, ModeSpec::Auto),
+    (1e-50, ModeSpec::Auto),
+    (0.1, ModeSpec::Classic),
+  ] {
+    let config = ChunkConfig::default()
+      .with_mode_spec(mode_spec)
+      .with_lossy_spec(LossySpec::MaxAbsError(max_abs_error));
+    assert!(simple_compress(&[1.0_f32, 2.0], &config).is_err());
+  }
+
+  let config = ChunkConfig::default().with_lossy_spec(LossySpec::MaxAbsError(0.5));
+  assert!(simple_compress(&[1_i32, 2], &config).is_err());
+}
+
+#[test]
+fn test_significant_bits() -> PcoResult<()> {
+  let mut rng = rand_xoshiro::Xoroshiro128PlusPlus::seed_from_u64(0);
+  let mut nums = (0..2000)
+    .map(|_| rng.gen_range(-1000.0..1000.0))
+    .collect::<Vec<f32>>();
+  nums.extend([f32::NAN, f32::NEG_INFINITY, f32::MAX, -0.0]);
+  let config = ChunkConfig::default().with_lossy_spec(LossySpec::SignificantBits(10));
+  let (compressed, meta) = compress_w_meta(&nums, &config)?;
+  assert_eq!(meta.mode, Mode::FloatQuant(13));
+  assert_eq!(
+    meta.error_bound,
+    Some(ErrorBound::SignificantBits(10))
+  );
+
+  let decompressed = simple_decompress::<f32>(&compressed)?;
+  for (&x, &y) in decompressed.iter().zip(&nums) {
+    if y.is_finite() {
+      assert!(
+        (x - y).abs() <= y.abs() / 2048.0,
+        "{} vs {}",
+        x,
+        y
+      );
+    } else {
+      assert_eq!(x.to_bits(), y.to_bits());
+    }
+  }
+  // f32::MAX gets rounded down instead of up to infinity
+  assert_eq!(
+    decompressed[2002],
+    f32::from_bits(0x7f7fe000)
+  );
+
+  // the rounding is fixed, so these results must never change
+  let nums = [
+    1.0 + 2.0_f32.powi(-11),
+    1.0 + 3.0 * 2.0_f32.powi(-11),
+    -1.7,
+    3.0e-40,
+  ];
+  let compressed = simple_compress(&nums, &config)?;
+  let decompressed = simple_decompress::<f32>(&compressed)?;
+  assert_eq!(
+    decompressed.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+    vec![0x3f800000, 0x3f804000, 0xbfd9a000, 0x00034000],
+  );
+
+  // f16 has only 10 mantissa bits, so this is lossless
+  let nums = (0..1000)
+    .map(|i| f16::from_f32(i as f32 * 0.37))
+    .collect::<Vec<_>>();
+  let (compressed, _) = compress_w_meta(&nums, &config)?;
+  assert_nums_eq(
+    &simple_decompress::<f16>(&compressed)?,
+    &nums,
+    "f16 significant bits",
+  )
+}
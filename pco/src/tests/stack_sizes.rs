@@ -19,8 +19,8 @@ fn test_stack_sizes() {
     mem::size_of::<LatentChunkCompressor<u64>>(),
     136
   );
-  assert_eq!(mem::size_of::<ChunkDecompressor<u64>>(), 168);
-  assert_eq!(mem::size_of::<ChunkCompressor>(), 624);
+  assert_eq!(mem::size_of::<ChunkDecompressor<u64>>(), 288);
+  assert_eq!(mem::size_of::<ChunkCompressor>(), 752);
 
   // decompression
   assert_eq!(
@@ -37,6 +37,6 @@ fn test_stack_sizes() {
   );
   assert_eq!(
     mem::size_of::<PageDecompressor<u64, &[u8]>>(),
-    256
+    400
   );
 }
@@ -1,10 +1,10 @@
 use crate::bit_writer::BitWriter;
-use crate::chunk_config::DeltaSpec;
+use crate::chunk_config::{DeltaSpec, LossySpec, ModeSpec};
 use crate::compression_intermediates::{BinCompressionInfo, PageInfoVar};
 use crate::compression_intermediates::{DissectedPage, PageInfo};
 use crate::constants::{
-  Bitlen, Weight, LIMITED_UNOPTIMIZED_BINS_LOG, MAX_COMPRESSION_LEVEL, MAX_DELTA_ENCODING_ORDER,
-  MAX_ENTRIES, OVERSHOOT_PADDING, PAGE_PADDING,
+  Bitlen, Weight, BITS_TO_ENCODE_SIGNIFICANT_BITS, LIMITED_UNOPTIMIZED_BINS_LOG,
+  MAX_COMPRESSION_LEVEL, MAX_DELTA_ENCODING_ORDER, MAX_ENTRIES, OVERSHOOT_PADDING, PAGE_PADDING,
 };
 use crate::data_types::SplitLatents;
 use crate::data_types::{Latent, LatentType, Number};
@@ -22,12 +22,12 @@ use crate::metadata::dyn_latents::DynLatents;
 use crate::metadata::page::PageMeta;
 use crate::metadata::page_latent_var::PageLatentVarMeta;
 use crate::metadata::per_latent_var::{LatentVarKey, PerLatentVar, PerLatentVarBuilder};
-use crate::metadata::{Bin, ChunkMeta, DeltaEncoding, Mode};
+use crate::metadata::{Bin, ChunkMeta, DeltaEncoding, Mode, Stats};
 use crate::wrapped::guarantee;
 use crate::{
-  ans, bin_optimization, bits, data_types, delta, ChunkConfig, PagingSpec, FULL_BATCH_N,
+  ans, bin_optimization, bits, delta, parallel, sampling, ChunkConfig, PagingSpec, FULL_BATCH_N,
 };
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::io::Write;
 
 // if it looks like the average page of size n will use k bits, hint that it
@@ -147,6 +147,29 @@ fn validate_config(config: &ChunkConfig) -> PcoResult<()> {
     }
   }
 
+  if let LossySpec::MaxAbsError(max_abs_error) = config.lossy_spec {
+    if !(max_abs_error.is_finite() && max_abs_error > 0.0) {
+      return Err(PcoError::invalid_argument(format!(
+        "max absolute error must be positive and finite (was {})",
+        max_abs_error,
+      )));
+    }
+  }
+  if let LossySpec::SignificantBits(significant_bits) = config.lossy_spec {
+    if significant_bits >= 1 << BITS_TO_ENCODE_SIGNIFICANT_BITS {
+      return Err(PcoError::invalid_argument(format!(
+        "significant bits may not exceed {} (was {})",
+        (1 << BITS_TO_ENCODE_SIGNIFICANT_BITS) - 1,
+        significant_bits,
+      )));
+    }
+  }
+  if config.lossy_spec != LossySpec::Lossless && config.mode_spec != ModeSpec::Auto {
+    return Err(PcoError::invalid_argument(
+      "lossy compression requires the Auto mode spec",
+    ));
+  }
+
   Ok(())
 }
 
@@ -243,6 +266,7 @@ fn delta_encode_and_build_page_infos(
     page_infos.push(PageInfo {
       page_n,
       per_latent_var,
+      stats: None,
     });
 
     start_idx = end_idx;
@@ -252,6 +276,20 @@ fn delta_encode_and_build_page_infos(
   (latents, page_infos)
 }
 
+fn unoptimized_bins_log_for_latent_var(key: LatentVarKey, unoptimized_bins_log: Bitlen) -> Bitlen {
+  match key {
+    // primary latents are generally the most important to compress, and
+    // delta latents typically have a small number of discrete values, so
+    // aren't slow to optimize anyway
+    LatentVarKey::Delta | LatentVarKey::Primary => unoptimized_bins_log,
+    // secondary latents should be compressed faster
+    LatentVarKey::Secondary => min(
+      unoptimized_bins_log,
+      LIMITED_UNOPTIMIZED_BINS_LOG,
+    ),
+  }
+}
+
 fn new_candidate_w_split_and_delta_encoding(
   latents: SplitLatents, // start out plain, gets delta encoded in place
   paging_spec: &PagingSpec,
@@ -267,23 +305,10 @@ fn new_candidate_w_split_and_delta_encoding(
     delta_encode_and_build_page_infos(delta_encoding, &n_per_page, latents);
 
   // training bins
-  let mut var_metas = PerLatentVarBuilder::default();
-  let mut latent_chunk_compressors = PerLatentVarBuilder::default();
-  let mut bin_countss = PerLatentVarBuilder::default();
-  for (key, latents) in latents.enumerated() {
-    let unoptimized_bins_log = match key {
-      // primary latents are generally the most important to compress, and
-      // delta latents typically have a small number of discrete values, so
-      // aren't slow to optimize anyway
-      LatentVarKey::Delta | LatentVarKey::Primary => unoptimized_bins_log,
-      // secondary latents should be compressed faster
-      LatentVarKey::Secondary => min(
-        unoptimized_bins_log,
-        LIMITED_UNOPTIMIZED_BINS_LOG,
-      ),
-    };
+  let trained = parallel::map_per_latent_var(latents, |key, latents| -> PcoResult<_> {
+    let unoptimized_bins_log = unoptimized_bins_log_for_latent_var(key, unoptimized_bins_log);
 
-    let (var_meta, lcc, bin_counts) = match_latent_enum!(
+    match_latent_enum!(
       latents,
       DynLatents<L>(latents) => {
         let contiguous_deltas = collect_contiguous_latents(&latents, &page_infos, key);
@@ -300,9 +325,16 @@ fn new_candidate_w_split_and_delta_encoding(
           bins: DynBins::new(bins).unwrap(),
           ans_size_log,
         };
-        (var_meta, lcc, bin_counts)
+        Ok((var_meta, lcc, bin_counts))
       }
-    );
+    )
+  });
+
+  let mut var_metas = PerLatentVarBuilder::default();
+  let mut latent_chunk_compressors = PerLatentVarBuilder::default();
+  let mut bin_countss = PerLatentVarBuilder::default();
+  for (key, trained) in trained.enumerated() {
+    let (var_meta, lcc, bin_counts) = trained?;
     var_metas.set(key, var_meta);
     latent_chunk_compressors.set(key, lcc);
     bin_countss.set(key, bin_counts);
@@ -316,6 +348,8 @@ fn new_candidate_w_split_and_delta_encoding(
     mode,
     delta_encoding,
     per_latent_var: var_metas,
+    stats: None,
+    error_bound: None,
   };
   let chunk_compressor = ChunkCompressor {
     meta,
@@ -439,6 +473,23 @@ fn choose_unoptimized_bins_log(compression_level: usize, n: usize) -> Bitlen {
   }
 }
 
+fn delta_encoding_for_spec(
+  delta_spec: DeltaSpec,
+  primary_latents: &DynLatents,
+  unoptimized_bins_log: Bitlen,
+) -> PcoResult<DeltaEncoding> {
+  let delta_encoding = match delta_spec {
+    DeltaSpec::Auto => choose_delta_encoding(primary_latents, unoptimized_bins_log)?,
+    DeltaSpec::None | DeltaSpec::TryConsecutive(0) => DeltaEncoding::None,
+    DeltaSpec::TryConsecutive(order) => DeltaEncoding::Consecutive(DeltaConsecutiveConfig {
+      order,
+      secondary_uses_delta: false,
+    }),
+    DeltaSpec::TryLookback => new_lookback_delta_encoding(primary_latents.len()),
+  };
+  Ok(delta_encoding)
+}
+
 // We pull this stuff out of `new` because it only depends on the latent type
 // and we don't need a specialization for each full number type.
 // Returns a chunk compressor and the counts (per latent var) of numbers in
@@ -450,15 +501,11 @@ fn new_candidate_w_split(
 ) -> PcoResult<(ChunkCompressor, PerLatentVar<Vec<Weight>>)> {
   let n = latents.primary.len();
   let unoptimized_bins_log = choose_unoptimized_bins_log(config.compression_level, n);
-  let delta_encoding = match config.delta_spec {
-    DeltaSpec::Auto => choose_delta_encoding(&latents.primary, unoptimized_bins_log)?,
-    DeltaSpec::None | DeltaSpec::TryConsecutive(0) => DeltaEncoding::None,
-    DeltaSpec::TryConsecutive(order) => DeltaEncoding::Consecutive(DeltaConsecutiveConfig {
-      order,
-      secondary_uses_delta: false,
-    }),
-    DeltaSpec::TryLookback => new_lookback_delta_encoding(n),
-  };
+  let delta_encoding = delta_encoding_for_spec(
+    config.delta_spec,
+    &latents.primary,
+    unoptimized_bins_log,
+  )?;
 
   new_candidate_w_split_and_delta_encoding(
     latents,
@@ -513,6 +560,26 @@ fn fallback_chunk_compressor(
   })
 }
 
+// Lossy specs round the numbers, so the fallback and stats must be derived
+// from the rounded numbers to make decompression deterministic.
+fn rounded_latents<T: Number>(
+  mode: Mode,
+  latents: &SplitLatents,
+  config: &ChunkConfig,
+) -> Option<Vec<T::L>> {
+  if config.lossy_spec == LossySpec::Lossless {
+    return None;
+  }
+
+  let mut primary = latents.primary.downcast_ref::<T::L>().unwrap().clone();
+  T::join_latents(
+    mode,
+    &mut primary,
+    latents.secondary.as_ref(),
+  );
+  Some(primary)
+}
+
 // Should this take nums as a slice of slices instead of having a config.paging_spec?
 pub(crate) fn new<T: Number>(nums: &[T], config: &ChunkConfig) -> PcoResult<ChunkCompressor> {
   validate_config(config)?;
@@ -528,20 +595,354 @@ pub(crate) fn new<T: Number>(nums: &[T], config: &ChunkConfig) -> PcoResult<Chun
     ));
   }
 
+  let rounded_latents = rounded_latents::<T>(mode, &latents, config);
+  let classic_latents = || match &rounded_latents {
+    Some(rounded_latents) => rounded_latents.clone(),
+    None => nums.iter().map(|&x| x.to_latent_ordered()).collect(),
+  };
+
   let (candidate, bin_counts) = new_candidate_w_split(mode, latents, config)?;
-  if candidate.should_fallback(
+  let mut chunk_compressor = if candidate.should_fallback(
     LatentType::new::<T::L>().unwrap(),
     n,
     bin_counts,
   ) {
-    let split_latents = data_types::split_latents_classic(nums);
-    return fallback_chunk_compressor(split_latents, config);
+    let split_latents = SplitLatents {
+      primary: DynLatents::new(classic_latents()).unwrap(),
+      secondary: None,
+    };
+    fallback_chunk_compressor(split_latents, config)?
+  } else {
+    candidate
+  };
+
+  chunk_compressor.finish_meta(config, classic_latents);
+  Ok(chunk_compressor)
+}
+
+// Returns the count of latents in each bin, or None if any latent is outside
+// every bin.
+fn bin_counts_if_fit<L: Latent>(latents: &[L], bins: &[Bin<L>]) -> Option<Vec<Weight>> {
+  // like the compression table, we assign each latent to the bin with the
+  // greatest lower bound not exceeding it
+  let mut sorted_idxs = (0..bins.len()).collect::<Vec<_>>();
+  sorted_idxs.sort_unstable_by_key(|&idx| bins[idx].lower);
+  let sorted_lowers = sorted_idxs
+    .iter()
+    .map(|&idx| bins[idx].lower)
+    .collect::<Vec<_>>();
+
+  let mut counts = vec![0; bins.len()];
+  for &latent in latents {
+    let sorted_idx = sorted_lowers
+      .partition_point(|&lower| lower <= latent)
+      .checked_sub(1)?;
+    let bin_idx = sorted_idxs[sorted_idx];
+    let bin = &bins[bin_idx];
+    if bin.offset_bits < L::BITS && (latent - bin.lower) >> bin.offset_bits > L::ZERO {
+      return None;
+    }
+    counts[bin_idx] += 1;
+  }
+  Some(counts)
+}
+
+fn compression_infos_from_bins<L: Latent>(bins: &[Bin<L>]) -> Vec<BinCompressionInfo<L>> {
+  bins
+    .iter()
+    .enumerate()
+    .map(|(symbol, bin)| {
+      let upper = if bin.offset_bits < L::BITS {
+        bin.lower + ((L::ONE << bin.offset_bits) - L::ONE)
+      } else {
+        L::MAX
+      };
+      BinCompressionInfo {
+        weight: bin.weight,
+        lower: bin.lower,
+        upper,
+        offset_bits: bin.offset_bits,
+        symbol: symbol as ans::Symbol,
+      }
+    })
+    .collect()
+}
+
+// Like new_candidate_w_split_and_delta_encoding, but with the bins of an
+// existing chunk meta instead of training new ones.
+// Returns None if any latent doesn't fit into those bins.
+fn candidate_from_meta(
+  meta: &ChunkMeta,
+  latents: SplitLatents,
+  paging_spec: &PagingSpec,
+) -> PcoResult<Option<(ChunkCompressor, PerLatentVar<Vec<Weight>>)>> {
+  let chunk_n = latents.primary.len();
+  let n_per_page = paging_spec.n_per_page(chunk_n)?;
+  let (latents, page_infos) =
+    delta_encode_and_build_page_infos(meta.delta_encoding, &n_per_page, latents);
+
+  let mut latent_chunk_compressors = PerLatentVarBuilder::default();
+  let mut bin_countss = PerLatentVarBuilder::default();
+  for (key, (latents, var_meta)) in latents.zip_exact(meta.per_latent_var.as_ref()).enumerated() {
+    let fitted = match_latent_enum!(
+      latents,
+      DynLatents<L>(latents) => {
+        let bins = var_meta.bins.downcast_ref::<L>().unwrap();
+        let contiguous_latents = collect_contiguous_latents(&latents, &page_infos, key);
+        match bin_counts_if_fit(&contiguous_latents, bins) {
+          Some(bin_counts) => {
+            let trained = TrainedBins {
+              infos: compression_infos_from_bins(bins),
+              ans_size_log: var_meta.ans_size_log,
+              counts: bin_counts.clone(),
+            };
+            let lcc = DynLatentChunkCompressor::new(
+              LatentChunkCompressor::new(trained, bins, latents)?
+            ).unwrap();
+            Some((lcc, bin_counts))
+          }
+          None => None,
+        }
+      }
+    );
+    match fitted {
+      Some((lcc, bin_counts)) => {
+        latent_chunk_compressors.set(key, lcc);
+        bin_countss.set(key, bin_counts);
+      }
+      None => return Ok(None),
+    }
+  }
+
+  let chunk_compressor = ChunkCompressor {
+    meta: ChunkMeta {
+      mode: meta.mode,
+      delta_encoding: meta.delta_encoding,
+      per_latent_var: meta.per_latent_var.clone(),
+      stats: None,
+      error_bound: None,
+    },
+    latent_chunk_compressors: latent_chunk_compressors.into(),
+    page_infos,
+  };
+  Ok(Some((chunk_compressor, bin_countss.into())))
+}
+
+fn est_chunk_size(meta: &ChunkMeta, n_pages: usize, body_bits: f64) -> usize {
+  meta.exact_size() + n_pages * meta.exact_page_meta_size() + (body_bits / 8.0).ceil() as usize
+}
+
+// Estimates the mode, delta encoding, and size of the chunk `new` would
+// create, without dissecting or writing any pages.
+// Bins are only trained on a sample of each latent variable.
+pub(crate) fn estimate<T: Number>(
+  nums: &[T],
+  config: &ChunkConfig,
+) -> PcoResult<(Mode, DeltaEncoding, usize)> {
+  validate_config(config)?;
+  let n = nums.len();
+  validate_chunk_size(n)?;
+
+  let (mode, latents) = T::choose_mode_and_split_latents(nums, config)?;
+  if !T::mode_is_valid(mode) {
+    return Err(PcoError::invalid_argument(format!(
+      "invalid mode for {} data type: {:?}",
+      std::any::type_name::<T>(),
+      mode
+    )));
   }
+  let unoptimized_bins_log = choose_unoptimized_bins_log(config.compression_level, n);
+  let delta_encoding = delta_encoding_for_spec(
+    config.delta_spec,
+    &latents.primary,
+    unoptimized_bins_log,
+  )?;
+  let n_per_page = config.paging_spec.n_per_page(n)?;
+  let n_pages = n_per_page.len();
+  let (latents, page_infos) =
+    delta_encode_and_build_page_infos(delta_encoding, &n_per_page, latents);
 
-  Ok(candidate)
+  let mut body_bits = 0.0;
+  let mut var_metas = PerLatentVarBuilder::default();
+  for (key, latents) in latents.enumerated() {
+    let unoptimized_bins_log = unoptimized_bins_log_for_latent_var(key, unoptimized_bins_log);
+    let var_meta = match_latent_enum!(
+      latents,
+      DynLatents<L>(latents) => {
+        let contiguous_latents = collect_contiguous_latents(&latents, &page_infos, key);
+        let n_latents = contiguous_latents.len();
+        let sample = sampling::choose_sample(&contiguous_latents, |&latent| Some(latent))
+          .unwrap_or(contiguous_latents);
+        let count_scale = n_latents as f64 / max(sample.len(), 1) as f64;
+        let trained = train_infos(sample, unoptimized_bins_log)?;
+        body_bits += bin_optimization::est_latent_bits(&trained.infos, &trained.counts, count_scale);
+        ChunkLatentVarMeta {
+          bins: DynBins::new(bins_from_compression_infos(&trained.infos)).unwrap(),
+          ans_size_log: trained.ans_size_log,
+        }
+      }
+    );
+    var_metas.set(key, var_meta);
+  }
+
+  // only the size of the stats matters here
+  let stats = if config.store_stats {
+    Some(Stats::compute::<T::L>(&[]))
+  } else {
+    None
+  };
+  let error_bound = config.lossy_spec.error_bound();
+  let meta = ChunkMeta {
+    mode,
+    delta_encoding,
+    per_latent_var: var_metas.into(),
+    stats,
+    error_bound,
+  };
+  let size = est_chunk_size(&meta, n_pages, body_bits);
+
+  let mut baseline_meta = guarantee::baseline_chunk_meta::<T::L>();
+  baseline_meta.stats = stats;
+  baseline_meta.error_bound = error_bound;
+  let baseline_size = est_chunk_size(
+    &baseline_meta,
+    n_pages,
+    (n * T::L::BITS as usize) as f64,
+  );
+  if size > baseline_size {
+    Ok((
+      Mode::Classic,
+      DeltaEncoding::None,
+      baseline_size,
+    ))
+  } else {
+    Ok((mode, delta_encoding, size))
+  }
+}
+
+fn validate_meta<T: Number>(meta: &ChunkMeta) -> PcoResult<()> {
+  meta.validate_latent_types(LatentType::new::<T::L>().unwrap())?;
+  if !T::mode_is_valid(meta.mode) {
+    return Err(PcoError::invalid_argument(format!(
+      "invalid mode for {} data type: {:?}",
+      std::any::type_name::<T>(),
+      meta.mode
+    )));
+  }
+  meta.validate_delta_encoding()
+}
+
+// Returns the candidate that uses the meta's mode, delta encoding, and bins,
+// along with its bin counts and classic latents. Returns None if the numbers
+// don't fit the meta.
+#[allow(clippy::type_complexity)]
+fn candidate_from_meta_w_nums<T: Number>(
+  nums: &[T],
+  meta: &ChunkMeta,
+  config: &ChunkConfig,
+) -> PcoResult<
+  Option<(
+    ChunkCompressor,
+    PerLatentVar<Vec<Weight>>,
+    Vec<T::L>,
+  )>,
+> {
+  validate_config(config)?;
+  validate_chunk_size(nums.len())?;
+  validate_meta::<T>(meta)?;
+
+  // Lossy compression chooses its mode from the error bound, so the meta
+  // only applies if it was made with the same one.
+  if meta.error_bound != config.lossy_spec.error_bound() {
+    return Ok(None);
+  }
+  let latents = if config.lossy_spec == LossySpec::Lossless {
+    T::split_latents_with_mode(nums, meta.mode)
+  } else {
+    let (mode, latents) = T::choose_mode_and_split_latents(nums, config)?;
+    if mode != meta.mode {
+      return Ok(None);
+    }
+    latents
+  };
+
+  let classic_latents = match rounded_latents::<T>(meta.mode, &latents, config) {
+    Some(rounded_latents) => rounded_latents,
+    None => nums.iter().map(|&x| x.to_latent_ordered()).collect(),
+  };
+  let res = candidate_from_meta(meta, latents, &config.paging_spec)?
+    .map(|(candidate, bin_counts)| (candidate, bin_counts, classic_latents));
+  Ok(res)
+}
+
+pub(crate) fn new_reusing_meta<T: Number>(
+  nums: &[T],
+  meta: &ChunkMeta,
+  config: &ChunkConfig,
+) -> PcoResult<ChunkCompressor> {
+  let (mut chunk_compressor, classic_latents) =
+    match candidate_from_meta_w_nums(nums, meta, config)? {
+      Some((candidate, bin_counts, classic_latents)) => {
+        let latent_type = LatentType::new::<T::L>().unwrap();
+        if candidate.should_fallback(latent_type, nums.len(), bin_counts) {
+          return new(nums, config);
+        }
+        (candidate, classic_latents)
+      }
+      // the numbers no longer fit the meta, so we refit from scratch
+      None => return new(nums, config),
+    };
+
+  chunk_compressor.finish_meta(config, || classic_latents);
+  Ok(chunk_compressor)
+}
+
+pub(crate) fn new_with_shared_meta<T: Number>(
+  nums: &[T],
+  meta: &ChunkMeta,
+  config: &ChunkConfig,
+) -> PcoResult<ChunkCompressor> {
+  if meta.stats.is_some() {
+    return Err(PcoError::invalid_argument(
+      "shared chunk metadata may not store stats",
+    ));
+  }
+
+  match candidate_from_meta_w_nums(nums, meta, config)? {
+    Some((mut chunk_compressor, _, _)) => {
+      chunk_compressor.meta.error_bound = meta.error_bound;
+      Ok(chunk_compressor)
+    }
+    None => Err(PcoError::invalid_argument(
+      "numbers do not fit the shared chunk metadata",
+    )),
+  }
 }
 
 impl ChunkCompressor {
+  // Stats and error bounds cost the same for the candidate and fallback, so
+  // we only add them after deciding.
+  fn finish_meta<L: Latent, F: FnOnce() -> Vec<L>>(
+    &mut self,
+    config: &ChunkConfig,
+    classic_latents: F,
+  ) {
+    self.meta.error_bound = config.lossy_spec.error_bound();
+    if config.store_stats {
+      self.set_stats(&classic_latents());
+    }
+  }
+
+  fn set_stats<L: Latent>(&mut self, latents: &[L]) {
+    self.meta.stats = Some(Stats::compute(latents));
+    let mut start = 0;
+    for page_info in &mut self.page_infos {
+      let end = start + page_info.page_n;
+      page_info.stats = Some(Stats::compute(&latents[start..end]));
+      start = end;
+    }
+  }
+
   fn should_fallback(
     &self,
     latent_type: LatentType,
@@ -622,15 +1023,18 @@ impl ChunkCompressor {
 
     let page_info = &page_infos[page_idx];
 
-    let per_latent_var = latent_chunk_compressors.as_ref().map(|key, lcc| {
-      let range = page_info.range_for_latent_var(key);
-      match_latent_enum!(
-        lcc,
-        DynLatentChunkCompressor<L>(inner) => {
-          inner.dissect_page(range)
-        }
-      )
-    });
+    let per_latent_var = parallel::map_per_latent_var(
+      latent_chunk_compressors.as_ref(),
+      |key, lcc| {
+        let range = page_info.range_for_latent_var(key);
+        match_latent_enum!(
+          lcc,
+          DynLatentChunkCompressor<L>(inner) => {
+            inner.dissect_page(range)
+          }
+        )
+      },
+    );
 
     Ok(DissectedPage {
       page_n: page_info.page_n,
@@ -736,7 +1140,10 @@ impl ChunkCompressor {
         }
       });
 
-    let page_meta = PageMeta { per_latent_var };
+    let page_meta = PageMeta {
+      per_latent_var,
+      stats: page_info.stats,
+    };
     let ans_size_logs = ans_default_state_and_size_log.map(|_, (_, size_log)| size_log);
     unsafe { page_meta.write_to(ans_size_logs, &mut writer) };
 
@@ -746,11 +1153,32 @@ impl ChunkCompressor {
     writer.flush()?;
     Ok(writer.into_inner())
   }
+
+  /// Writes every page, in order, to the destination.
+  ///
+  /// Will return an error if the provided `Write` errors.
+  ///
+  /// With the `parallel` feature, pages are encoded concurrently, but the
+  /// bytes written are the same as calling
+  /// [`write_page`][Self::write_page] on each page in order.
+  pub fn write_pages<W: Write>(&self, mut dst: W) -> PcoResult<W> {
+    let page_idxs = (0..self.page_infos.len()).collect::<Vec<_>>();
+    let pages = parallel::map(page_idxs, |page_idx| {
+      self.write_page(page_idx, Vec::new())
+    });
+    for page in pages {
+      dst.write_all(&page?)?;
+    }
+    Ok(dst)
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::errors::ErrorKind;
+  use crate::standalone;
+  use crate::wrapped::FileCompressor;
 
   #[test]
   fn test_choose_delta_sample() {
@@ -784,4 +1212,105 @@ mod tests {
       vec![0, 1, 3, 4, 6, 7]
     );
   }
+
+  #[test]
+  fn test_write_pages() -> PcoResult<()> {
+    let nums = (0..2000_i64).map(|x| x * 3 + x % 7).collect::<Vec<_>>();
+    let config = ChunkConfig::default().with_paging_spec(PagingSpec::Exact(vec![700, 1300]));
+    let cc = FileCompressor::default().chunk_compressor(&nums, &config)?;
+    let mut expected = cc.write_page(0, Vec::new())?;
+    expected = cc.write_page(1, expected)?;
+    assert_eq!(cc.write_pages(Vec::new())?, expected);
+    Ok(())
+  }
+
+  fn compress_reusing_meta<T: Number>(
+    nums: &[T],
+    meta: &ChunkMeta,
+    config: &ChunkConfig,
+  ) -> PcoResult<ChunkMeta> {
+    let fc = standalone::FileCompressor::default();
+    let cc = fc.chunk_compressor_reusing_meta(nums, meta, config)?;
+    let mut compressed = fc.write_header(Vec::new())?;
+    compressed = cc.write_chunk(compressed)?;
+    compressed = fc.write_footer(compressed)?;
+    assert_eq!(
+      standalone::simple_decompress::<T>(&compressed)?,
+      nums
+    );
+    Ok(cc.meta().clone())
+  }
+
+  #[test]
+  fn test_reuse_meta() -> PcoResult<()> {
+    let config = ChunkConfig::default();
+    let day0 = (0..3000_i64)
+      .map(|i| 1000 * (i * 7 + i % 11))
+      .collect::<Vec<_>>();
+    let meta = FileCompressor::default()
+      .chunk_compressor(&day0, &config)?
+      .meta()
+      .clone();
+    assert!(matches!(meta.mode, Mode::IntMult(_)));
+
+    // similar numbers fit into the same bins
+    let day1 = (0..2000_i64)
+      .map(|i| 1000 * (i * 7 + (i + 3) % 11))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      compress_reusing_meta(&day1, &meta, &config)?,
+      meta
+    );
+
+    // numbers outside the bins get refit
+    let outliers = (0..2000_i64).map(|i| i * i).collect::<Vec<_>>();
+    assert_ne!(
+      compress_reusing_meta(&outliers, &meta, &config)?,
+      meta
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_reuse_meta_floats() -> PcoResult<()> {
+    let nums = (0..1000).map(|i| i as f32 * 0.1).collect::<Vec<_>>();
+    let config = ChunkConfig::default().with_store_stats(true);
+    let meta = FileCompressor::default()
+      .chunk_compressor(&nums, &config)?
+      .meta()
+      .clone();
+    let reused = compress_reusing_meta(&nums[..500], &meta, &config)?;
+    assert_eq!(reused.mode, meta.mode);
+    assert_eq!(reused.per_latent_var, meta.per_latent_var);
+    assert_ne!(reused.stats, meta.stats);
+
+    // a different lossy spec means the meta doesn't apply
+    let lossy_config = ChunkConfig::default().with_lossy_spec(LossySpec::MaxAbsError(0.01));
+    let lossy =
+      FileCompressor::default().chunk_compressor_reusing_meta(&nums, &meta, &lossy_config)?;
+    assert!(lossy.meta().error_bound.is_some());
+    assert_ne!(
+      lossy.meta().per_latent_var,
+      meta.per_latent_var
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_reuse_meta_wrong_type() -> PcoResult<()> {
+    let meta = FileCompressor::default()
+      .chunk_compressor(&[1_u32, 2, 3], &ChunkConfig::default())?
+      .meta()
+      .clone();
+    let res = FileCompressor::default().chunk_compressor_reusing_meta(
+      &[1_u64, 2, 3],
+      &meta,
+      &ChunkConfig::default(),
+    );
+    assert!(matches!(
+      res.unwrap_err().kind,
+      ErrorKind::InvalidArgument
+    ));
+    Ok(())
+  }
 }
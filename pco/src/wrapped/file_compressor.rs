@@ -1,10 +1,11 @@
 use std::io::Write;
 
 use crate::bit_writer::BitWriter;
-use crate::constants::HEADER_PADDING;
+use crate::constants::{HEADER_PADDING, OVERSHOOT_PADDING};
 use crate::data_types::Number;
-use crate::errors::PcoResult;
+use crate::errors::{PcoError, PcoResult};
 use crate::metadata::format_version::FormatVersion;
+use crate::metadata::ChunkMeta;
 use crate::wrapped::chunk_compressor;
 use crate::wrapped::chunk_compressor::ChunkCompressor;
 use crate::ChunkConfig;
@@ -73,4 +74,67 @@ impl FileCompressor {
   ) -> PcoResult<ChunkCompressor> {
     chunk_compressor::new(nums, config)
   }
+
+  /// Creates a `ChunkCompressor` that reuses the mode, delta encoding, and
+  /// bins of an existing chunk's metadata, e.g. from a previous chunk with a
+  /// similar distribution.
+  ///
+  /// Will return an error if any arguments provided are invalid or if the
+  /// metadata doesn't apply to this data type.
+  ///
+  /// This skips most of the compute of
+  /// [`chunk_compressor`][Self::chunk_compressor], since it doesn't need to
+  /// choose a mode or delta encoding or optimize bins.
+  /// If any of the numbers fall outside the reused bins, compress poorly with
+  /// them, or were made with a different lossy spec, this refits the chunk
+  /// from scratch as `chunk_compressor` would.
+  /// The config's `mode_spec`, `delta_spec`, and `compression_level` are only
+  /// used in that case.
+  pub fn chunk_compressor_reusing_meta<T: Number>(
+    &self,
+    nums: &[T],
+    meta: &ChunkMeta,
+    config: &ChunkConfig,
+  ) -> PcoResult<ChunkCompressor> {
+    chunk_compressor::new_reusing_meta(nums, meta, config)
+  }
+
+  /// Creates a `ChunkCompressor` whose metadata is exactly the shared
+  /// metadata provided, so that its pages can be decompressed with it.
+  ///
+  /// Like a compression dictionary, shared metadata can be written once
+  /// with [`write_shared_meta`][Self::write_shared_meta] and then used by
+  /// many chunks, each of which only writes its pages.
+  /// The wrapping format is responsible for recording which shared metadata
+  /// each chunk uses, e.g. by an id, so that decompressors can
+  /// [register][crate::wrapped::FileDecompressor::register_shared_meta] it.
+  ///
+  /// Will return an error if any arguments provided are invalid, if the
+  /// metadata stores stats or doesn't apply to this data type, or if the
+  /// numbers don't fit its bins or lossy spec.
+  /// In the latter case, a new shared metadata may be needed.
+  pub fn chunk_compressor_with_shared_meta<T: Number>(
+    &self,
+    nums: &[T],
+    meta: &ChunkMeta,
+    config: &ChunkConfig,
+  ) -> PcoResult<ChunkCompressor> {
+    chunk_compressor::new_with_shared_meta(nums, meta, config)
+  }
+
+  /// Writes metadata to be shared by many chunks to the destination.
+  ///
+  /// Will return an error if the metadata stores stats, since they would
+  /// be wrong for all but one chunk, or if the provided `Write` errors.
+  pub fn write_shared_meta<W: Write>(&self, meta: &ChunkMeta, dst: W) -> PcoResult<W> {
+    if meta.stats.is_some() {
+      return Err(PcoError::invalid_argument(
+        "shared chunk metadata may not store stats",
+      ));
+    }
+
+    let mut writer = BitWriter::new(dst, meta.exact_size() + OVERSHOOT_PADDING);
+    unsafe { meta.write_to(&mut writer)? };
+    Ok(writer.into_inner())
+  }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use better_io::BetterBufRead;
@@ -6,7 +7,7 @@ use crate::bit_reader;
 use crate::bit_reader::BitReaderBuilder;
 use crate::constants::{CHUNK_META_PADDING, HEADER_PADDING};
 use crate::data_types::{LatentType, Number};
-use crate::errors::PcoResult;
+use crate::errors::{PcoError, PcoResult};
 use crate::metadata::chunk::ChunkMeta;
 use crate::metadata::format_version::FormatVersion;
 use crate::wrapped::chunk_decompressor::ChunkDecompressor;
@@ -16,6 +17,7 @@ use crate::wrapped::chunk_decompressor::ChunkDecompressor;
 #[non_exhaustive]
 pub struct FileDecompressor {
   format_version: FormatVersion,
+  shared_metas: HashMap<u32, ChunkMeta>,
 }
 
 impl FileDecompressor {
@@ -29,7 +31,10 @@ impl FileDecompressor {
     let mut reader_builder = BitReaderBuilder::new(src, HEADER_PADDING, 0);
     let format_version = reader_builder.with_reader(FormatVersion::read_from)?;
     Ok((
-      Self { format_version },
+      Self {
+        format_version,
+        shared_metas: HashMap::new(),
+      },
       reader_builder.into_inner(),
     ))
   }
@@ -45,8 +50,14 @@ impl FileDecompressor {
   /// insufficient data are found.
   pub fn chunk_decompressor<T: Number, R: BetterBufRead>(
     &self,
-    mut src: R,
+    src: R,
   ) -> PcoResult<(ChunkDecompressor<T>, R)> {
+    let (chunk_meta, src) = self.read_chunk_meta::<T, R>(src)?;
+    let cd = ChunkDecompressor::new(chunk_meta)?;
+    Ok((cd, src))
+  }
+
+  fn read_chunk_meta<T: Number, R: BetterBufRead>(&self, mut src: R) -> PcoResult<(ChunkMeta, R)> {
     bit_reader::ensure_buf_read_capacity(&mut src, CHUNK_META_PADDING);
     let mut reader_builder = BitReaderBuilder::new(src, CHUNK_META_PADDING, 0);
     let latent_type = LatentType::new::<T::L>().unwrap();
@@ -57,7 +68,47 @@ impl FileDecompressor {
         latent_type,
       )?
     };
-    let cd = ChunkDecompressor::new(chunk_meta)?;
-    Ok((cd, reader_builder.into_inner()))
+    Ok((chunk_meta, reader_builder.into_inner()))
+  }
+
+  /// Reads metadata shared by many chunks and registers it under `id`,
+  /// returning the remaining input.
+  ///
+  /// Will return an error if version incompatibilities, corruptions, or
+  /// insufficient data are found.
+  ///
+  /// Registering another metadata under the same `id` replaces the old one.
+  /// See
+  /// [`FileCompressor::chunk_compressor_with_shared_meta`][crate::wrapped::FileCompressor::chunk_compressor_with_shared_meta]
+  /// for how shared metadata is written.
+  pub fn register_shared_meta<T: Number, R: BetterBufRead>(
+    &mut self,
+    id: u32,
+    src: R,
+  ) -> PcoResult<R> {
+    let (chunk_meta, src) = self.read_chunk_meta::<T, R>(src)?;
+    // validate it now so corruptions are found where the meta is read
+    ChunkDecompressor::<T>::new(chunk_meta.clone())?;
+    self.shared_metas.insert(id, chunk_meta);
+    Ok(src)
+  }
+
+  /// Returns a `ChunkDecompressor` for a chunk that uses the shared metadata
+  /// registered under `id`.
+  ///
+  /// Will return an error if no metadata is registered under `id` or if it
+  /// was registered for a different data type.
+  pub fn shared_chunk_decompressor<T: Number>(&self, id: u32) -> PcoResult<ChunkDecompressor<T>> {
+    let chunk_meta = match self.shared_metas.get(&id) {
+      Some(chunk_meta) => chunk_meta,
+      None => {
+        return Err(PcoError::invalid_argument(format!(
+          "no shared chunk metadata is registered under id {}",
+          id,
+        )))
+      }
+    };
+    chunk_meta.validate_latent_types(LatentType::new::<T::L>().unwrap())?;
+    ChunkDecompressor::new(chunk_meta.clone())
   }
 }
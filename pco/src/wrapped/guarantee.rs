@@ -27,11 +27,19 @@ pub(crate) fn baseline_chunk_meta<L: Latent>() -> ChunkMeta {
       primary,
       secondary: None,
     },
+    stats: None,
+    error_bound: None,
   }
 }
 
 /// Returns the maximum possible byte size of a wrapped chunk for a given
 /// latent type (e.g. u32 or u64) and count of numbers.
+///
+/// This assumes a single page, no stored
+/// [`Stats`][crate::metadata::Stats], and lossless compression; stats add
+/// `2 * L::BITS / 8 + 4` bytes to the chunk metadata and to each page, and a lossy
+/// [`ErrorBound`][crate::metadata::ErrorBound] adds up to 9 bytes to the chunk
+/// metadata.
 pub fn chunk_size<L: Latent>(n: usize) -> usize {
   baseline_chunk_meta::<L>().exact_size() + n * L::BITS.div_ceil(8) as usize
 }
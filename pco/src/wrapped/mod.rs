@@ -4,7 +4,7 @@ pub use file_compressor::FileCompressor;
 pub use file_decompressor::FileDecompressor;
 pub use page_decompressor::PageDecompressor;
 
-mod chunk_compressor;
+pub(crate) mod chunk_compressor;
 mod chunk_decompressor;
 mod file_compressor;
 mod file_decompressor;
@@ -1,19 +1,19 @@
 use std::cmp::min;
 use std::fmt::Debug;
-use std::marker::PhantomData;
 
 use better_io::BetterBufRead;
 
+use crate::aggregate::Aggregates;
 use crate::bit_reader;
 use crate::bit_reader::BitReaderBuilder;
 use crate::constants::{FULL_BATCH_N, PAGE_PADDING};
-use crate::data_types::Number;
+use crate::data_types::{Latent, Number};
 use crate::errors::{PcoError, PcoResult};
 use crate::latent_page_decompressor::DynLatentPageDecompressor;
 use crate::macros::match_latent_enum;
 use crate::metadata::page::PageMeta;
 use crate::metadata::per_latent_var::{PerLatentVar, PerLatentVarBuilder};
-use crate::metadata::{ChunkMeta, DeltaEncoding, DynBins, DynLatents, Mode};
+use crate::metadata::{ChunkMeta, DeltaEncoding, DynBins, DynLatents, Mode, Stats};
 use crate::progress::Progress;
 
 const PERFORMANT_BUF_READ_CAPACITY: usize = 8192;
@@ -29,6 +29,7 @@ struct PageDecompressorInner<R: BetterBufRead> {
   n: usize,
   mode: Mode,
   delta_encoding: DeltaEncoding,
+  stats: Option<Stats>,
 
   // mutable
   reader_builder: BitReaderBuilder<R>,
@@ -41,7 +42,32 @@ struct PageDecompressorInner<R: BetterBufRead> {
 /// Holds metadata about a page and supports decompression.
 pub struct PageDecompressor<T: Number, R: BetterBufRead> {
   inner: PageDecompressorInner<R>,
-  phantom: PhantomData<T>,
+  // ordered latents known to bound every number in the chunk, if any
+  chunk_bounds: Option<(T::L, T::L)>,
+}
+
+// what a range predicate matches, as far as metadata alone can tell
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RangeMatch {
+  Nothing,
+  Everything,
+  Unknown,
+}
+
+fn match_ranges<L: Latent>(ranges: &[(L, L)], lo: L, hi: L) -> RangeMatch {
+  if ranges
+    .iter()
+    .all(|&(lower, upper)| upper < lo || lower >= hi)
+  {
+    RangeMatch::Nothing
+  } else if ranges
+    .iter()
+    .all(|&(lower, upper)| lower >= lo && upper < hi)
+  {
+    RangeMatch::Everything
+  } else {
+    RangeMatch::Unknown
+  }
 }
 
 fn convert_from_latents_to_numbers<T: Number>(dst: &mut [T]) {
@@ -118,6 +144,14 @@ impl<R: BetterBufRead> PageDecompressorInner<R> {
 
     let page_meta =
       reader_builder.with_reader(|reader| unsafe { PageMeta::read_from(reader, chunk_meta) })?;
+    if let Some(stats) = &page_meta.stats {
+      if stats.count != n {
+        return Err(PcoError::corruption(format!(
+          "page stats count {} does not match page n {}",
+          stats.count, n,
+        )));
+      }
+    }
 
     let mode = chunk_meta.mode;
     let latent_decompressors = make_latent_decompressors(chunk_meta, &page_meta, n)?;
@@ -130,6 +164,7 @@ impl<R: BetterBufRead> PageDecompressorInner<R> {
       n,
       mode,
       delta_encoding: chunk_meta.delta_encoding,
+      stats: page_meta.stats,
       reader_builder,
       n_processed: 0,
       latent_decompressors,
@@ -146,18 +181,26 @@ impl<R: BetterBufRead> PageDecompressorInner<R> {
 impl<T: Number, R: BetterBufRead> PageDecompressor<T, R> {
   #[inline(never)]
   pub(crate) fn new(src: R, chunk_meta: &ChunkMeta, n: usize) -> PcoResult<Self> {
+    let inner = PageDecompressorInner::new(src, chunk_meta, n)?;
+    let chunk_bounds = chunk_meta.bounds::<T>().map(|(lower, upper)| {
+      (
+        lower.to_latent_ordered(),
+        upper.to_latent_ordered(),
+      )
+    });
     Ok(Self {
-      inner: PageDecompressorInner::new(src, chunk_meta, n)?,
-      phantom: PhantomData::<T>,
+      inner,
+      chunk_bounds,
     })
   }
 
-  fn decompress_batch(&mut self, dst: &mut [T]) -> PcoResult<()> {
-    let batch_n = dst.len();
+  // decodes the next batch of latents, leaving the primary latents in
+  // primary_dst and any secondary latents in the secondary scratch
+  fn decompress_latent_batch(&mut self, primary_dst: &mut [T::L]) -> PcoResult<()> {
+    let batch_n = primary_dst.len();
     let inner = &mut self.inner;
     let n = inner.n;
     let n_remaining = inner.n_remaining();
-    let mode = inner.mode;
 
     // DELTA LATENTS
     if let Some(LatentScratch {
@@ -191,7 +234,6 @@ impl<T: Number, R: BetterBufRead> PageDecompressor<T, R> {
 
     // PRIMARY LATENTS
     inner.reader_builder.with_reader(|reader| unsafe {
-      let primary_dst = T::transmute_to_latents(dst);
       let dyn_lpd = inner
         .latent_decompressors
         .primary
@@ -229,13 +271,6 @@ impl<T: Number, R: BetterBufRead> PageDecompressor<T, R> {
       })?;
     }
 
-    T::join_latents(
-      mode,
-      T::transmute_to_latents(dst),
-      inner.secondary_scratch.as_ref().map(|scratch| &scratch.dst),
-    );
-    convert_from_latents_to_numbers(dst);
-
     inner.n_processed += batch_n;
     if inner.n_processed == n {
       inner.reader_builder.with_reader(|reader| {
@@ -246,6 +281,45 @@ impl<T: Number, R: BetterBufRead> PageDecompressor<T, R> {
     Ok(())
   }
 
+  // decodes the next batch into dst as ordered latents, which still need to
+  // be converted into numbers
+  fn decompress_joined_latent_batch(&mut self, dst: &mut [T]) -> PcoResult<()> {
+    self.decompress_latent_batch(T::transmute_to_latents(dst))?;
+
+    let inner = &self.inner;
+    T::join_latents(
+      inner.mode,
+      T::transmute_to_latents(dst),
+      inner.secondary_scratch.as_ref().map(|scratch| &scratch.dst),
+    );
+    Ok(())
+  }
+
+  fn decompress_batch(&mut self, dst: &mut [T]) -> PcoResult<()> {
+    self.decompress_joined_latent_batch(dst)?;
+    convert_from_latents_to_numbers(dst);
+    Ok(())
+  }
+
+  fn validate_batch_len(&self, len: usize, name: &str) -> PcoResult<()> {
+    let n_remaining = self.inner.n_remaining();
+    if len % FULL_BATCH_N != 0 && len < n_remaining {
+      return Err(PcoError::invalid_argument(format!(
+        "{}'s length must either be a multiple of {} or be \
+         at least the count of numbers remaining ({} < {})",
+        name, FULL_BATCH_N, len, n_remaining,
+      )));
+    }
+    Ok(())
+  }
+
+  /// Returns the page's summary statistics, if they were stored.
+  ///
+  /// These are read from the page metadata without decoding any latents.
+  pub fn stats(&self) -> Option<&Stats> {
+    self.inner.stats.as_ref()
+  }
+
   /// Reads the next decompressed numbers into the destination, returning
   /// progress into the page and advancing along the compressed data.
   ///
@@ -254,18 +328,9 @@ impl<T: Number, R: BetterBufRead> PageDecompressor<T, R> {
   /// `dst` must have length either a multiple of 256 or be at least the count
   /// of numbers remaining in the page.
   pub fn decompress(&mut self, num_dst: &mut [T]) -> PcoResult<Progress> {
-    let n_remaining = self.inner.n_remaining();
-    if num_dst.len() % FULL_BATCH_N != 0 && num_dst.len() < n_remaining {
-      return Err(PcoError::invalid_argument(format!(
-        "num_dst's length must either be a multiple of {} or be \
-         at least the count of numbers remaining ({} < {})",
-        FULL_BATCH_N,
-        num_dst.len(),
-        n_remaining,
-      )));
-    }
+    self.validate_batch_len(num_dst.len(), "num_dst")?;
 
-    let n_to_process = min(num_dst.len(), n_remaining);
+    let n_to_process = min(num_dst.len(), self.inner.n_remaining());
 
     let mut n_processed = 0;
     while n_processed < n_to_process {
@@ -280,8 +345,217 @@ impl<T: Number, R: BetterBufRead> PageDecompressor<T, R> {
     })
   }
 
+  /// Reads past the next `n` numbers without writing them anywhere,
+  /// returning progress into the page and advancing along the compressed
+  /// data.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// `n` must be either a multiple of 256 or be at least the count of numbers
+  /// remaining in the page.
+  /// The latents still need to be decoded, since each batch depends on the
+  /// state left by the previous one, but they are never converted into
+  /// numbers.
+  pub fn skip(&mut self, n: usize) -> PcoResult<Progress> {
+    self.validate_batch_len(n, "n")?;
+
+    let n_to_process = min(n, self.inner.n_remaining());
+    let mut latent_scratch = vec![T::L::default(); min(n_to_process, FULL_BATCH_N)];
+
+    let mut n_processed = 0;
+    while n_processed < n_to_process {
+      let batch_n = min(FULL_BATCH_N, n_to_process - n_processed);
+      self.decompress_latent_batch(&mut latent_scratch[..batch_n])?;
+      n_processed += batch_n;
+    }
+
+    Ok(Progress {
+      n_processed,
+      finished: self.inner.n_remaining() == 0,
+    })
+  }
+
+  /// Reads through the rest of the page, returning the count, min, max, and
+  /// sum of its numbers without writing them anywhere.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// Numbers are decoded a batch at a time into a small internal buffer.
+  /// If the page stored its statistics and none of it has been read yet,
+  /// the min and max are taken from those instead of being compared.
+  pub fn aggregate(&mut self) -> PcoResult<Aggregates<T>> {
+    let mut res = Aggregates::empty();
+    let stats = match &self.inner.stats {
+      Some(stats) if self.inner.n_processed == 0 => Some(stats),
+      _ => None,
+    };
+    if let Some(stats) = stats {
+      res.min = stats.min::<T>();
+      res.max = stats.max::<T>();
+    }
+    let use_stats = stats.is_some();
+
+    let mut batch = vec![T::default(); min(self.inner.n_remaining(), FULL_BATCH_N)];
+    while self.inner.n_remaining() > 0 {
+      let batch_n = min(FULL_BATCH_N, self.inner.n_remaining());
+      let dst = &mut batch[..batch_n];
+      self.decompress_joined_latent_batch(dst)?;
+      if !use_stats {
+        res.update_from_latents(T::transmute_to_latents(dst));
+      }
+      convert_from_latents_to_numbers(dst);
+      res.update_from_nums(dst);
+    }
+
+    Ok(res)
+  }
+
+  // checks the page's stats, then the chunk's bounds, each of which give
+  // inclusive ranges of ordered latents known to contain every number
+  fn match_range(&self, lo: T::L, hi: T::L) -> RangeMatch {
+    if lo >= hi {
+      return RangeMatch::Nothing;
+    }
+
+    let stats_range = self.inner.stats.as_ref().and_then(|stats| {
+      Some((
+        stats.min::<T>()?.to_latent_ordered(),
+        stats.max::<T>()?.to_latent_ordered(),
+      ))
+    });
+    let stats_match = stats_range.map_or(RangeMatch::Unknown, |range| {
+      match_ranges(&[range], lo, hi)
+    });
+    if stats_match != RangeMatch::Unknown {
+      return stats_match;
+    }
+
+    self.chunk_bounds.map_or(RangeMatch::Unknown, |range| {
+      match_ranges(&[range], lo, hi)
+    })
+  }
+
+  /// Reads through the rest of the page, returning the indices of the
+  /// numbers `x` with `lo <= x < hi`, counted from the start of the page.
+  ///
+  /// Will return an error if corruptions or insufficient data are found.
+  ///
+  /// Numbers are compared in pco's total order, so for floats, `-0.0` is
+  /// less than `0.0` and NaNs sort to the ends, as with `total_cmp`.
+  /// If the page's stats or the chunk's bounds (see
+  /// [`ChunkMeta::bounds`]) show that the range matches no numbers or all of
+  /// them, the numbers are never converted or compared.
+  /// Their latents are still decoded as in [`skip`][Self::skip], since that
+  /// is the only way to find where the page ends.
+  /// Otherwise, each batch is compared right after decoding into a small
+  /// internal buffer.
+  pub fn filter_range(&mut self, lo: T, hi: T) -> PcoResult<Vec<u32>> {
+    let lo = lo.to_latent_ordered();
+    let hi = hi.to_latent_ordered();
+    let start = self.inner.n_processed;
+    let n_remaining = self.inner.n_remaining();
+
+    let mut res = Vec::new();
+    match self.match_range(lo, hi) {
+      RangeMatch::Nothing => {
+        self.skip(n_remaining)?;
+      }
+      RangeMatch::Everything => {
+        self.skip(n_remaining)?;
+        res.extend(start as u32..(start + n_remaining) as u32);
+      }
+      RangeMatch::Unknown => {
+        let mut batch = vec![T::default(); min(n_remaining, FULL_BATCH_N)];
+        while self.inner.n_remaining() > 0 {
+          let batch_start = self.inner.n_processed;
+          let batch_n = min(FULL_BATCH_N, self.inner.n_remaining());
+          let dst = &mut batch[..batch_n];
+          self.decompress_joined_latent_batch(dst)?;
+          let latents = T::transmute_to_latents(dst);
+          for (i, &l) in latents.iter().enumerate() {
+            if lo <= l && l < hi {
+              res.push((batch_start + i) as u32);
+            }
+          }
+        }
+      }
+    }
+
+    Ok(res)
+  }
+
+  // for reading any data the wrapping format stores after the page
+  pub(crate) fn src_mut(&mut self) -> &mut R {
+    self.inner.reader_builder.inner_mut()
+  }
+
   /// Returns the rest of the compressed data source.
   pub fn into_src(self) -> R {
     self.inner.reader_builder.into_inner()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::chunk_config::{ChunkConfig, DeltaSpec};
+  use crate::wrapped::{FileCompressor, FileDecompressor};
+
+  fn match_range_for(config: &ChunkConfig, lo: i64, hi: i64) -> PcoResult<RangeMatch> {
+    let nums = (0..1000_i64).map(|x| (x * 13) % 500).collect::<Vec<_>>();
+    let fc = FileCompressor::default();
+    let cc = fc.chunk_compressor(&nums, config)?;
+    let mut header = fc.write_header(Vec::new())?;
+    header = cc.write_chunk_meta(header)?;
+    let page = cc.write_page(0, Vec::new())?;
+
+    let (fd, src) = FileDecompressor::new(header.as_slice())?;
+    let (cd, _) = fd.chunk_decompressor::<i64, _>(src)?;
+    let pd = cd.page_decompressor(page.as_slice(), nums.len())?;
+    Ok(pd.match_range(
+      lo.to_latent_ordered(),
+      hi.to_latent_ordered(),
+    ))
+  }
+
+  #[test]
+  fn test_match_range_from_metadata() -> PcoResult<()> {
+    for (delta_spec, store_stats) in [
+      (DeltaSpec::TryConsecutive(1), true),
+      (DeltaSpec::None, false),
+    ] {
+      let config = ChunkConfig {
+        delta_spec,
+        store_stats,
+        ..Default::default()
+      };
+      assert_eq!(
+        match_range_for(&config, 1_000, 2_000)?,
+        RangeMatch::Nothing
+      );
+      assert_eq!(
+        match_range_for(&config, -1, 1_000)?,
+        RangeMatch::Everything
+      );
+      assert_eq!(
+        match_range_for(&config, 10, 20)?,
+        RangeMatch::Unknown
+      );
+      assert_eq!(
+        match_range_for(&config, 20, 10)?,
+        RangeMatch::Nothing
+      );
+    }
+
+    // without stats, delta encoded bins say nothing about the numbers
+    let config = ChunkConfig {
+      delta_spec: DeltaSpec::TryConsecutive(1),
+      ..Default::default()
+    };
+    assert_eq!(
+      match_range_for(&config, 1_000, 2_000)?,
+      RangeMatch::Unknown
+    );
+    Ok(())
+  }
+}
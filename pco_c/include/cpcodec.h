@@ -15,6 +15,11 @@ extern "C" {
 #define PCO_TYPE_U16 7
 #define PCO_TYPE_I16 8
 #define PCO_TYPE_F16 9
+#define PCO_TYPE_U8 10
+#define PCO_TYPE_I8 11
+#define PCO_TYPE_U128 12
+#define PCO_TYPE_I128 13
+#define PCO_TYPE_BF16 14
 
 #if defined (__cplusplus)
 }
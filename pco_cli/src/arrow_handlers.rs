@@ -29,9 +29,11 @@ pub fn from_dtype(dtype: &DataType) -> Result<Box<dyn ArrowHandler>> {
     Float16 => Float16Type,
     Float32 => Float32Type,
     Float64 => Float64Type,
+    Int8 => Int8Type,
     Int16 => Int16Type,
     Int32 => Int32Type,
     Int64 => Int64Type,
+    UInt8 => UInt8Type,
     UInt16 => UInt16Type,
     UInt32 => UInt32Type,
     UInt64 => UInt64Type,
@@ -41,6 +43,7 @@ pub fn from_dtype(dtype: &DataType) -> Result<Box<dyn ArrowHandler>> {
     Timestamp(TimeUnit::Nanosecond, _) => TimestampNanosecondType,
     Date32 => Date32Type,
     Date64 => Date64Type,
+    Decimal128(_, _) => Decimal128Type,
   )
 }
 
@@ -10,7 +10,7 @@ use crate::bench::codecs::parquet::ParquetConfig;
 use crate::bench::codecs::snappy::SnappyConfig;
 use crate::bench::codecs::zstd::ZstdConfig;
 use crate::bench::IterOpt;
-use crate::bench::{BenchStat, Precomputed};
+use crate::bench::{core_dtype_to_str, BenchStat, Precomputed};
 use crate::chunk_config_opt::ChunkConfigOpt;
 use crate::dtypes::PcoNumber;
 use crate::num_vec::NumVec;
@@ -44,6 +44,11 @@ trait CodecInternal: Clone + CommandFactory + Debug + FromArgMatches + Send + Sy
   fn name(&self) -> &'static str;
   fn get_confs(&self) -> Vec<(&'static str, String)>;
 
+  // compress and decompress may assume this is true
+  fn supports_dtype(&self, _dtype: NumberType) -> bool {
+    true
+  }
+
   fn compress<T: PcoNumber>(&self, nums: &[T]) -> Vec<u8>;
   fn decompress<T: PcoNumber>(&self, compressed: &[u8]) -> Vec<T>;
 
@@ -143,6 +148,14 @@ impl<C: CodecInternal> CodecSurface for C {
     thread_idx: usize,
   ) -> Result<Precomputed> {
     let dtype = num_vec.dtype();
+    if !self.supports_dtype(dtype) {
+      return Err(anyhow!(
+        "codec {} does not support {} data (on dataset {})",
+        self.name(),
+        core_dtype_to_str(dtype),
+        dataset,
+      ));
+    }
 
     // compress
     let compressed = self.compress_dynamic(num_vec);
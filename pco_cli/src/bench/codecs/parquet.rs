@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use parquet::basic::{Compression, ZstdLevel};
+use parquet::basic::Compression;
 use parquet::column::reader::get_typed_column_reader;
 use parquet::file::properties::{WriterProperties, WriterVersion};
 use parquet::file::reader::FileReader;
@@ -12,12 +12,13 @@ use parquet::schema::parser::parse_message_type;
 
 use crate::bench::codecs::CodecInternal;
 use crate::dtypes::PcoNumber;
+use crate::parse;
 
 const ZSTD: &str = "zstd";
 
 #[derive(Clone, Debug, Parser)]
 pub struct ParquetConfig {
-  #[arg(long, value_parser=str_to_compression, default_value = "uncompressed")]
+  #[arg(long, value_parser=parse::parquet_compression, default_value = "uncompressed")]
   compression: Compression,
   // Larger group sizes work better on some datasets, and smaller ones on
   // others, sometimes with dramatic impact.
@@ -36,26 +37,6 @@ struct Encoding {
   basic: Option<parquet::basic::Encoding>,
 }
 
-fn str_to_compression(s: &str) -> Result<Compression> {
-  let res = match s.to_lowercase().as_str() {
-    "uncompressed" => Compression::UNCOMPRESSED,
-    "snappy" => Compression::SNAPPY,
-    _ => {
-      if let Some(zstd_level_str) = s.strip_prefix(ZSTD) {
-        let level = if zstd_level_str.is_empty() {
-          ZstdLevel::default()
-        } else {
-          ZstdLevel::try_new(zstd_level_str.parse::<i32>()?)?
-        };
-        Compression::ZSTD(level)
-      } else {
-        return Err(anyhow!("unknown parquet codec {}", s));
-      }
-    }
-  };
-  Ok(res)
-}
-
 fn compression_to_string(compression: &Compression) -> String {
   match compression {
     Compression::UNCOMPRESSED => "uncompressed".to_string(),
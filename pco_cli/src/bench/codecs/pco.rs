@@ -25,6 +25,13 @@ fn unparse_mode_spec(spec: &ModeSpec) -> String {
   }
 }
 
+fn unparse_option<T: ToString>(value: Option<T>) -> String {
+  match value {
+    Some(value) => value.to_string(),
+    None => "None".to_string(),
+  }
+}
+
 impl CodecInternal for ChunkConfigOpt {
   fn name(&self) -> &'static str {
     "pco"
@@ -36,6 +43,14 @@ impl CodecInternal for ChunkConfigOpt {
       ("delta", unparse_delta_spec(&self.delta)),
       ("mode", unparse_mode_spec(&self.mode)),
       ("chunk-n", self.chunk_n.to_string()),
+      (
+        "max-abs-error",
+        unparse_option(self.max_abs_error),
+      ),
+      (
+        "significant-bits",
+        unparse_option(self.significant_bits),
+      ),
     ]
   }
 
@@ -2,7 +2,8 @@ use std::convert::TryInto;
 use std::mem;
 
 use clap::Parser;
-use half::f16;
+use half::{bf16, f16};
+use pco::data_types::NumberType;
 
 use crate::bench::codecs::CodecInternal;
 use crate::dtypes::{PcoNumber, TurboPforable};
@@ -27,6 +28,10 @@ impl CodecInternal for TurboPforConfig {
     ]
   }
 
+  fn supports_dtype(&self, dtype: NumberType) -> bool {
+    !matches!(dtype, NumberType::U128 | NumberType::I128)
+  }
+
   fn compress<T: PcoNumber>(&self, nums: &[T]) -> Vec<u8> {
     let mut nums = nums.to_vec();
     // not sure this is the real contract, just a heuristic
@@ -87,12 +92,31 @@ macro_rules! impl_pforable {
   };
 }
 
+impl_pforable!(u8, u8, p4nenc8, p4ndec8);
 impl_pforable!(u16, u16, p4nenc128v16, p4ndec128v16);
 impl_pforable!(u32, u32, p4nenc128v32, p4ndec128v32);
 impl_pforable!(u64, u64, p4nenc128v64, p4ndec128v64);
+impl_pforable!(i8, u8, p4nenc8, p4ndec8);
 impl_pforable!(i16, u16, p4nenc128v16, p4ndec128v16);
 impl_pforable!(i32, u32, p4nenc128v32, p4ndec128v32);
 impl_pforable!(i64, u64, p4nenc128v64, p4ndec128v64);
 impl_pforable!(f16, u16, p4nenc128v16, p4ndec128v16);
+impl_pforable!(bf16, u16, p4nenc128v16, p4ndec128v16);
 impl_pforable!(f32, u32, p4nenc128v32, p4ndec128v32);
 impl_pforable!(f64, u64, p4nenc128v64, p4ndec128v64);
+
+macro_rules! impl_unpforable {
+  ($t: ty) => {
+    impl TurboPforable for $t {
+      unsafe fn encode(_src: &mut [Self], _dst: &mut [u8]) -> usize {
+        unreachable!("turbo pfor does not support 128-bit integers")
+      }
+      unsafe fn decode(_src: &mut [u8], _n: usize, _dst: &mut [Self]) {
+        unreachable!("turbo pfor does not support 128-bit integers")
+      }
+    }
+  };
+}
+
+impl_unpforable!(u128);
+impl_unpforable!(i128);
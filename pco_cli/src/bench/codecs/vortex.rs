@@ -1,10 +1,10 @@
 use once_cell::sync::Lazy;
 
 use crate::bench::codecs::{utils, CodecInternal};
-use crate::dtypes::PcoNumber;
+use crate::dtypes::{PcoNumber, Vortexable};
 use clap::Parser;
+use half::{bf16, f16};
 use pco::data_types::NumberType;
-use pco::match_number_enum;
 use tokio::runtime::Runtime;
 use vortex::arrays::PrimitiveArray;
 use vortex::buffer::ByteBuffer;
@@ -30,20 +30,12 @@ impl CodecInternal for VortexConfig {
     vec![]
   }
 
+  fn supports_dtype(&self, dtype: NumberType) -> bool {
+    !matches!(dtype, NumberType::U128 | NumberType::I128)
+  }
+
   fn compress<T: PcoNumber>(&self, nums: &[T]) -> Vec<u8> {
-    // can't figure out a way to avoid copying here
-    let byte_buffer = ByteBuffer::copy_from(unsafe { utils::num_slice_to_bytes(nums) });
-    let number_type = NumberType::from_descriminant(T::NUMBER_TYPE_BYTE).unwrap();
-    let dtype = match_number_enum!(number_type,
-      NumberType<T> => {
-        T::dtype()
-      }
-    );
-    let ptype = match dtype {
-      DType::Primitive(ptype, _) => ptype,
-      _ => unreachable!(),
-    };
-    let vortex_arr = PrimitiveArray::from_byte_buffer(byte_buffer, ptype, Validity::NonNullable);
+    let vortex_arr = T::to_vortex(nums);
 
     // unfortunately vortex only has an async API
     // By default, writing an array will decompress it back to its canonical form and then recompress it, so there's no need to compress it ahead of time.
@@ -60,8 +52,74 @@ impl CodecInternal for VortexConfig {
     let mut res = vec![];
     for array_result in x.scan().unwrap().into_array_iter().unwrap() {
       let array = array_result.unwrap().to_primitive().unwrap();
-      res.extend(array.buffer::<T>());
+      T::extend_from_vortex(&array, &mut res);
     }
     res
   }
 }
+
+macro_rules! impl_vortexable {
+  ($t: ty) => {
+    impl Vortexable for $t {
+      fn to_vortex(nums: &[Self]) -> PrimitiveArray {
+        // can't figure out a way to avoid copying here
+        let byte_buffer = ByteBuffer::copy_from(unsafe { utils::num_slice_to_bytes(nums) });
+        let ptype = match <$t>::dtype() {
+          DType::Primitive(ptype, _) => ptype,
+          _ => unreachable!(),
+        };
+        PrimitiveArray::from_byte_buffer(byte_buffer, ptype, Validity::NonNullable)
+      }
+
+      fn extend_from_vortex(array: &PrimitiveArray, dst: &mut Vec<Self>) {
+        dst.extend(array.buffer::<$t>());
+      }
+    }
+  };
+}
+
+impl_vortexable!(u8);
+impl_vortexable!(u16);
+impl_vortexable!(u32);
+impl_vortexable!(u64);
+impl_vortexable!(i8);
+impl_vortexable!(i16);
+impl_vortexable!(i32);
+impl_vortexable!(i64);
+impl_vortexable!(f16);
+impl_vortexable!(f32);
+impl_vortexable!(f64);
+
+// Vortex has no bfloat16 type, so we give it the raw bits.
+impl Vortexable for bf16 {
+  fn to_vortex(nums: &[Self]) -> PrimitiveArray {
+    let bits = nums.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    u16::to_vortex(&bits)
+  }
+
+  fn extend_from_vortex(array: &PrimitiveArray, dst: &mut Vec<Self>) {
+    dst.extend(
+      array
+        .buffer::<u16>()
+        .iter()
+        .map(|&bits| bf16::from_bits(bits)),
+    );
+  }
+}
+
+macro_rules! impl_unvortexable {
+  ($t: ty) => {
+    impl Vortexable for $t {
+      fn to_vortex(_nums: &[Self]) -> PrimitiveArray {
+        unreachable!("vortex does not support 128-bit integers")
+      }
+
+      fn extend_from_vortex(_array: &PrimitiveArray, _dst: &mut Vec<Self>) {
+        unreachable!("vortex does not support 128-bit integers")
+      }
+    }
+  };
+}
+
+impl_unvortexable!(u128);
+impl_unvortexable!(i128);
@@ -1,6 +1,6 @@
 use clap::Parser;
 
-use pco::{ChunkConfig, DeltaSpec, ModeSpec, PagingSpec};
+use pco::{ChunkConfig, DeltaSpec, LossySpec, ModeSpec, PagingSpec};
 
 use crate::parse;
 
@@ -22,16 +22,29 @@ pub struct ChunkConfigOpt {
   /// classic if the given mode is especially bad.
   #[arg(long, default_value = "Auto", value_parser = parse::mode_spec)]
   pub mode: ModeSpec,
+  /// If provided, lossily rounds floats so that each decompressed number is
+  /// within this absolute error of the original.
+  #[arg(long)]
+  pub max_abs_error: Option<f64>,
+  /// If provided, lossily rounds floats' mantissas to this many bits.
+  #[arg(long, conflicts_with = "max_abs_error")]
+  pub significant_bits: Option<u32>,
   #[arg(long, default_value_t = pco::DEFAULT_MAX_PAGE_N)]
   pub chunk_n: usize,
 }
 
 impl From<&ChunkConfigOpt> for ChunkConfig {
   fn from(opt: &ChunkConfigOpt) -> Self {
+    let lossy_spec = match (opt.max_abs_error, opt.significant_bits) {
+      (Some(max_abs_error), _) => LossySpec::MaxAbsError(max_abs_error),
+      (None, Some(significant_bits)) => LossySpec::SignificantBits(significant_bits),
+      (None, None) => LossySpec::Lossless,
+    };
     ChunkConfig::default()
       .with_compression_level(opt.level)
       .with_delta_spec(opt.delta)
       .with_mode_spec(opt.mode)
       .with_paging_spec(PagingSpec::EqualPagesUpTo(opt.chunk_n))
+      .with_lossy_spec(lossy_spec)
   }
 }
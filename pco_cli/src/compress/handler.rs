@@ -1,7 +1,9 @@
 use std::cmp::min;
 use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom};
 
 use anyhow::Result;
+use arrow::array::Array;
 use arrow::datatypes::Schema;
 
 use pco::standalone::FileCompressor;
@@ -30,16 +32,25 @@ impl<P: ArrowNumber> CompressHandler for ArrowHandlerImpl<P> {
 
     let config = ChunkConfig::from(&opt.chunk_config);
     let chunk_size = opt.chunk_config.chunk_n;
-    let fc = FileCompressor::default();
-    fc.write_header(&file)?;
-
     let col_idx = utils::find_col_idx(
       schema,
       opt.input_column.col_idx,
       &opt.input_column.col_name,
     )?;
+    // If the column has nulls, chunks hold only the non-null numbers, and we
+    // write the validity of each row after them.
+    // We only know whether there are nulls after reading the whole column
+    // (some inputs, like CSVs, can have nulls even in non-nullable fields), so
+    // we write a non-nullable header for now and rewrite it at the end if
+    // needed; the header's size doesn't depend on its flags.
+    let mut has_nulls = false;
+    let mut n_rows = 0;
+    let fc = FileCompressor::default();
+    fc.write_header(&file)?;
+
     let reader = input::new_column_reader(schema, col_idx, &opt.input_file)?;
     let mut num_buffer = Vec::<P::Pco>::new();
+    let mut validity = Vec::new();
 
     let write_chunks = |num_buffer: &mut Vec<P::Pco>, finish: bool| -> Result<()> {
       let n = num_buffer.len();
@@ -62,6 +73,15 @@ impl<P: ArrowNumber> CompressHandler for ArrowHandlerImpl<P> {
 
     for array_result in reader {
       let array = array_result?;
+      if !has_nulls && array.null_count() > 0 {
+        // every earlier row was valid
+        has_nulls = true;
+        validity = vec![true; n_rows];
+      }
+      if has_nulls {
+        validity.extend((0..array.len()).map(|i| array.is_valid(i)));
+      }
+      n_rows += array.len();
       num_buffer.extend(utils::arrow_to_nums::<P>(array));
       write_chunks(&mut num_buffer, false)?;
     }
@@ -69,6 +89,125 @@ impl<P: ArrowNumber> CompressHandler for ArrowHandlerImpl<P> {
     write_chunks(&mut num_buffer, true)?;
 
     fc.write_footer(&file)?;
+    if has_nulls {
+      let fc = fc.with_nullable(true);
+      fc.write_validity(&validity, &file)?;
+      (&file).seek(SeekFrom::Start(0))?;
+      fc.write_header(&file)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+  use std::fs::File;
+  use std::path::{Path, PathBuf};
+  use std::sync::Arc;
+
+  use arrow::array::Decimal128Array;
+  use arrow::record_batch::RecordBatch;
+  use clap::Parser;
+  use parquet::arrow::ArrowWriter;
+  use pco::standalone::{simple_decompress, simple_decompress_nullable, FileDecompressor};
+
+  use crate::compress::compress;
+
+  use super::*;
+
+  fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+      "pco_compress_{}_{}",
+      std::process::id(),
+      name
+    ))
+  }
+
+  fn compress_csv_col(input: &Path, col_name: &str, output: &Path) -> Result<()> {
+    let opt = CompressOpt::try_parse_from([
+      "compress",
+      "--overwrite",
+      "--input",
+      input.to_str().unwrap(),
+      "--csv-has-header",
+      "--dtype",
+      "i64",
+      "--col-name",
+      col_name,
+      "--chunk-n",
+      "300",
+      output.to_str().unwrap(),
+    ])?;
+    compress(opt)
+  }
+
+  #[test]
+  fn test_compress_nulls() -> Result<()> {
+    // a single-column CSV would skip empty lines, so we add a second column
+    let rows = (0..1000_i64)
+      .map(|i| if i % 7 == 3 { None } else { Some(i * i) })
+      .collect::<Vec<_>>();
+    let mut csv = "x,y\n".to_string();
+    for (i, row) in rows.iter().enumerate() {
+      let x = row.map(|x| x.to_string()).unwrap_or_default();
+      csv.push_str(&format!("{},{}\n", x, i));
+    }
+    let input = temp_path("nulls.csv");
+    fs::write(&input, csv)?;
+
+    let output = temp_path("nulls.pco");
+    compress_csv_col(&input, "x", &output)?;
+    let recovered = simple_decompress_nullable::<i64>(&fs::read(&output)?)?;
+    assert_eq!(recovered.to_options(), rows);
+
+    // columns without nulls don't get validity
+    compress_csv_col(&input, "y", &output)?;
+    let compressed = fs::read(&output)?;
+    let (fd, _) = FileDecompressor::new(compressed.as_slice())?;
+    assert!(!fd.is_nullable());
+
+    fs::remove_file(input)?;
+    fs::remove_file(output)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_compress_decimal_scale() -> Result<()> {
+    let nums = (0..1000_i128).map(|i| i * 1234 - 5).collect::<Vec<_>>();
+    let input = temp_path("decimal.parquet");
+    let output = temp_path("decimal.pco");
+    for scale in [0, 2] {
+      let array = Decimal128Array::from(nums.clone()).with_precision_and_scale(10, scale)?;
+      let batch = RecordBatch::try_from_iter([("x", Arc::new(array) as _)])?;
+      let mut writer = ArrowWriter::try_new(File::create(&input)?, batch.schema(), None)?;
+      writer.write(&batch)?;
+      writer.close()?;
+
+      let opt = CompressOpt::try_parse_from([
+        "compress",
+        "--overwrite",
+        "--input",
+        input.to_str().unwrap(),
+        "--col-name",
+        "x",
+        output.to_str().unwrap(),
+      ])?;
+      if scale == 0 {
+        compress(opt)?;
+        assert_eq!(
+          simple_decompress::<i128>(&fs::read(&output)?)?,
+          nums
+        );
+      } else {
+        // decompressing would give the values without their scale
+        fs::remove_file(&output)?;
+        assert!(compress(opt).is_err());
+        assert!(!output.exists());
+      }
+    }
+
+    fs::remove_file(input)?;
     Ok(())
   }
 }
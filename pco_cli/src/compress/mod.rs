@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use arrow::datatypes::DataType;
 use clap::Parser;
 
 use crate::input::{InputColumnOpt, InputFileOpt};
@@ -34,6 +35,16 @@ pub fn compress(opt: CompressOpt) -> Result<()> {
     &opt.input_column.col_name,
   )?;
   let dtype = schema.field(col_idx).data_type();
+  // .pco files only hold the unscaled integers, so the scale would be lost
+  if let DataType::Decimal128(_, scale) = dtype {
+    if *scale != 0 {
+      return Err(anyhow!(
+        "{:?} has nonzero scale {}, which .pco files cannot store",
+        dtype,
+        scale,
+      ));
+    }
+  }
   let handler = arrow_handlers::from_dtype(dtype)?;
   handler.compress(&opt, &schema)
 }
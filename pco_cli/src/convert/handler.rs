@@ -0,0 +1,281 @@
+use std::cmp::min;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use arrow::array::Array;
+use arrow::csv::{Writer as CsvWriter, WriterBuilder as CsvWriterBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::ipc::writer::FileWriter as IpcFileWriter;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+
+use crate::convert::{ConvertOpt, OutputFormat};
+use crate::core_handlers::CoreHandlerImpl;
+use crate::dtypes::PcoNumber;
+use crate::row_reader::RowReader;
+
+const BATCH_N: usize = 1 << 16;
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+// We write the header before knowing the count of numbers, so we pad it to
+// a fixed size that fits any count and rewrite it at the end.
+const NPY_HEADER_SIZE: usize = 128;
+
+pub trait ConvertHandler {
+  fn convert(&self, opt: &ConvertOpt) -> Result<()>;
+}
+
+impl<T: PcoNumber> ConvertHandler for CoreHandlerImpl<T> {
+  fn convert(&self, opt: &ConvertOpt) -> Result<()> {
+    let mut reader = RowReader::<T>::new(&opt.input)?;
+    let schema = Arc::new(Schema::new(vec![Field::new(
+      &opt.col_name,
+      T::ARROW_DTYPE,
+      reader.has_nulls(),
+    )]));
+    let mut output = Output::new(opt, schema.clone())?;
+    let mut remaining_limit = opt.limit.unwrap_or(usize::MAX);
+
+    while remaining_limit > 0 {
+      let Some(array) = reader.next_batch(min(BATCH_N, remaining_limit))? else {
+        break;
+      };
+      remaining_limit -= array.len();
+      let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)])?;
+      output.write(&batch)?;
+    }
+
+    output.close()
+  }
+}
+
+fn open_output(opt: &ConvertOpt) -> Result<File> {
+  let mut open_options = OpenOptions::new();
+  open_options.write(true);
+  if opt.overwrite {
+    open_options.create(true);
+    open_options.truncate(true);
+  } else {
+    open_options.create_new(true);
+  }
+  Ok(open_options.open(&opt.output)?)
+}
+
+enum Output {
+  Csv(Box<CsvWriter<BufWriter<File>>>),
+  Parquet(ArrowWriter<File>),
+  Arrow(IpcFileWriter<BufWriter<File>>),
+  Npy(NpyWriter),
+}
+
+impl Output {
+  fn new(opt: &ConvertOpt, schema: SchemaRef) -> Result<Self> {
+    let format = opt.format()?;
+    // check these before creating the output file
+    let npy_descr = if format == OutputFormat::Npy {
+      if schema.field(0).is_nullable() {
+        return Err(anyhow!(
+          "NumPy arrays cannot represent nulls; use another output format"
+        ));
+      }
+      Some(npy_descr(schema.field(0).data_type())?)
+    } else {
+      None
+    };
+
+    let file = open_output(opt)?;
+    let output = match format {
+      OutputFormat::Csv => Output::Csv(Box::new(
+        CsvWriterBuilder::new()
+          .with_header(!opt.csv_no_header)
+          .build(BufWriter::new(file)),
+      )),
+      OutputFormat::Parquet => {
+        let properties = WriterProperties::builder()
+          .set_compression(opt.parquet_compression)
+          .build();
+        Output::Parquet(ArrowWriter::try_new(
+          file,
+          schema,
+          Some(properties),
+        )?)
+      }
+      OutputFormat::Arrow => Output::Arrow(IpcFileWriter::try_new(
+        BufWriter::new(file),
+        &schema,
+      )?),
+      OutputFormat::Npy => Output::Npy(NpyWriter::new(file, npy_descr.unwrap())?),
+    };
+    Ok(output)
+  }
+
+  fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+    match self {
+      Output::Csv(writer) => writer.write(batch)?,
+      Output::Parquet(writer) => writer.write(batch)?,
+      Output::Arrow(writer) => writer.write(batch)?,
+      Output::Npy(writer) => writer.write(batch.column(0).as_ref())?,
+    }
+    Ok(())
+  }
+
+  fn close(self) -> Result<()> {
+    match self {
+      Output::Csv(writer) => {
+        writer.into_inner().flush()?;
+      }
+      Output::Parquet(writer) => {
+        writer.close()?;
+      }
+      Output::Arrow(writer) => {
+        // this finishes the IPC file before returning the inner writer
+        writer.into_inner()?.flush()?;
+      }
+      Output::Npy(writer) => writer.close()?,
+    }
+    Ok(())
+  }
+}
+
+fn npy_descr(dtype: &DataType) -> Result<String> {
+  let type_str = match dtype {
+    DataType::Int8 => "i1",
+    DataType::Int16 => "i2",
+    DataType::Int32 => "i4",
+    DataType::Int64 => "i8",
+    DataType::UInt8 => "u1",
+    DataType::UInt16 => "u2",
+    DataType::UInt32 => "u4",
+    DataType::UInt64 => "u8",
+    DataType::Float16 => "f2",
+    DataType::Float32 => "f4",
+    DataType::Float64 => "f8",
+    other => return Err(anyhow!("{} has no NumPy equivalent", other)),
+  };
+  let byte_order = if cfg!(target_endian = "little") {
+    '<'
+  } else {
+    '>'
+  };
+  Ok(format!("{}{}", byte_order, type_str))
+}
+
+fn npy_header(descr: &str, n: usize) -> Vec<u8> {
+  let dict = format!(
+    "{{'descr': '{}', 'fortran_order': False, 'shape': ({},), }}",
+    descr, n,
+  );
+  let header_len = NPY_HEADER_SIZE - NPY_MAGIC.len() - 4;
+  let mut res = NPY_MAGIC.to_vec();
+  // format version 1.0
+  res.extend([1, 0]);
+  res.extend((header_len as u16).to_le_bytes());
+  res.extend(format!("{:<width$}\n", dict, width = header_len - 1).bytes());
+  res
+}
+
+struct NpyWriter {
+  dst: BufWriter<File>,
+  descr: String,
+  n: usize,
+}
+
+impl NpyWriter {
+  fn new(file: File, descr: String) -> Result<Self> {
+    let mut dst = BufWriter::new(file);
+    dst.write_all(&npy_header(&descr, 0))?;
+    Ok(Self { dst, descr, n: 0 })
+  }
+
+  fn write(&mut self, array: &dyn Array) -> Result<()> {
+    let data = array.to_data();
+    let width = data.data_type().primitive_width().unwrap();
+    let start = data.offset() * width;
+    let end = start + data.len() * width;
+    self
+      .dst
+      .write_all(&data.buffers()[0].as_slice()[start..end])?;
+    self.n += data.len();
+    Ok(())
+  }
+
+  fn close(mut self) -> Result<()> {
+    self.dst.seek(SeekFrom::Start(0))?;
+    self.dst.write_all(&npy_header(&self.descr, self.n))?;
+    self.dst.flush()?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+  use std::path::PathBuf;
+
+  use arrow::array::Int32Array;
+  use arrow::ipc::reader::FileReader as IpcFileReader;
+  use clap::Parser;
+  use pco::standalone::simple_compress_nullable;
+  use pco::ChunkConfig;
+
+  use super::*;
+
+  fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+      "pco_convert_{}_{}",
+      std::process::id(),
+      name
+    ))
+  }
+
+  #[test]
+  fn test_convert_nulls() -> Result<()> {
+    let rows = (0..1000)
+      .map(|i| if i % 7 == 0 { None } else { Some(i) })
+      .collect::<Vec<_>>();
+    let values = rows.iter().flatten().copied().collect::<Vec<i32>>();
+    let validity = rows.iter().map(Option::is_some).collect::<Vec<_>>();
+    let input = temp_path("nulls.pco");
+    fs::write(
+      &input,
+      simple_compress_nullable(&values, &validity, &ChunkConfig::default())?,
+    )?;
+
+    let output = temp_path("nulls.arrow");
+    let opt = ConvertOpt::try_parse_from([
+      "convert",
+      "--overwrite",
+      input.to_str().unwrap(),
+      output.to_str().unwrap(),
+    ])?;
+    CoreHandlerImpl::<i32>::default().convert(&opt)?;
+    let reader = IpcFileReader::try_new(File::open(&output)?, None)?;
+    assert!(reader.schema().field(0).is_nullable());
+    let mut recovered = Vec::new();
+    for batch in reader {
+      let batch = batch?;
+      let array = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+      recovered.extend(array.iter());
+    }
+    assert_eq!(recovered, rows);
+
+    let npy_output = temp_path("nulls.npy");
+    let opt = ConvertOpt::try_parse_from([
+      "convert",
+      input.to_str().unwrap(),
+      npy_output.to_str().unwrap(),
+    ])?;
+    assert!(CoreHandlerImpl::<i32>::default().convert(&opt).is_err());
+    assert!(!npy_output.exists());
+
+    fs::remove_file(input)?;
+    fs::remove_file(output)?;
+    Ok(())
+  }
+}
@@ -0,0 +1,100 @@
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Read};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueEnum};
+use parquet::basic::Compression;
+
+use crate::{core_handlers, parse, utils};
+
+pub mod handler;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+  Csv,
+  Parquet,
+  /// Arrow IPC file format, also known as Feather V2.
+  Arrow,
+  /// NumPy .npy array.
+  Npy,
+}
+
+/// Convert a standalone .pco file into a different format.
+#[derive(Clone, Debug, Parser)]
+pub struct ConvertOpt {
+  /// Overwrite the output path (if it exists) instead of failing.
+  #[arg(long)]
+  pub overwrite: bool,
+  /// Format to write.
+  /// If not specified, this is inferred from the output path's extension:
+  /// .csv, .parquet, .arrow/.feather/.ipc, or .npy.
+  #[arg(long)]
+  pub to: Option<OutputFormat>,
+  /// Name of the column in CSV, Parquet, and Arrow IPC outputs.
+  #[arg(long, default_value = "c0")]
+  pub col_name: String,
+  /// Whether to omit the header row in CSV outputs.
+  #[arg(long)]
+  pub csv_no_header: bool,
+  /// Compression codec for Parquet outputs, e.g. uncompressed, snappy, or
+  /// zstd3.
+  #[arg(long, value_parser = parse::parquet_compression, default_value = "zstd")]
+  pub parquet_compression: Compression,
+  /// Maximum count of numbers to convert.
+  #[arg(long)]
+  pub limit: Option<usize>,
+
+  /// Input .pco path to read from.
+  pub input: PathBuf,
+  /// Output path to write to.
+  pub output: PathBuf,
+}
+
+impl ConvertOpt {
+  pub fn format(&self) -> Result<OutputFormat> {
+    if let Some(format) = self.to {
+      return Ok(format);
+    }
+
+    let extension = self
+      .output
+      .extension()
+      .and_then(|extension| extension.to_str())
+      .map(|extension| extension.to_lowercase());
+    match extension.as_deref() {
+      Some("csv") => Ok(OutputFormat::Csv),
+      Some("parquet") => Ok(OutputFormat::Parquet),
+      Some("arrow") | Some("feather") | Some("ipc") => Ok(OutputFormat::Arrow),
+      Some("npy") => Ok(OutputFormat::Npy),
+      _ => Err(anyhow!(
+        "could not infer output format from {:?}; please specify --to",
+        self.output,
+      )),
+    }
+  }
+}
+
+pub fn convert(opt: ConvertOpt) -> Result<()> {
+  // fail early on unknown formats, before reading anything
+  opt.format()?;
+
+  let mut initial_bytes = vec![0; pco::standalone::guarantee::header_size() + 1];
+  match OpenOptions::new()
+    .read(true)
+    .open(&opt.input)?
+    .read_exact(&mut initial_bytes)
+  {
+    Ok(()) => (),
+    Err(e) if matches!(e.kind(), ErrorKind::UnexpectedEof) => (),
+    other => other?,
+  };
+  let Some(dtype) = utils::get_standalone_dtype(&initial_bytes)? else {
+    return Err(anyhow!(
+      "{:?} has no chunks, so its data type is unknown",
+      opt.input,
+    ));
+  };
+  let handler = core_handlers::from_dtype(dtype);
+  handler.convert(&opt)
+}
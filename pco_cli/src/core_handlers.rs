@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 use pco::data_types::NumberType;
 use pco::match_number_enum;
 
+use crate::convert::handler::ConvertHandler;
 use crate::decompress::handler::DecompressHandler;
 use crate::dtypes::PcoNumber;
 use crate::inspect::handler::InspectHandler;
@@ -22,7 +23,7 @@ pub fn from_dtype(dtype: NumberType) -> Box<dyn CoreHandler> {
   )
 }
 
-pub trait CoreHandler: DecompressHandler + InspectHandler {}
+pub trait CoreHandler: ConvertHandler + DecompressHandler + InspectHandler {}
 
 #[derive(Clone, Debug, Default)]
 pub struct CoreHandlerImpl<T> {
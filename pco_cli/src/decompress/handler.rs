@@ -1,23 +1,21 @@
 use std::cmp::min;
-use std::fs::OpenOptions;
 use std::io::Write;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use arrow::array::PrimitiveArray;
 use arrow::csv::WriterBuilder as CsvWriterBuilder;
-use arrow::datatypes::{ArrowPrimitiveType, Field, Schema};
+use arrow::datatypes::{Field, Schema};
 use arrow::record_batch::RecordBatch;
 
-use better_io::BetterBufReader;
-use pco::standalone::{FileDecompressor, MaybeChunkDecompressor};
-use pco::FULL_BATCH_N;
-
 use crate::core_handlers::CoreHandlerImpl;
 use crate::decompress::DecompressOpt;
 use crate::decompress::OutputKind::*;
 use crate::dtypes::PcoNumber;
+use crate::row_reader::RowReader;
+
+const BATCH_N: usize = 1 << 16;
 
 pub trait DecompressHandler {
   fn decompress(&self, opt: &DecompressOpt) -> Result<()>;
@@ -25,37 +23,21 @@ pub trait DecompressHandler {
 
 impl<T: PcoNumber> DecompressHandler for CoreHandlerImpl<T> {
   fn decompress(&self, opt: &DecompressOpt) -> Result<()> {
-    let file = OpenOptions::new().read(true).open(&opt.path)?;
-    let src = BetterBufReader::from_read_simple(file);
-    let (fd, mut src) = FileDecompressor::new(src)?;
-
+    let mut reader = RowReader::<T>::new(&opt.path)?;
+    if matches!(opt.output, Binary) && reader.has_nulls() {
+      return Err(anyhow!(
+        "binary output cannot represent nulls; use txt output instead"
+      ));
+    }
     let mut writer = new_column_writer::<T>(opt)?;
     let mut remaining_limit = opt.limit.unwrap_or(usize::MAX);
-    let mut nums = Vec::new();
-
-    loop {
-      if remaining_limit == 0 {
-        break;
-      }
 
-      if let MaybeChunkDecompressor::Some(mut cd) = fd.chunk_decompressor::<T, _>(src)? {
-        let n = cd.n();
-        let batch_size = min(n, remaining_limit);
-        // how many pco should decompress
-        let pco_size = (1 + batch_size / FULL_BATCH_N) * FULL_BATCH_N;
-        nums.resize(pco_size, T::default());
-        let _ = cd.decompress(&mut nums)?;
-        src = cd.into_src();
-        let arrow_nums = nums
-          .iter()
-          .take(batch_size)
-          .map(|&x| T::to_arrow_native(x))
-          .collect::<Vec<_>>();
-        writer.write(arrow_nums)?;
-        remaining_limit -= batch_size;
-      } else {
+    while remaining_limit > 0 {
+      let Some(array) = reader.next_batch(min(BATCH_N, remaining_limit))? else {
         break;
-      }
+      };
+      remaining_limit -= array.len();
+      writer.write(array)?;
     }
 
     writer.close()?;
@@ -73,7 +55,7 @@ fn new_column_writer<T: PcoNumber>(opt: &DecompressOpt) -> Result<Box<dyn Column
 }
 
 trait ColumnWriter<T: PcoNumber> {
-  fn write(&mut self, nums: Vec<<T::Arrow as ArrowPrimitiveType>::Native>) -> Result<()>;
+  fn write(&mut self, array: PrimitiveArray<T::Arrow>) -> Result<()>;
   fn close(&mut self) -> Result<()>;
 }
 
@@ -83,10 +65,10 @@ struct TxtWriter<T: PcoNumber> {
 }
 
 impl<T: PcoNumber> ColumnWriter<T> for TxtWriter<T> {
-  fn write(&mut self, arrow_natives: Vec<<T::Arrow as ArrowPrimitiveType>::Native>) -> Result<()> {
-    let schema = Schema::new(vec![Field::new("c0", T::ARROW_DTYPE, false)]);
-    let c0 = PrimitiveArray::<T::Arrow>::from_iter_values(arrow_natives);
-    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(c0)])?;
+  fn write(&mut self, array: PrimitiveArray<T::Arrow>) -> Result<()> {
+    // nulls are written as ""
+    let schema = Schema::new(vec![Field::new("c0", T::ARROW_DTYPE, true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)])?;
     let mut stdout_bytes = Vec::<u8>::new();
     {
       let mut writer = CsvWriterBuilder::new()
@@ -109,9 +91,9 @@ struct BinaryWriter<T: PcoNumber> {
 }
 
 impl<T: PcoNumber> ColumnWriter<T> for BinaryWriter<T> {
-  fn write(&mut self, arrow_natives: Vec<<T::Arrow as ArrowPrimitiveType>::Native>) -> Result<()> {
+  fn write(&mut self, array: PrimitiveArray<T::Arrow>) -> Result<()> {
     let mut out = std::io::stdout();
-    for &x in &arrow_natives {
+    for &x in array.values() {
       out.write_all(&T::arrow_native_to_bytes(x))?;
     }
     Ok(())
@@ -1,11 +1,16 @@
+#[cfg(feature = "full_bench")]
+use std::borrow::Cow;
 use std::{any, mem};
 
 use anyhow::anyhow;
 use anyhow::Result;
 use arrow::datatypes as arrow_dtypes;
 use arrow::datatypes::DataType as ArrowDataType;
-use arrow::datatypes::{ArrowPrimitiveType, DataType};
-use half::f16;
+use arrow::datatypes::{
+  i256, ArrowPrimitiveType, DataType, DECIMAL128_MAX_PRECISION, DECIMAL256_MAX_PRECISION,
+};
+use half::{bf16, f16};
+use parquet::data_type::FixedLenByteArray;
 use pco::data_types::{Number, NumberType};
 
 use crate::num_vec::NumVec;
@@ -39,7 +44,7 @@ pub trait Parquetable: Sized {
 pub trait QCompressable: Sized {
   type Qco: q_compress::data_types::NumberLike;
 
-  fn nums_to_qco(nums: &[Self]) -> &[Self::Qco];
+  fn nums_to_qco(nums: &[Self]) -> Cow<[Self::Qco]>;
   fn qco_to_nums(vec: Vec<Self::Qco>) -> Vec<Self>;
 }
 
@@ -50,9 +55,13 @@ pub trait TurboPforable: Sized {
 }
 
 #[cfg(feature = "full_bench")]
-pub trait PcoNumber:
-  Number + Parquetable + QCompressable + TurboPforable + vortex::dtype::NativePType
-{
+pub trait Vortexable: Sized {
+  fn to_vortex(nums: &[Self]) -> vortex::arrays::PrimitiveArray;
+  fn extend_from_vortex(array: &vortex::arrays::PrimitiveArray, dst: &mut Vec<Self>);
+}
+
+#[cfg(feature = "full_bench")]
+pub trait PcoNumber: Number + Parquetable + QCompressable + TurboPforable + Vortexable {
   const ARROW_DTYPE: DataType;
 
   type Arrow: ArrowPrimitiveType;
@@ -102,19 +111,25 @@ macro_rules! parquetable {
   };
 }
 
-macro_rules! trivial {
-  ($t: ty, $name: ident, $p: ty) => {
+macro_rules! trivial_qcompressable {
+  ($t: ty) => {
     #[cfg(feature = "full_bench")]
     impl QCompressable for $t {
       type Qco = $t;
 
-      fn nums_to_qco(nums: &[Self]) -> &[Self::Qco] {
-        nums
+      fn nums_to_qco(nums: &[Self]) -> Cow<[Self::Qco]> {
+        Cow::Borrowed(nums)
       }
       fn qco_to_nums(vec: Vec<Self::Qco>) -> Vec<Self> {
         vec
       }
     }
+  };
+}
+
+macro_rules! trivial {
+  ($t: ty, $name: ident, $p: ty) => {
+    trivial_qcompressable!($t);
 
     impl PcoNumber for $t {
       const ARROW_DTYPE: DataType = <$p as ArrowPrimitiveType>::DATA_TYPE;
@@ -169,8 +184,8 @@ parquetable!(f64, parquet::data_type::DoubleType, "DOUBLE");
 parquetable!(i32, parquet::data_type::Int32Type, "INT32");
 parquetable!(i64, parquet::data_type::Int64Type, "INT64");
 
-// For 16-bit types, we have no way to transmute into parquet types, so we need
-// to copy.
+// For 8- and 16-bit types, we have no way to transmute into parquet types, so
+// we need to copy.
 impl Parquetable for f16 {
   const PARQUET_DTYPE_STR: &'static str = "FLOAT";
   const TRANSMUTABLE: bool = false;
@@ -186,6 +201,45 @@ impl Parquetable for f16 {
   }
 }
 
+impl Parquetable for bf16 {
+  const PARQUET_DTYPE_STR: &'static str = "FLOAT";
+  const TRANSMUTABLE: bool = false;
+  type Parquet = parquet::data_type::FloatType;
+
+  fn copy_nums_to_parquet(nums: &[Self]) -> Vec<f32> {
+    nums.iter().map(|x| x.to_f32()).collect()
+  }
+  fn parquet_to_nums(vec: Vec<f32>) -> Vec<Self> {
+    vec.into_iter().map(bf16::from_f32).collect()
+  }
+}
+
+impl Parquetable for i8 {
+  const PARQUET_DTYPE_STR: &'static str = "INT32";
+  const TRANSMUTABLE: bool = false;
+  type Parquet = parquet::data_type::Int32Type;
+
+  fn copy_nums_to_parquet(nums: &[Self]) -> Vec<i32> {
+    nums.iter().map(|&x| x as i32).collect()
+  }
+  fn parquet_to_nums(vec: Vec<i32>) -> Vec<Self> {
+    vec.into_iter().map(|x| x as i8).collect()
+  }
+}
+
+impl Parquetable for u8 {
+  const PARQUET_DTYPE_STR: &'static str = "INT32";
+  const TRANSMUTABLE: bool = false;
+  type Parquet = parquet::data_type::Int32Type;
+
+  fn copy_nums_to_parquet(nums: &[Self]) -> Vec<i32> {
+    nums.iter().map(|&x| x as i32).collect()
+  }
+  fn parquet_to_nums(vec: Vec<i32>) -> Vec<Self> {
+    vec.into_iter().map(|x| x as u8).collect()
+  }
+}
+
 impl Parquetable for i16 {
   const PARQUET_DTYPE_STR: &'static str = "INT32";
   const TRANSMUTABLE: bool = false;
@@ -243,18 +297,119 @@ impl Parquetable for u64 {
   }
 }
 
+// Parquet stores 128-bit decimals as big-endian fixed length byte arrays.
+macro_rules! parquetable_128 {
+  ($t: ty) => {
+    impl Parquetable for $t {
+      const PARQUET_DTYPE_STR: &'static str = "FIXED_LEN_BYTE_ARRAY (16)";
+      const TRANSMUTABLE: bool = false;
+      type Parquet = parquet::data_type::FixedLenByteArrayType;
+
+      fn copy_nums_to_parquet(nums: &[Self]) -> Vec<FixedLenByteArray> {
+        nums
+          .iter()
+          .map(|x| FixedLenByteArray::from(x.to_be_bytes().to_vec()))
+          .collect()
+      }
+      fn parquet_to_nums(vec: Vec<FixedLenByteArray>) -> Vec<Self> {
+        vec
+          .into_iter()
+          .map(|x| <$t>::from_be_bytes(x.data().try_into().unwrap()))
+          .collect()
+      }
+    }
+  };
+}
+
+parquetable_128!(i128);
+parquetable_128!(u128);
+
 #[cfg(feature = "full_bench")]
 impl QCompressable for f16 {
   type Qco = u16;
 
-  fn nums_to_qco(nums: &[Self]) -> &[Self::Qco] {
-    unsafe { mem::transmute(nums) }
+  fn nums_to_qco(nums: &[Self]) -> Cow<[Self::Qco]> {
+    Cow::Borrowed(unsafe { mem::transmute::<&[f16], &[u16]>(nums) })
   }
   fn qco_to_nums(vec: Vec<Self::Qco>) -> Vec<Self> {
     unsafe { mem::transmute(vec) }
   }
 }
 
+#[cfg(feature = "full_bench")]
+impl QCompressable for bf16 {
+  type Qco = u16;
+
+  fn nums_to_qco(nums: &[Self]) -> Cow<[Self::Qco]> {
+    Cow::Borrowed(unsafe { mem::transmute::<&[bf16], &[u16]>(nums) })
+  }
+  fn qco_to_nums(vec: Vec<Self::Qco>) -> Vec<Self> {
+    unsafe { mem::transmute(vec) }
+  }
+}
+
+// q_compress has no 8-bit types, so we widen to 16 bits.
+macro_rules! qcompressable_widened {
+  ($t: ty, $qco: ty) => {
+    #[cfg(feature = "full_bench")]
+    impl QCompressable for $t {
+      type Qco = $qco;
+
+      fn nums_to_qco(nums: &[Self]) -> Cow<[Self::Qco]> {
+        Cow::Owned(nums.iter().map(|&x| x as $qco).collect())
+      }
+      fn qco_to_nums(vec: Vec<Self::Qco>) -> Vec<Self> {
+        vec.into_iter().map(|x| x as $t).collect()
+      }
+    }
+  };
+}
+
+qcompressable_widened!(i8, i16);
+qcompressable_widened!(u8, u16);
+
+trivial_qcompressable!(i128);
+trivial_qcompressable!(u128);
+
+// Arrow's Decimal128 type has a nonzero scale by default, but we want integers.
+impl PcoNumber for i128 {
+  const ARROW_DTYPE: DataType = DataType::Decimal128(DECIMAL128_MAX_PRECISION, 0);
+
+  type Arrow = arrow_dtypes::Decimal128Type;
+
+  fn to_arrow_native(self) -> <Self::Arrow as ArrowPrimitiveType>::Native {
+    self
+  }
+
+  fn make_num_vec(nums: Vec<Self>) -> NumVec {
+    NumVec::I128(nums)
+  }
+
+  fn arrow_native_to_bytes(x: <Self::Arrow as ArrowPrimitiveType>::Native) -> Vec<u8> {
+    x.to_le_bytes().to_vec()
+  }
+}
+
+// Arrow has no unsigned 128-bit type, so we use the smallest decimal type that
+// can hold every u128.
+impl PcoNumber for u128 {
+  const ARROW_DTYPE: DataType = DataType::Decimal256(DECIMAL256_MAX_PRECISION, 0);
+
+  type Arrow = arrow_dtypes::Decimal256Type;
+
+  fn to_arrow_native(self) -> <Self::Arrow as ArrowPrimitiveType>::Native {
+    i256::from_parts(self, 0)
+  }
+
+  fn make_num_vec(nums: Vec<Self>) -> NumVec {
+    NumVec::U128(nums)
+  }
+
+  fn arrow_native_to_bytes(x: <Self::Arrow as ArrowPrimitiveType>::Native) -> Vec<u8> {
+    x.to_parts().0.to_le_bytes().to_vec()
+  }
+}
+
 impl PcoNumber for f16 {
   const ARROW_DTYPE: DataType = arrow_dtypes::Float16Type::DATA_TYPE;
 
@@ -273,11 +428,32 @@ impl PcoNumber for f16 {
   }
 }
 
+// Arrow has no bfloat16 type, so we widen to f32, which is lossless.
+impl PcoNumber for bf16 {
+  const ARROW_DTYPE: DataType = arrow_dtypes::Float32Type::DATA_TYPE;
+
+  type Arrow = arrow_dtypes::Float32Type;
+
+  fn to_arrow_native(self) -> <Self::Arrow as ArrowPrimitiveType>::Native {
+    self.to_f32()
+  }
+
+  fn make_num_vec(nums: Vec<Self>) -> NumVec {
+    NumVec::BF16(nums)
+  }
+
+  fn arrow_native_to_bytes(x: <Self::Arrow as ArrowPrimitiveType>::Native) -> Vec<u8> {
+    bf16::from_f32(x).to_le_bytes().to_vec()
+  }
+}
+
 trivial!(f32, F32, arrow_dtypes::Float32Type);
 trivial!(f64, F64, arrow_dtypes::Float64Type);
+trivial!(i8, I8, arrow_dtypes::Int8Type);
 trivial!(i16, I16, arrow_dtypes::Int16Type);
 trivial!(i32, I32, arrow_dtypes::Int32Type);
 trivial!(i64, I64, arrow_dtypes::Int64Type);
+trivial!(u8, U8, arrow_dtypes::UInt8Type);
 trivial!(u16, U16, arrow_dtypes::UInt16Type);
 trivial!(u32, U32, arrow_dtypes::UInt32Type);
 trivial!(u64, U64, arrow_dtypes::UInt64Type);
@@ -289,21 +465,25 @@ extra_arrow!(i64, arrow_dtypes::TimestampMicrosecondType);
 extra_arrow!(i64, arrow_dtypes::TimestampNanosecondType);
 extra_arrow!(i32, arrow_dtypes::Date32Type);
 extra_arrow!(i64, arrow_dtypes::Date64Type);
+extra_arrow!(i128, arrow_dtypes::Decimal128Type);
 
 pub fn from_arrow(arrow_dtype: &ArrowDataType) -> Result<NumberType> {
   let res = match arrow_dtype {
     ArrowDataType::Float16 => NumberType::F16,
     ArrowDataType::Float32 => NumberType::F32,
     ArrowDataType::Float64 => NumberType::F64,
+    ArrowDataType::Int8 => NumberType::I8,
     ArrowDataType::Int16 => NumberType::I16,
     ArrowDataType::Int32 => NumberType::I32,
     ArrowDataType::Int64 => NumberType::I64,
+    ArrowDataType::UInt8 => NumberType::U8,
     ArrowDataType::UInt16 => NumberType::U16,
     ArrowDataType::UInt32 => NumberType::U32,
     ArrowDataType::UInt64 => NumberType::U64,
     ArrowDataType::Timestamp(_, _) => NumberType::I64,
     ArrowDataType::Date32 => NumberType::I32,
     ArrowDataType::Date64 => NumberType::I64,
+    ArrowDataType::Decimal128(_, _) => NumberType::I128,
     _ => {
       return Err(anyhow!(
         "unable to convert arrow dtype {:?} to pco",
@@ -319,12 +499,17 @@ pub fn to_arrow(dtype: NumberType) -> ArrowDataType {
     NumberType::F16 => ArrowDataType::Float16,
     NumberType::F32 => ArrowDataType::Float32,
     NumberType::F64 => ArrowDataType::Float64,
+    NumberType::I8 => ArrowDataType::Int8,
     NumberType::I16 => ArrowDataType::Int16,
     NumberType::I32 => ArrowDataType::Int32,
     NumberType::I64 => ArrowDataType::Int64,
+    NumberType::U8 => ArrowDataType::UInt8,
     NumberType::U16 => ArrowDataType::UInt16,
     NumberType::U32 => ArrowDataType::UInt32,
     NumberType::U64 => ArrowDataType::UInt64,
+    NumberType::I128 => i128::ARROW_DTYPE,
+    NumberType::U128 => u128::ARROW_DTYPE,
+    NumberType::BF16 => bf16::ARROW_DTYPE,
     other => panic!(
       "number type {:?} not yet supported in pco_cli",
       other
@@ -6,8 +6,8 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Result};
 use arrow::array::{
-  ArrayData, ArrayRef, Float16Array, Float32Array, Float64Array, Int16Array, Int32Array,
-  Int64Array, UInt16Array, UInt32Array, UInt64Array,
+  ArrayData, ArrayRef, Decimal128Array, Float16Array, Float32Array, Float64Array, Int16Array,
+  Int32Array, Int64Array, Int8Array, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
 };
 use arrow::buffer::Buffer;
 use arrow::csv;
@@ -15,7 +15,7 @@ use arrow::csv::Reader as CsvReader;
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatchReader;
 use clap::Parser;
-use half::f16;
+use half::{bf16, f16};
 use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
 use parquet::arrow::{ArrowSchemaConverter, ProjectionMask};
 
@@ -182,8 +182,14 @@ fn infer_csv_schema(col_opt: &InputColumnOpt, file_opt: &InputFileOpt) -> Result
   let mut fields = Vec::new();
   for (col_idx, field) in inferred_schema.fields().iter().enumerate() {
     let new_field = match (&col_opt.col_name, &col_opt.col_idx) {
-      (Some(name), None) if name == field.name() => Field::new(name, dtype.clone(), false),
-      (None, Some(idx)) if *idx == col_idx => Field::new(field.name(), dtype.clone(), false),
+      (Some(name), None) if name == field.name() => {
+        Field::new(name, dtype.clone(), field.is_nullable())
+      }
+      (None, Some(idx)) if *idx == col_idx => Field::new(
+        field.name(),
+        dtype.clone(),
+        field.is_nullable(),
+      ),
       _ => field.as_ref().clone(),
     };
     fields.push(new_field);
@@ -419,16 +425,27 @@ impl PcoColumnReader {
     use NumberType::*;
 
     let compressed = fs::read(&self.col_path)?;
-    let array: ArrayRef = match self.dtype {
+    // bf16 columns appear as f32 in the schema, so we defer to the file's own
+    // number type
+    let dtype = utils::get_standalone_dtype(&compressed)?.unwrap_or(self.dtype);
+    let array: ArrayRef = match dtype {
       F16 => Arc::new(Float16Array::from(simple_decompress::<f16>(
         &compressed,
       )?)),
+      BF16 => Arc::new(Float32Array::from_iter_values(
+        simple_decompress::<bf16>(&compressed)?
+          .into_iter()
+          .map(bf16::to_f32),
+      )),
       F32 => Arc::new(Float32Array::from(simple_decompress::<f32>(
         &compressed,
       )?)),
       F64 => Arc::new(Float64Array::from(simple_decompress::<f64>(
         &compressed,
       )?)),
+      I8 => Arc::new(Int8Array::from(simple_decompress::<i8>(
+        &compressed,
+      )?)),
       I16 => Arc::new(Int16Array::from(simple_decompress::<i16>(
         &compressed,
       )?)),
@@ -438,6 +455,13 @@ impl PcoColumnReader {
       I64 => Arc::new(Int64Array::from(simple_decompress::<i64>(
         &compressed,
       )?)),
+      I128 => Arc::new(
+        Decimal128Array::from(simple_decompress::<i128>(&compressed)?)
+          .with_data_type(dtypes::to_arrow(I128)),
+      ),
+      U8 => Arc::new(UInt8Array::from(simple_decompress::<u8>(
+        &compressed,
+      )?)),
       U16 => Arc::new(UInt16Array::from(simple_decompress::<u16>(
         &compressed,
       )?)),
@@ -470,3 +494,28 @@ impl Iterator for PcoColumnReader {
     Some(self.get_array())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use pco::standalone::simpler_compress;
+
+  use crate::dtypes::PcoNumber;
+
+  use super::*;
+
+  #[test]
+  fn test_u128_pco_field() -> Result<()> {
+    let path = std::env::temp_dir().join(format!(
+      "pco_input_{}_u128.pco",
+      std::process::id()
+    ));
+    fs::write(
+      &path,
+      simpler_compress(&[1_u128 << 100, 7], 8)?,
+    )?;
+    let field = get_pco_field(&path)?.unwrap();
+    assert_eq!(field.data_type(), &u128::ARROW_DTYPE);
+    fs::remove_file(path)?;
+    Ok(())
+  }
+}
@@ -53,6 +53,10 @@ pub struct ChunkSummary {
   n: usize,
   mode: String,
   delta_encoding: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  max_abs_error: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  significant_bits: Option<u32>,
   // using BTreeMaps to preserve ordering
   latent_vars: BTreeMap<String, LatentVarSummary>,
 }
@@ -183,6 +187,8 @@ impl<T: PcoNumber> InspectHandler for CoreHandlerImpl<T> {
         n: chunk_ns[idx],
         mode: format!("{:?}", meta.mode),
         delta_encoding: format!("{:?}", meta.delta_encoding),
+        max_abs_error: meta.error_bound.and_then(|bound| bound.max_abs_error()),
+        significant_bits: meta.error_bound.and_then(|bound| bound.significant_bits()),
         latent_vars,
       });
     }
@@ -7,6 +7,7 @@ mod arrow_handlers;
 mod bench;
 mod chunk_config_opt;
 mod compress;
+mod convert;
 mod core_handlers;
 mod decompress;
 mod dtypes;
@@ -15,6 +16,7 @@ mod inspect;
 pub mod num_vec;
 mod opt;
 mod parse;
+mod row_reader;
 mod utils;
 
 fn main() -> Result<()> {
@@ -22,6 +24,7 @@ fn main() -> Result<()> {
   match opt {
     Opt::Bench(bench_opt) => bench::bench(bench_opt),
     Opt::Compress(compress_opt) => compress::compress(compress_opt),
+    Opt::Convert(convert_opt) => convert::convert(convert_opt),
     Opt::Decompress(decompress_opt) => decompress::decompress(decompress_opt),
     Opt::Inspect(inspect_opt) => inspect::inspect(inspect_opt),
   }
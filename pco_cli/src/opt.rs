@@ -2,11 +2,12 @@ use clap::{Parser, Subcommand};
 
 use crate::bench::BenchOpt;
 use crate::compress::CompressOpt;
+use crate::convert::ConvertOpt;
 use crate::decompress::DecompressOpt;
 use crate::inspect::InspectOpt;
 
 #[derive(Clone, Debug, Parser)]
-#[command(about = "compress, decompress, and inspect .pco files")]
+#[command(about = "compress, decompress, convert, and inspect .pco files")]
 pub struct OptWrapper {
   #[command(subcommand)]
   pub opt: Opt,
@@ -16,6 +17,7 @@ pub struct OptWrapper {
 pub enum Opt {
   Bench(BenchOpt),
   Compress(CompressOpt),
+  Convert(ConvertOpt),
   Decompress(DecompressOpt),
   Inspect(InspectOpt),
 }
@@ -1,5 +1,6 @@
 use anyhow::anyhow;
-use arrow::datatypes::{DataType, TimeUnit};
+use arrow::datatypes::{DataType, TimeUnit, DECIMAL128_MAX_PRECISION};
+use parquet::basic::{Compression, ZstdLevel};
 
 use pco::{DeltaSpec, ModeSpec};
 
@@ -47,9 +48,15 @@ pub fn arrow_dtype(s: &str) -> anyhow::Result<DataType> {
     ("f16", DataType::Float16),
     ("f32", DataType::Float32),
     ("f64", DataType::Float64),
+    ("i8", DataType::Int8),
     ("i16", DataType::Int16),
     ("i32", DataType::Int32),
     ("i64", DataType::Int64),
+    (
+      "i128",
+      DataType::Decimal128(DECIMAL128_MAX_PRECISION, 0),
+    ),
+    ("u8", DataType::UInt8),
     ("u16", DataType::UInt16),
     ("u32", DataType::UInt32),
     ("u64", DataType::UInt64),
@@ -87,3 +94,23 @@ pub fn arrow_dtype(s: &str) -> anyhow::Result<DataType> {
       .collect::<Vec<_>>()
   ))
 }
+
+pub fn parquet_compression(s: &str) -> anyhow::Result<Compression> {
+  let res = match s.to_lowercase().as_str() {
+    "uncompressed" => Compression::UNCOMPRESSED,
+    "snappy" => Compression::SNAPPY,
+    _ => {
+      if let Some(zstd_level_str) = s.strip_prefix("zstd") {
+        let level = if zstd_level_str.is_empty() {
+          ZstdLevel::default()
+        } else {
+          ZstdLevel::try_new(zstd_level_str.parse::<i32>()?)?
+        };
+        Compression::ZSTD(level)
+      } else {
+        return Err(anyhow!("unknown parquet codec {}", s));
+      }
+    }
+  };
+  Ok(res)
+}
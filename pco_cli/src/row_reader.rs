@@ -0,0 +1,121 @@
+use std::cmp::min;
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use arrow::array::PrimitiveArray;
+use arrow::buffer::NullBuffer;
+
+use better_io::BetterBufReader;
+use pco::standalone::{FileDecompressor, MaybeChunkDecompressor, NumberReader};
+
+use crate::dtypes::PcoNumber;
+
+// Nullable files store their validity after all the chunks, and chunks don't
+// record their compressed size, so we skip through the chunks to find it.
+fn read_validity<T: PcoNumber>(path: &Path) -> Result<Option<Vec<bool>>> {
+  let file = OpenOptions::new().read(true).open(path)?;
+  let (fd, mut src) = FileDecompressor::new(BetterBufReader::from_read_simple(file))?;
+  if !fd.is_nullable() {
+    return Ok(None);
+  }
+
+  let rest = loop {
+    match fd.chunk_decompressor::<T, _>(src)? {
+      MaybeChunkDecompressor::Some(mut cd) => {
+        cd.skip(cd.n())?;
+        src = cd.into_src();
+      }
+      MaybeChunkDecompressor::EndOfData(rest) => break rest,
+    }
+  };
+  let (validity, _) = fd.read_validity(rest)?;
+  Ok(Some(validity))
+}
+
+/// Reads the rows of a standalone .pco file as Arrow arrays, including null
+/// slots if the file is nullable.
+pub struct RowReader<T: PcoNumber> {
+  numbers: NumberReader<T, BetterBufReader<File>>,
+  validity: Option<Vec<bool>>,
+  row_idx: usize,
+  buffer: Vec<T>,
+}
+
+impl<T: PcoNumber> RowReader<T> {
+  pub fn new(path: &Path) -> Result<Self> {
+    let validity = read_validity::<T>(path)?;
+    let file = OpenOptions::new().read(true).open(path)?;
+    let numbers = NumberReader::new(BetterBufReader::from_read_simple(file), 1)?;
+    Ok(Self {
+      numbers,
+      validity,
+      row_idx: 0,
+      buffer: Vec::new(),
+    })
+  }
+
+  pub fn has_nulls(&self) -> bool {
+    self
+      .validity
+      .as_ref()
+      .is_some_and(|validity| validity.contains(&false))
+  }
+
+  // reads up to n numbers into the buffer, returning the count read
+  fn read_numbers(&mut self, n: usize) -> Result<usize> {
+    self.buffer.resize(n, T::default());
+    Ok(self.numbers.read(&mut self.buffer)?)
+  }
+
+  /// Returns the next `max_n` rows, or fewer at the end of the file, or
+  /// `None` once every row has been read.
+  pub fn next_batch(&mut self, max_n: usize) -> Result<Option<PrimitiveArray<T::Arrow>>> {
+    let Some(validity) = &self.validity else {
+      let n = self.read_numbers(max_n)?;
+      if n == 0 {
+        return Ok(None);
+      }
+      let array = PrimitiveArray::<T::Arrow>::from_iter_values(
+        self.buffer[..n].iter().map(|&x| T::to_arrow_native(x)),
+      );
+      return Ok(Some(array.with_data_type(T::ARROW_DTYPE)));
+    };
+
+    let end = min(self.row_idx + max_n, validity.len());
+    let batch_validity = validity[self.row_idx..end].to_vec();
+    let n_valid = batch_validity.iter().filter(|&&is_valid| is_valid).count();
+    let is_mismatched = if batch_validity.is_empty() {
+      // every row has been read, so no numbers should remain
+      self.read_numbers(1)? > 0
+    } else {
+      self.read_numbers(n_valid)? < n_valid
+    };
+    if is_mismatched {
+      return Err(anyhow!(
+        "validity does not match the count of numbers in the file"
+      ));
+    }
+    if batch_validity.is_empty() {
+      return Ok(None);
+    }
+
+    let mut nums = self.buffer.iter();
+    let natives = batch_validity
+      .iter()
+      .map(|&is_valid| {
+        if is_valid {
+          T::to_arrow_native(*nums.next().unwrap())
+        } else {
+          Default::default()
+        }
+      })
+      .collect::<Vec<_>>();
+    let array = PrimitiveArray::<T::Arrow>::new(
+      natives.into(),
+      Some(NullBuffer::from(batch_validity)),
+    );
+    self.row_idx = end;
+    Ok(Some(array.with_data_type(T::ARROW_DTYPE)))
+  }
+}
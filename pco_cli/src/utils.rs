@@ -1,7 +1,7 @@
 use std::any;
 
 use anyhow::{anyhow, Result};
-use arrow::array::{ArrayRef, AsArray};
+use arrow::array::{Array, ArrayRef, AsArray};
 use arrow::datatypes::Schema;
 
 use pco::data_types::{Number, NumberType};
@@ -59,11 +59,16 @@ pub fn dtype_name<T: Number>() -> String {
     .to_string()
 }
 
+// Null slots hold arbitrary values, so we only return the non-null numbers.
 pub fn arrow_to_nums<P: ArrowNumber>(arrow_array: ArrayRef) -> Vec<P::Pco> {
-  arrow_array
-    .as_primitive::<P>()
-    .values()
-    .iter()
-    .map(|&x| P::native_to_pco(x))
-    .collect()
+  let primitive = arrow_array.as_primitive::<P>();
+  if primitive.null_count() == 0 {
+    primitive
+      .values()
+      .iter()
+      .map(|&x| P::native_to_pco(x))
+      .collect()
+  } else {
+    primitive.iter().flatten().map(P::native_to_pco).collect()
+  }
 }
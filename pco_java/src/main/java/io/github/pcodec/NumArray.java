@@ -5,8 +5,8 @@ package io.github.pcodec;
  *
  * The underlying representation is e.g. a long[] for the i64 data type. For
  * the data types where Java has no built in data type, we use integers of the
- * corresponding precision. For instance, an f16 array is represented as a
- * short[]. Under the hood, Pco transmutes data into the correct data type.
+ * corresponding precision. For instance, f16 and bf16 arrays are represented
+ * as short[]s. Under the hood, Pco transmutes data into the correct data type.
  */
 public class NumArray {
     public final Object nums;
@@ -26,6 +26,10 @@ public class NumArray {
         return NumberType.fromByte(numberTypeByte);
     }
 
+    public static NumArray i8Array(byte[] nums) {
+        return new NumArray(nums, NumberType.I8);
+    }
+
     public static NumArray i16Array(short[] nums) {
         return new NumArray(nums, NumberType.I16);
     }
@@ -38,6 +42,10 @@ public class NumArray {
         return new NumArray(nums, NumberType.I64);
     }
 
+    public static NumArray u8Array(byte[] nums) {
+        return new NumArray(nums, NumberType.U8);
+    }
+
     public static NumArray u16Array(short[] nums) {
         return new NumArray(nums, NumberType.U16);
     }
@@ -50,6 +58,10 @@ public class NumArray {
         return new NumArray(nums, NumberType.U64);
     }
 
+    public static NumArray bf16Array(short[] nums) {
+        return new NumArray(nums, NumberType.BF16);
+    }
+
     public static NumArray f16Array(short[] nums) {
         return new NumArray(nums, NumberType.F16);
     }
@@ -66,6 +78,13 @@ public class NumArray {
         return new IllegalStateException("Cannot cast pco NumArray of " + this.numberType() + " to " + numberType);
     }
 
+    public byte[] as_i8_array() throws IllegalStateException {
+        if (numberTypeByte == NumberType.I8.byte_) {
+            return (byte[]) this.nums;
+        }
+        throw invalidNumberType(NumberType.I8);
+    }
+
     public short[] as_i16_array() throws IllegalStateException {
         if (numberTypeByte == NumberType.I16.byte_) {
             return (short[]) this.nums;
@@ -87,6 +106,13 @@ public class NumArray {
         throw invalidNumberType(NumberType.I64);
     }
 
+    public byte[] as_u8_array() throws IllegalStateException {
+        if (numberTypeByte == NumberType.U8.byte_) {
+            return (byte[]) this.nums;
+        }
+        throw invalidNumberType(NumberType.U8);
+    }
+
     public short[] as_u16_array() throws IllegalStateException {
         if (numberTypeByte == NumberType.U16.byte_) {
             return (short[]) this.nums;
@@ -108,6 +134,13 @@ public class NumArray {
         throw invalidNumberType(NumberType.U64);
     }
 
+    public short[] as_bf16_array() throws IllegalStateException {
+        if (numberTypeByte == NumberType.BF16.byte_) {
+            return (short[]) this.nums;
+        }
+        throw invalidNumberType(NumberType.BF16);
+    }
+
     public short[] as_f16_array() throws IllegalStateException {
         if (numberTypeByte == NumberType.F16.byte_) {
             return (short[]) this.nums;
@@ -6,12 +6,15 @@ package io.github.pcodec;
  * Each number type has a corresponding unique byte.
  */
 public enum NumberType {
+    I8((byte) 11),
     I16((byte) 8),
     I32((byte) 3),
     I64((byte) 4),
+    BF16((byte) 14),
     F16((byte) 9),
     F32((byte) 5),
     F64((byte) 6),
+    U8((byte) 10),
     U16((byte) 7),
     U32((byte) 1),
     U64((byte) 2);
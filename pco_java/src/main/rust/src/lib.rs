@@ -8,12 +8,11 @@ mod result;
 mod traits;
 
 use crate::result::{Exception, ExceptionKind, Result};
-use crate::traits::JavaConversions;
+use crate::traits::{match_java_number_enum, JavaConversions};
 use jni::objects::{JClass, JObject, JPrimitiveArray, JValueGen};
 use jni::sys::*;
 use jni::JNIEnv;
 use pco::data_types::{Number, NumberType};
-use pco::match_number_enum;
 use pco::standalone::FileDecompressor;
 
 fn handle_result(env: &mut JNIEnv, result: Result<jobject>) -> jobject {
@@ -52,7 +51,7 @@ fn simple_compress_inner(
   let (j_src, number_type) = num_array::from_java(env, j_num_array)?;
   let chunk_config = config::from_java(env, j_chunk_config)?;
 
-  let compressed = match_number_enum!(number_type, NumberType<T> => {
+  let compressed = match_java_number_enum!(number_type, NumberType<T> => {
       let j_src = JPrimitiveArray::from(j_src);
       let len = env.get_array_length(&j_src)? as usize;
       let mut nums = Vec::with_capacity(len);
@@ -103,8 +102,15 @@ fn simple_decompress_inner(env: &mut JNIEnv, src: jbyteArray) -> Result<jobject>
 
   use pco::standalone::NumberTypeOrTermination::*;
   match maybe_number_type {
+    Known(number_type @ (NumberType::I128 | NumberType::U128)) => Err(Exception {
+      kind: ExceptionKind::Runtime,
+      msg: format!(
+        "pco number type {:?} is not supported in Java",
+        number_type,
+      ),
+    }),
     Known(number_type) => {
-      match_number_enum!(
+      match_java_number_enum!(
           number_type,
           NumberType<T> => {
             decompress_chunks::<T>(env, rest, file_decompressor)
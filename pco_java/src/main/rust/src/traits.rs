@@ -1,10 +1,37 @@
+#![allow(clippy::single_component_path_imports)]
+
 use crate::result::Result;
-use half::f16;
+use half::{bf16, f16};
 use jni::objects::{JPrimitiveArray, TypeArray};
 use jni::JNIEnv;
 use pco::data_types::Number;
 use std::mem;
 
+// Java has no 128-bit integer types, so we only dispatch over the number types
+// it supports.
+dtype_dispatch::build_dtype_macros!(
+  #[allow(unused_macros)]
+  define_java_number_enum,
+  match_java_number_enum,
+  Number,
+  {
+    BF16 => half::bf16,
+    F16 => half::f16,
+    F32 => f32,
+    F64 => f64,
+    I8 => i8,
+    I16 => i16,
+    I32 => i32,
+    I64 => i64,
+    U8 => u8,
+    U16 => u16,
+    U32 => u32,
+    U64 => u64,
+  },
+);
+
+pub(crate) use match_java_number_enum;
+
 pub trait JavaConversions: Number {
   type Java: TypeArray;
   fn get_region(env: &JNIEnv, src: &JPrimitiveArray<Self::Java>, dst: &mut [Self]) -> Result<()>;
@@ -61,6 +88,12 @@ macro_rules! transmute_impl {
   };
 }
 
+trivial_impl!(
+  i8,
+  get_byte_array_region,
+  new_byte_array,
+  set_byte_array_region
+);
 trivial_impl!(
   i16,
   get_short_array_region,
@@ -92,7 +125,9 @@ trivial_impl!(
   set_double_array_region
 );
 
+transmute_impl!(bf16, i16);
 transmute_impl!(f16, i16);
+transmute_impl!(u8, i8);
 transmute_impl!(u16, i16);
 transmute_impl!(u32, i32);
 transmute_impl!(u64, i64);
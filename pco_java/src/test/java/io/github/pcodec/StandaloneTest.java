@@ -22,6 +22,15 @@ public class StandaloneTest {
         assertArrayEquals(src, recovered.as_i64_array());
     }
 
+    @Test
+    public void testByteRecovery() {
+        byte[] src = { 1, -2, 3 };
+        NumArray numArray = NumArray.u8Array(src);
+        byte[] compressed = Standalone.simple_compress(numArray, new ChunkConfig());
+        NumArray recovered = Standalone.simple_decompress(compressed).get();
+        assertArrayEquals(src, recovered.as_u8_array());
+    }
+
     @Test
     public void testF16Recovery() {
         short[] src = { 1, 2, 3 };
@@ -31,6 +40,16 @@ public class StandaloneTest {
         assertArrayEquals(src, recovered.as_f16_array());
     }
 
+    @Test
+    public void testBf16Recovery() {
+        // 1.0, 2.0, and -3.0 as bfloat16 bits
+        short[] src = { 0x3f80, 0x4000, (short) 0xc040 };
+        NumArray numArray = NumArray.bf16Array(src);
+        byte[] compressed = Standalone.simple_compress(numArray, new ChunkConfig());
+        NumArray recovered = Standalone.simple_decompress(compressed).get();
+        assertArrayEquals(src, recovered.as_bf16_array());
+    }
+
     @Test
     public void testIllegalArgument() {
         short[] src = { 1, 2, 3 };
@@ -10,9 +10,9 @@ use pyo3::{pyfunction, wrap_pyfunction, Bound, PyObject, PyResult, Python};
 
 use pco::data_types::{Number, NumberType};
 use pco::standalone::FileDecompressor;
-use pco::{match_number_enum, standalone, ChunkConfig};
+use pco::{standalone, ChunkConfig};
 
-use crate::utils::pco_err_to_py;
+use crate::utils::{match_py_number_enum, pco_err_to_py};
 use crate::{utils, PyChunkConfig, PyProgress};
 
 fn simple_compress_generic<'py, T: Number + Element>(
@@ -49,7 +49,8 @@ pub fn register(m: &Bound<PyModule>) -> PyResult<()> {
   ///
   /// :param nums: numpy array to compress. This may have any shape.
   ///   However, it must be contiguous, and only the following data types are
-  ///   supported: float16, float32, float64, int16, int32, int64, uint16, uint32, uint64.
+  ///   supported: float16, float32, float64, int8, int16, int32, int64,
+  ///   uint8, uint16, uint32, uint64.
   /// :param config: a ChunkConfig object containing compression level and
   ///   other settings.
   ///
@@ -64,7 +65,7 @@ pub fn register(m: &Bound<PyModule>) -> PyResult<()> {
   ) -> PyResult<Bound<'py, PyBytes>> {
     let config: ChunkConfig = config.try_into()?;
     let number_type = utils::number_type_from_numpy(py, &nums.dtype())?;
-    match_number_enum!(
+    match_py_number_enum!(
       number_type,
       NumberType<T> => {
         simple_compress_generic(py, nums.downcast::<PyArray1<T>>()?, &config)
@@ -93,7 +94,7 @@ pub fn register(m: &Bound<PyModule>) -> PyResult<()> {
     dst: &Bound<PyUntypedArray>,
   ) -> PyResult<PyProgress> {
     let number_type = utils::number_type_from_numpy(py, &dst.dtype())?;
-    match_number_enum!(
+    match_py_number_enum!(
       number_type,
       NumberType<T> => {
         simple_decompress_into_generic(py, compressed, dst.downcast::<PyArray1<T>>()?)
@@ -123,8 +124,8 @@ pub fn register(m: &Bound<PyModule>) -> PyResult<()> {
       .map_err(pco_err_to_py)?;
     match maybe_number_type {
       Known(number_type) => {
-        match_number_enum!(
-          number_type,
+        match_py_number_enum!(
+          utils::check_numpy_supported(number_type)?,
           NumberType<T> => {
             let res = py
               .allow_threads(|| file_decompressor.simple_decompress::<T>(src))
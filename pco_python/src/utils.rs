@@ -1,17 +1,55 @@
+#![allow(clippy::single_component_path_imports)]
+
 use numpy::{PyArrayDescr, PyArrayDescrMethods};
-use pco::data_types::NumberType;
+use pco::data_types::{Number, NumberType};
 use pco::errors::PcoError;
 use pyo3::exceptions::{PyRuntimeError, PyTypeError};
 use pyo3::{Bound, PyErr, PyResult, Python};
 
+// Numpy has no 128-bit integer or bfloat16 types, so we only dispatch over the
+// number types it supports.
+dtype_dispatch::build_dtype_macros!(
+  define_py_number_enum,
+  match_py_number_enum,
+  Number,
+  {
+    F16 => half::f16,
+    F32 => f32,
+    F64 => f64,
+    I8 => i8,
+    I16 => i16,
+    I32 => i32,
+    I64 => i64,
+    U8 => u8,
+    U16 => u16,
+    U32 => u32,
+    U64 => u64,
+  },
+);
+
+pub(crate) use define_py_number_enum;
+pub(crate) use match_py_number_enum;
+
+pub fn check_numpy_supported(number_type: NumberType) -> PyResult<NumberType> {
+  match number_type {
+    NumberType::BF16 | NumberType::I128 | NumberType::U128 => Err(PyTypeError::new_err(format!(
+      "data type {:?} is not supported by numpy",
+      number_type,
+    ))),
+    _ => Ok(number_type),
+  }
+}
+
 pub fn core_dtype_from_str(s: &str) -> PyResult<NumberType> {
   match s.to_uppercase().as_str() {
     "F16" => Ok(NumberType::F16),
     "F32" => Ok(NumberType::F32),
     "F64" => Ok(NumberType::F64),
+    "I8" => Ok(NumberType::I8),
     "I16" => Ok(NumberType::I16),
     "I32" => Ok(NumberType::I32),
     "I64" => Ok(NumberType::I64),
+    "U8" => Ok(NumberType::U8),
     "U16" => Ok(NumberType::U16),
     "U32" => Ok(NumberType::U32),
     "U64" => Ok(NumberType::U64),
@@ -23,12 +61,16 @@ pub fn core_dtype_from_str(s: &str) -> PyResult<NumberType> {
 }
 
 pub fn number_type_from_numpy(py: Python, dtype: &Bound<PyArrayDescr>) -> PyResult<NumberType> {
-  let res = if dtype.is_equiv_to(&numpy::dtype::<u16>(py)) {
+  let res = if dtype.is_equiv_to(&numpy::dtype::<u8>(py)) {
+    NumberType::U8
+  } else if dtype.is_equiv_to(&numpy::dtype::<u16>(py)) {
     NumberType::U16
   } else if dtype.is_equiv_to(&numpy::dtype::<u32>(py)) {
     NumberType::U32
   } else if dtype.is_equiv_to(&numpy::dtype::<u64>(py)) {
     NumberType::U64
+  } else if dtype.is_equiv_to(&numpy::dtype::<i8>(py)) {
+    NumberType::I8
   } else if dtype.is_equiv_to(&numpy::dtype::<i16>(py)) {
     NumberType::I16
   } else if dtype.is_equiv_to(&numpy::dtype::<i32>(py)) {
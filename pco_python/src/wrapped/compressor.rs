@@ -7,9 +7,9 @@ use pyo3::{pyclass, pymethods, Bound, PyResult, Python};
 
 use pco::data_types::{Number, NumberType};
 use pco::wrapped::{ChunkCompressor, FileCompressor};
-use pco::{match_number_enum, ChunkConfig};
+use pco::ChunkConfig;
 
-use crate::utils::pco_err_to_py;
+use crate::utils::{match_py_number_enum, pco_err_to_py};
 use crate::{utils, PyChunkConfig};
 
 /// The top-level object for creating wrapped pcodec files.
@@ -64,7 +64,8 @@ impl PyFc {
   ///
   /// :param nums: numpy array to compress. This may have any shape.
   ///   However, it must be contiguous, and only the following data types are
-  ///   supported: float16, float32, float64, int16, int32, int64, uint16, uint32, uint64.
+  ///   supported: float16, float32, float64, int8, int16, int32, int64,
+  ///   uint8, uint16, uint32, uint64.
   /// :param config: a ChunkConfig object containing compression level and
   ///   other settings.
   ///
@@ -79,7 +80,7 @@ impl PyFc {
   ) -> PyResult<PyCc> {
     let config = config.try_into()?;
     let number_type = utils::number_type_from_numpy(py, &nums.dtype())?;
-    match_number_enum!(
+    match_py_number_enum!(
       number_type,
       NumberType<T> => {
         let cc = self.chunk_compressor_generic::<T>(py, nums.downcast::<PyArray1<T>>()?, &config)?;
@@ -1,6 +1,5 @@
 use numpy::{PyArray1, PyArrayMethods, PyUntypedArray};
 use pco::data_types::NumberType;
-use pco::match_number_enum;
 use pyo3::prelude::*;
 use pyo3::types::{PyBytes, PyModule};
 use pyo3::{pyclass, pymethods, Bound, PyResult, Python};
@@ -8,7 +7,9 @@ use pyo3::{pyclass, pymethods, Bound, PyResult, Python};
 use pco::data_types::Number;
 use pco::wrapped::{ChunkDecompressor, FileDecompressor};
 
-use crate::utils::{core_dtype_from_str, pco_err_to_py};
+use crate::utils::{
+  core_dtype_from_str, define_py_number_enum, match_py_number_enum, pco_err_to_py,
+};
 use crate::PyProgress;
 
 /// The top-level object for decompressing wrapped pcodec files.
@@ -16,7 +17,7 @@ use crate::PyProgress;
 #[pyclass(name = "FileDecompressor")]
 struct PyFd(FileDecompressor);
 
-pco::define_number_enum!(
+define_py_number_enum!(
   #[derive()]
   DynCd(ChunkDecompressor)
 );
@@ -60,7 +61,7 @@ impl PyFd {
     let fd = &self.0;
     let dtype = core_dtype_from_str(dtype)?;
 
-    let (inner, rest) = match_number_enum!(
+    let (inner, rest) = match_py_number_enum!(
       dtype,
       NumberType<T> => {
         let (generic_cd, rest) = fd
@@ -106,7 +107,7 @@ impl PyCd {
   ) -> PyResult<(PyProgress, usize)> {
     let src = src.as_bytes();
 
-    let (progress, rest) = match_number_enum!(
+    let (progress, rest) = match_py_number_enum!(
       &self.0,
       DynCd<T>(cd) => {
         let arr = dst.downcast::<PyArray1<T>>()?;
@@ -4,7 +4,7 @@ from pcodec import ChunkConfig, PagingSpec
 from pcodec.wrapped import FileCompressor, FileDecompressor
 
 np.random.seed(12345)
-all_dtypes = ("f2", "f4", "f8", "i2", "i4", "i8", "u2", "u4", "u8")
+all_dtypes = ("f2", "f4", "f8", "i1", "i2", "i4", "i8", "u1", "u2", "u4", "u8")
 
 
 @pytest.mark.parametrize("dtype", all_dtypes)